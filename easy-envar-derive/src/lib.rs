@@ -0,0 +1,457 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, Fields, GenericArgument, Lit, LitStr, PathArguments, Type};
+
+
+/// Derives a `from_env()` constructor that loads every field from the environment.
+///
+/// Each field is loaded from the environment variable named after the field
+/// in `SCREAMING_SNAKE_CASE`, then parsed into the field's type like the matching `Envar` variant,
+/// such as `Envar::Bool` for `bool`, or else via `FromStr`.
+/// Fields are declared as an `EnvarSpec` and loaded like any other variable,
+/// so their origins are recorded and their errors match those of `EnvarSet`.
+/// Every field is attempted, and all failures are returned together.
+///
+/// The naming rule can be changed with `#[envar(rename_all = "...")]` on the struct, to one of
+/// `SCREAMING_SNAKE_CASE` (the default), `snake_case`, `camelCase`, or `PascalCase`,
+/// and a single field can be renamed with `#[envar(name = "...")]`.
+///
+/// Fields accept these attributes to control loading and validation:
+///
+/// - `#[envar(default = 8080)]`: the value used when the variable is not set.
+/// - `#[envar(optional)]`: the field is an `Option`, which is `None` when the variable is not set.
+/// - `#[envar(range = "1024..=65535")]`: the range the parsed value must lie in.
+/// - `#[envar(secret)]`: values are masked as `***` in errors. The field must be an `easy_envar::Secret`
+///   (or an `Option` of one), so printing the struct with `Debug` masks it too.
+/// - `#[envar(number_format = "human")]`: integers may be spelled like `1_000_000` or `0x1F` (see `NumberFormat`).
+///
+/// `from_env_prefixed(prefix)` loads every variable under `prefix` instead.
+/// `from_env()` uses the prefix set with `#[envar(prefix = "...")]` on the struct, if any.
+/// A field marked `#[envar(nested)]` is loaded as a nested struct, which must derive `Envar` too,
+/// from the variables prefixed with the field's name and a separator. The separator is `__`
+/// unless set with `#[envar(separator = "...")]` on the outer struct, so `APP__DATABASE__HOST`
+/// maps to the `host` field of the `database` field when loading with the `APP__` prefix.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::Envar;
+///
+/// #[derive(Envar)]
+/// struct Config {
+///     derive_doc_host: String,
+///     derive_doc_port: u16,
+///     derive_doc_secure: bool,
+/// }
+///
+/// std::env::set_var("DERIVE_DOC_HOST", "localhost");
+/// std::env::set_var("DERIVE_DOC_PORT", "8080");
+/// std::env::set_var("DERIVE_DOC_SECURE", "true");
+///
+/// let config = Config::from_env().unwrap();
+///
+/// assert_eq!(config.derive_doc_host, "localhost");
+/// assert_eq!(config.derive_doc_port, 8080);
+/// assert!(config.derive_doc_secure);
+/// ```
+///
+/// Defaults and validation:
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarError, Secret};
+///
+/// #[derive(Envar)]
+/// #[envar(prefix = "FIELD_DOC_")]
+/// struct Config {
+///     #[envar(default = 8080, range = "1024..=65535")]
+///     port: u16,
+///     #[envar(optional)]
+///     telemetry_key: Option<String>,
+///     #[envar(secret)]
+///     api_key: Secret,
+/// }
+///
+/// std::env::set_var("FIELD_DOC_API_KEY", "hunter2");
+///
+/// let config = Config::from_env().unwrap();
+///
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(config.telemetry_key, None);
+/// assert_eq!(config.api_key.expose(), "hunter2");
+/// assert_eq!(format!("{:?}", config.api_key), "***");
+///
+/// std::env::set_var("FIELD_DOC_PORT", "80");
+///
+/// let errors = Config::from_env().err().unwrap();
+///
+/// assert!(matches!(&errors[0], EnvarError::ConstraintViolation { constraint, .. } if constraint == "must be in range 1024..=65535"));
+/// ```
+///
+/// Fields are parsed like the matching `Envar` variant, so a `bool` accepts `yes` and `on`,
+/// and numbers are trimmed:
+///
+/// ```rust
+/// use easy_envar::Envar;
+///
+/// #[derive(Envar)]
+/// #[envar(prefix = "PARSE_DOC_")]
+/// struct Config {
+///     secure: bool,
+///     port: u16,
+///     #[envar(number_format = "human")]
+///     max_body: u64,
+/// }
+///
+/// std::env::set_var("PARSE_DOC_SECURE", "yes");
+/// std::env::set_var("PARSE_DOC_PORT", " 8080 ");
+/// std::env::set_var("PARSE_DOC_MAX_BODY", "1_000_000");
+///
+/// let config = Config::from_env().unwrap();
+///
+/// assert!(config.secure);
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(config.max_body, 1_000_000);
+/// ```
+///
+/// A `secret` field of any other type is rejected, since its value would be printed in plain text:
+///
+/// ```rust,compile_fail
+/// use easy_envar::Envar;
+///
+/// #[derive(Envar)]
+/// struct Config {
+///     #[envar(secret)]
+///     api_key: String,
+/// }
+/// ```
+///
+/// Renaming:
+///
+/// ```rust
+/// use easy_envar::Envar;
+///
+/// #[derive(Envar)]
+/// #[envar(prefix = "RENAME_DOC_", rename_all = "PascalCase")]
+/// struct Config {
+///     max_connections: u32,
+///     #[envar(name = "BIND_PORT")]
+///     port: u16,
+/// }
+///
+/// std::env::set_var("RENAME_DOC_MaxConnections", "16");
+/// std::env::set_var("RENAME_DOC_BIND_PORT", "8080");
+///
+/// let config = Config::from_env().unwrap();
+///
+/// assert_eq!(config.max_connections, 16);
+/// assert_eq!(config.port, 8080);
+/// ```
+///
+/// Nested structs:
+///
+/// ```rust
+/// use easy_envar::Envar;
+///
+/// #[derive(Envar)]
+/// struct Database {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Envar)]
+/// struct Config {
+///     #[envar(nested)]
+///     database: Database,
+/// }
+///
+/// std::env::set_var("NESTED_DOC__DATABASE__HOST", "db");
+/// std::env::set_var("NESTED_DOC__DATABASE__PORT", "5432");
+///
+/// let config = Config::from_env_prefixed("NESTED_DOC__").unwrap();
+///
+/// assert_eq!(config.database.host, "db");
+/// assert_eq!(config.database.port, 5432);
+/// ```
+#[proc_macro_derive(Envar, attributes(envar))]
+pub fn derive_envar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+
+/// Options set with `#[envar(...)]` on the struct.
+struct StructOptions {
+    separator: String,
+    prefix: String,
+    rename_all: RenameRule,
+}
+
+
+/// Options set with `#[envar(...)]` on a field.
+struct FieldOptions {
+    nested: bool,
+    name: Option<String>,
+    default: Option<String>,
+    optional: bool,
+    range: Option<LitStr>,
+    secret: bool,
+    human_numbers: bool,
+}
+
+
+/// How field names are turned into environment variable names.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    ScreamingSnake,
+    Snake,
+    Camel,
+    Pascal,
+}
+
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "snake_case" => Ok(RenameRule::Snake),
+            "camelCase" => Ok(RenameRule::Camel),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            _ => Err(syn::Error::new_spanned(lit, "expected one of `SCREAMING_SNAKE_CASE`, `snake_case`, `camelCase`, or `PascalCase`")),
+        }
+    }
+
+    /// Applies the rule to a `snake_case` field name.
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::ScreamingSnake => field.to_uppercase(),
+            RenameRule::Snake => field.to_string(),
+            RenameRule::Camel | RenameRule::Pascal => {
+                let mut name = String::new();
+
+                for (i, word) in field.split('_').filter(|word| !word.is_empty()).enumerate() {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) if i > 0 || matches!(self, RenameRule::Pascal) => {
+                            name.extend(first.to_uppercase());
+                            name.push_str(chars.as_str());
+                        },
+                        _ => name.push_str(word),
+                    }
+                }
+
+                name
+            },
+        }
+    }
+}
+
+
+fn struct_options(attrs: &[Attribute]) -> syn::Result<StructOptions> {
+    let mut options = StructOptions {
+        separator: "__".to_string(),
+        prefix: String::new(),
+        rename_all: RenameRule::ScreamingSnake,
+    };
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("envar")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("separator") {
+                options.separator = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("prefix") {
+                options.prefix = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("rename_all") {
+                options.rename_all = RenameRule::parse(&meta.value()?.parse()?)?;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `envar` attribute on a struct"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+
+fn field_options(attrs: &[Attribute]) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        nested: false,
+        name: None,
+        default: None,
+        optional: false,
+        range: None,
+        secret: false,
+        human_numbers: false,
+    };
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("envar")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                options.nested = true;
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                options.default = Some(match meta.value()?.parse::<Expr>()? {
+                    Expr::Lit(lit) => match lit.lit {
+                        Lit::Str(lit) => lit.value(),
+                        lit => quote!(#lit).to_string(),
+                    },
+                    Expr::Unary(expr) => quote!(#expr).to_string().replace(' ', ""),
+                    expr => return Err(syn::Error::new_spanned(expr, "expected a literal")),
+                });
+                Ok(())
+            } else if meta.path.is_ident("optional") {
+                options.optional = true;
+                Ok(())
+            } else if meta.path.is_ident("range") {
+                options.range = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("secret") {
+                options.secret = true;
+                Ok(())
+            } else if meta.path.is_ident("number_format") {
+                let format = meta.value()?.parse::<LitStr>()?;
+                options.human_numbers = match format.value().as_str() {
+                    "human" => true,
+                    "strict" => false,
+                    _ => return Err(syn::Error::new_spanned(format, "expected `human` or `strict`")),
+                };
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `envar` attribute on a field"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+
+/// Returns whether `ty` is `Secret`, by name, since the macro can't resolve types.
+fn is_secret(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| segment.ident == "Secret"),
+        _ => false,
+    }
+}
+
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "`Envar` can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "`Envar` can only be derived for structs")),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let idents: Vec<_> = fields.iter().map(|field| field.ident.as_ref().unwrap()).collect();
+
+    let options = struct_options(&input.attrs)?;
+    let separator = &options.separator;
+    let prefix = &options.prefix;
+
+    let loads = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let field_options = field_options(&field.attrs)?;
+        let key = match field_options.name {
+            Some(name) => name,
+            None => options.rename_all.apply(ident.to_string().trim_start_matches("r#")),
+        };
+
+        if field_options.nested {
+            return Ok(quote! {
+                let #ident = <#ty>::from_env_prefixed(&::std::format!("{}{}{}", prefix, #key, #separator))
+                    .map_err(|e| errors.extend(e))
+                    .ok();
+            });
+        }
+
+        let inner = match field_options.optional {
+            true => option_inner(ty).ok_or_else(|| syn::Error::new_spanned(ty, "`optional` fields must be an `Option`"))?,
+            false => ty,
+        };
+
+        let default = field_options.default.iter();
+        if field_options.secret && !is_secret(inner) {
+            return Err(syn::Error::new_spanned(ty, "`secret` fields must be an `easy_envar::Secret`, so `Debug` doesn't print the value"));
+        }
+
+        let secret = field_options.secret.then(|| quote!(.secret()));
+        let number_format = field_options.human_numbers.then(|| quote!(.number_format(::easy_envar::NumberFormat::human())));
+        let range = field_options.range.iter().map(LitStr::parse::<Expr>).collect::<syn::Result<Vec<_>>>()?;
+
+        let load = match field_options.optional {
+            true => quote!(load_optional),
+            false => quote!(load),
+        };
+
+        Ok(quote! {
+            let #ident = ::easy_envar::__private::#load::<#inner>(prefix, #key, |spec| spec
+                #(.or_default(#default))*
+                #secret
+                #number_format
+                #(.range::<#inner, _>(#range))*
+            )
+                .map_err(|e| errors.push(e))
+                .ok();
+        })
+    }).collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Loads every field from its environment variable.
+            pub fn from_env() -> ::std::result::Result<Self, ::std::vec::Vec<::easy_envar::EnvarError>> {
+                Self::from_env_prefixed(#prefix)
+            }
+
+            /// Loads every field from its environment variable, prefixed with `prefix`.
+            pub fn from_env_prefixed(prefix: &str) -> ::std::result::Result<Self, ::std::vec::Vec<::easy_envar::EnvarError>> {
+                let mut errors = ::std::vec::Vec::new();
+
+                #(#loads)*
+
+                if !errors.is_empty() {
+                    return ::std::result::Result::Err(errors);
+                }
+
+                ::std::result::Result::Ok(Self {
+                    #(#idents: #idents.unwrap(),)*
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,146 @@
+use crate::{EnvarError, LoadedEnvar};
+
+
+/// A report comparing the declared environment variables against the actual environment.
+///
+/// Created by `EnvarSet::audit`. Its `Display` output is a human-readable summary,
+/// suitable for printing as a preflight check in CI. Values are never included,
+/// except in the messages of errors raised by non-secret variables.
+#[derive(Debug)]
+pub struct AuditReport {
+    entries: Vec<AuditEntry>,
+    unused: Vec<String>,
+}
+
+
+/// The audited state of a single declared environment variable.
+#[derive(Debug)]
+pub struct AuditEntry {
+    key: String,
+    matched_key: Option<String>,
+    description: Option<String>,
+    status: AuditStatus,
+}
+
+
+/// The state of a declared environment variable in the actual environment.
+#[derive(Debug)]
+pub enum AuditStatus {
+    /// The variable is set, and loads successfully.
+    Present,
+
+    /// The variable is set to an empty or whitespace-only value, which still loads successfully.
+    Empty,
+
+    /// The variable is not set, and its default is used instead.
+    Defaulted,
+
+    /// The variable is not set, has no default, and is skipped because of `LoadLevel::Warn`.
+    Skipped,
+
+    /// The variable is not set, and has no default.
+    Missing,
+
+    /// The variable is set, but fails to load.
+    Invalid(EnvarError),
+}
+
+
+impl AuditReport {
+    /// Creates a report from its entries and the unused `.env` keys.
+    pub(crate) fn new(entries: Vec<AuditEntry>, unused: Vec<String>) -> Self {
+        AuditReport { entries, unused }
+    }
+
+    /// Returns the audited declared variables, in declaration order.
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    /// Returns the `.env` keys that no declared variable reads, in file order.
+    pub fn unused(&self) -> &[String] {
+        &self.unused
+    }
+
+    /// Returns whether no variable is missing or invalid, and every `.env` key is used.
+    pub fn is_ok(&self) -> bool {
+        self.unused.is_empty() && self.entries.iter().all(|entry| {
+            !matches!(entry.status, AuditStatus::Missing | AuditStatus::Invalid(_))
+        })
+    }
+}
+
+
+impl AuditEntry {
+    /// Classifies the result of loading a variable whose raw value was `raw`.
+    pub(crate) fn new(key: String, description: Option<&str>, raw: Result<(String, String), EnvarError>, loaded: Result<Option<LoadedEnvar>, EnvarError>) -> Self {
+        let matched_key = match &raw {
+            Ok((matched_key, _)) if *matched_key != key => Some(matched_key.clone()),
+            _ => None,
+        };
+
+        let status = match (raw.map(|(_, raw)| raw), loaded) {
+            (_, Ok(None)) => AuditStatus::Skipped,
+            (_, Err(EnvarError::Missing { .. })) => AuditStatus::Missing,
+            (_, Err(e)) => AuditStatus::Invalid(e),
+            (Err(_), Ok(Some(_))) => AuditStatus::Defaulted,
+            (Ok(raw), Ok(Some(_))) if raw.trim().is_empty() => AuditStatus::Empty,
+            (Ok(_), Ok(Some(_))) => AuditStatus::Present,
+        };
+
+        AuditEntry { key, matched_key, description: description.map(str::to_string), status }
+    }
+
+    /// Returns the name the variable was looked up under, including any prefix.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the deprecated alias or fallback key the value was read from,
+    /// if it was not read from the variable's own name.
+    pub fn matched_key(&self) -> Option<&str> {
+        self.matched_key.as_deref()
+    }
+
+    /// Returns the variable's description, if one was attached with `describe`.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the state of the variable.
+    pub fn status(&self) -> &AuditStatus {
+        &self.status
+    }
+}
+
+
+impl std::fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            let mut label = entry.key.clone();
+
+            if let Some(matched_key) = &entry.matched_key {
+                label.push_str(&format!(" from {}", matched_key));
+            }
+
+            if let Some(description) = &entry.description {
+                label.push_str(&format!(" ({})", description));
+            }
+
+            match &entry.status {
+                AuditStatus::Present => writeln!(f, "present    {}", label)?,
+                AuditStatus::Empty => writeln!(f, "empty      {}", label)?,
+                AuditStatus::Defaulted => writeln!(f, "defaulted  {}", label)?,
+                AuditStatus::Skipped => writeln!(f, "skipped    {}", label)?,
+                AuditStatus::Missing => writeln!(f, "missing    {}", label)?,
+                AuditStatus::Invalid(e) => writeln!(f, "invalid    {}: {}", entry.key, e)?,
+            }
+        }
+
+        for key in &self.unused {
+            writeln!(f, "unused     {}", key)?;
+        }
+
+        Ok(())
+    }
+}
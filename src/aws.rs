@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::process::Command;
+
+use crate::remote::{env_context, not_installed, parallel, Failure, RemoteErrorKind};
+
+
+/// The most secrets `batch-get-secret-value` returns per call.
+const BATCH_SIZE: usize = 20;
+
+
+/// Fetches secrets from AWS Secrets Manager with the `aws` command, twenty per call and up to `concurrency` calls at once.
+///
+/// Credentials and the region are resolved by the command as usual,
+/// from `AWS_PROFILE`, `AWS_REGION`, instance metadata, and so on.
+/// Each id may be a secret's name or its ARN.
+pub(crate) fn fetch(ids: &[&str], concurrency: usize) -> HashMap<String, Result<String, Failure>> {
+    let batches: Vec<&[&str]> = ids.chunks(BATCH_SIZE).collect();
+    let mut values = HashMap::new();
+
+    for (batch, response) in batches.iter().zip(parallel(&batches, concurrency, |batch| batch_get(batch))) {
+        match response {
+            Ok(response) => values.extend(response),
+            Err(failure) => values.extend(batch.iter().map(|id| (id.to_string(), Err(failure.clone())))),
+        }
+    }
+
+    values
+}
+
+
+/// Returns the profile, region, and access key the `aws` command picks up from the environment.
+pub(crate) fn context() -> String {
+    env_context(&["AWS_PROFILE", "AWS_DEFAULT_PROFILE", "AWS_REGION", "AWS_DEFAULT_REGION", "AWS_ACCESS_KEY_ID"])
+}
+
+
+/// Fetches one batch of secrets, reporting each secret the response doesn't include as not found.
+fn batch_get(ids: &[&str]) -> Result<HashMap<String, Result<String, Failure>>, Failure> {
+    let output = Command::new("aws")
+        .args(["secretsmanager", "batch-get-secret-value", "--output", "json", "--secret-id-list"])
+        .args(ids)
+        .output()
+        .map_err(|e| not_installed("aws", "AWS CLI", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Failure { kind: classify(&stderr), message: stderr.trim().to_string() });
+    }
+
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout).map_err(|e| Failure {
+        kind: RemoteErrorKind::Unavailable,
+        message: format!("unexpected response from `aws`: {}", e),
+    })?;
+
+    let mut values = HashMap::new();
+
+    for secret in response["SecretValues"].as_array().into_iter().flatten() {
+        let id = ids.iter().find(|id| secret["Name"].as_str() == Some(**id) || secret["ARN"].as_str() == Some(**id));
+        let Some(id) = id else { continue };
+
+        let value = match secret["SecretString"].as_str() {
+            Some(val) => Ok(val.to_string()),
+            None => Err(Failure {
+                kind: RemoteErrorKind::Unavailable,
+                message: "the secret has no string value, binary secrets are not supported".to_string(),
+            }),
+        };
+        values.insert(id.to_string(), value);
+    }
+
+    for error in response["Errors"].as_array().into_iter().flatten() {
+        let Some(id) = error["SecretId"].as_str() else { continue };
+        let code = error["ErrorCode"].as_str().unwrap_or_default();
+        let message = error["Message"].as_str().unwrap_or(code);
+
+        values.insert(id.to_string(), Err(Failure { kind: classify(code), message: message.to_string() }));
+    }
+
+    for id in ids {
+        values.entry(id.to_string()).or_insert_with(|| Err(Failure {
+            kind: RemoteErrorKind::NotFound,
+            message: "secret not found".to_string(),
+        }));
+    }
+
+    Ok(values)
+}
+
+
+/// Tells authorization failures and missing secrets apart from an AWS error code or message.
+fn classify(error: &str) -> RemoteErrorKind {
+    const UNAUTHORIZED: [&str; 6] = [
+        "AccessDenied",
+        "UnrecognizedClient",
+        "InvalidClientTokenId",
+        "ExpiredToken",
+        "DecryptionFailure",
+        "Unable to locate credentials",
+    ];
+
+    if UNAUTHORIZED.iter().any(|code| error.contains(code)) {
+        RemoteErrorKind::Unauthorized
+    } else if error.contains("ResourceNotFound") {
+        RemoteErrorKind::NotFound
+    } else {
+        RemoteErrorKind::Unavailable
+    }
+}
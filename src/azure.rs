@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::process::Command;
+
+use crate::remote::{env_context, not_installed, parallel, Failure, RemoteErrorKind};
+
+
+/// The variable naming the key vault of secrets declared without one.
+const VAULT_VAR: &str = "EASY_ENVAR_AZURE_VAULT";
+
+
+/// Fetches secrets from Azure Key Vault with the `az` command, one call per secret and up to `concurrency` calls at once.
+///
+/// Each id is `vault/secret`, or a secret name in the key vault named by `EASY_ENVAR_AZURE_VAULT`.
+/// Credentials are resolved by the command as usual, from `az login` or a managed identity.
+pub(crate) fn fetch(ids: &[&str], concurrency: usize) -> HashMap<String, Result<String, Failure>> {
+    let values = parallel(ids, concurrency, |id| show(id));
+    ids.iter().map(|id| id.to_string()).zip(values).collect()
+}
+
+
+/// Returns the key vault that secrets declared without one are read from.
+pub(crate) fn context() -> String {
+    env_context(&[VAULT_VAR])
+}
+
+
+/// Reads the current version of one secret.
+fn show(id: &str) -> Result<String, Failure> {
+    let (vault, name) = match id.split_once('/') {
+        Some((vault, name)) => (vault.to_string(), name),
+        None => match std::env::var(VAULT_VAR) {
+            Ok(vault) => (vault, id),
+            Err(_) => return Err(Failure {
+                kind: RemoteErrorKind::Unavailable,
+                message: format!("no key vault given as `vault/{}`, and `{}` is not set", id, VAULT_VAR),
+            }),
+        },
+    };
+
+    let output = Command::new("az")
+        .args(["keyvault", "secret", "show", "--query", "value", "--output", "json"])
+        .args(["--vault-name", &vault, "--name", name])
+        .output()
+        .map_err(|e| not_installed("az", "Azure CLI", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Failure { kind: classify(&stderr), message: stderr.trim().to_string() });
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|e| Failure {
+        kind: RemoteErrorKind::Unavailable,
+        message: format!("unexpected response from `az`: {}", e),
+    })
+}
+
+
+/// Tells authentication failures and missing secrets apart from the command's error output.
+fn classify(stderr: &str) -> RemoteErrorKind {
+    const UNAUTHORIZED: [&str; 4] = ["Forbidden", "AuthorizationFailed", "Unauthorized", "az login"];
+
+    if UNAUTHORIZED.iter().any(|code| stderr.contains(code)) {
+        RemoteErrorKind::Unauthorized
+    } else if stderr.contains("SecretNotFound") || stderr.contains("NotFound") {
+        RemoteErrorKind::NotFound
+    } else {
+        RemoteErrorKind::Unavailable
+    }
+}
@@ -0,0 +1,185 @@
+//! Checks `.env` files against declared environment variables, for use in CI,
+//! and compares environments against each other.
+//!
+//! `check` exits with 0 if the file is valid, 1 if a variable is missing, invalid, or unknown,
+//! and 2 if the files could not be read. `diff` exits with 0 if the environments agree,
+//! 1 if they differ, and 2 if the files could not be read.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{value_parser, Arg, ArgMatches, Command};
+use easy_envar::{DotenvFile, EnvarSet, ProcessEnv, Source};
+
+
+fn main() -> ExitCode {
+    let matches = Command::new("easy-envar")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Checks .env files against declared environment variables")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("check")
+                .about("Reports missing, invalid, and unknown variables in a .env file")
+                .arg(Arg::new("env")
+                    .long("env")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .default_value(".env")
+                    .help("The .env file to check"))
+                .arg(Arg::new("manifest")
+                    .long("manifest")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("An envar.toml manifest declaring the variables [default: envar.toml]"))
+                .arg(Arg::new("declarations")
+                    .long("declarations")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .conflicts_with("manifest")
+                    .help("A .env template written by `write_example` [default: .env.example]"))
+                .arg(Arg::new("build-script")
+                    .long("build-script")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .conflicts_with_all(["manifest", "declarations"])
+                    .help("A build script to scan for declarations [default: build.rs]")),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Shows variables added, removed, or changed between two environments, with secrets masked")
+                .arg(Arg::new("left")
+                    .value_name("LEFT")
+                    .value_parser(value_parser!(PathBuf))
+                    .required(true)
+                    .help("A .env file, or the process environment if RIGHT is omitted"))
+                .arg(Arg::new("right")
+                    .value_name("RIGHT")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("The .env file to compare against LEFT"))
+                .arg(Arg::new("manifest")
+                    .long("manifest")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Only compare the variables declared in an envar.toml manifest"))
+                .arg(Arg::new("declarations")
+                    .long("declarations")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .conflicts_with("manifest")
+                    .help("Only compare the variables declared in a .env template written by `write_example`"))
+                .arg(Arg::new("build-script")
+                    .long("build-script")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .conflicts_with_all(["manifest", "declarations"])
+                    .help("Only compare the variables declared in a build script")),
+        )
+        .get_matches();
+
+    let result = match matches.subcommand() {
+        Some(("check", matches)) => check(matches),
+        Some(("diff", matches)) => diff(matches),
+        _ => unreachable!("a subcommand is required"),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::from(2)
+        },
+    }
+}
+
+
+/// Checks the `.env` file, printing the report and returning whether it is valid.
+fn check(matches: &ArgMatches) -> Result<ExitCode, String> {
+    let set = declarations(matches)?;
+    let path = matches.get_one::<PathBuf>("env").expect("has a default");
+    let dotenv = dotenv(path)?;
+
+    let report = set.audit_from(&dotenv, Some(&dotenv));
+    print!("{}", report);
+
+    if report.is_ok() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        eprintln!("error: {} does not match the declared variables", path.display());
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+
+/// Compares two `.env` files, or the process environment and a `.env` file,
+/// printing the differences and returning whether there were any.
+///
+/// Without declarations, comparing two files compares every variable either sets,
+/// while comparing the process environment only compares the variables the file sets,
+/// so the rest of the environment, such as `PATH`, isn't reported as removed.
+fn diff(matches: &ArgMatches) -> Result<ExitCode, String> {
+    let left = matches.get_one::<PathBuf>("left").expect("is required");
+
+    let (left, right): (Box<dyn Source>, DotenvFile) = match matches.get_one::<PathBuf>("right") {
+        Some(right) => (Box::new(dotenv(left)?), dotenv(right)?),
+        None => {
+            let right = dotenv(left)?;
+            let env: HashMap<String, String> = right.names().into_iter()
+                .filter_map(|key| ProcessEnv.var(&key).ok().map(|val| (key, val)))
+                .collect();
+            (Box::new(env), right)
+        },
+    };
+
+    let declared = ["manifest", "declarations", "build-script"].iter().any(|id| matches.contains_id(id));
+    let diff = if declared {
+        declarations(matches)?.diff(left.as_ref(), &right)
+    } else {
+        easy_envar::diff(left.as_ref(), &right)
+    };
+
+    print!("{}", diff);
+
+    if diff.is_empty() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+
+/// Reads the declared variables from the given manifest, template, or build script,
+/// or else from `envar.toml`, `.env.example`, or `build.rs` in the current directory.
+fn declarations(matches: &ArgMatches) -> Result<EnvarSet<'static>, String> {
+    if let Some(path) = matches.get_one::<PathBuf>("manifest") {
+        return EnvarSet::from_manifest(path).map_err(|e| e.to_string());
+    }
+    if let Some(path) = matches.get_one::<PathBuf>("declarations") {
+        return read(path).map(|contents| EnvarSet::from_example(&contents));
+    }
+    if let Some(path) = matches.get_one::<PathBuf>("build-script") {
+        return read(path).map(|contents| EnvarSet::from_build_script(&contents));
+    }
+
+    if Path::new("envar.toml").is_file() {
+        EnvarSet::from_manifest("envar.toml").map_err(|e| e.to_string())
+    } else if Path::new(".env.example").is_file() {
+        read(Path::new(".env.example")).map(|contents| EnvarSet::from_example(&contents))
+    } else if Path::new("build.rs").is_file() {
+        read(Path::new("build.rs")).map(|contents| EnvarSet::from_build_script(&contents))
+    } else {
+        Err("no declarations found: pass --manifest, --declarations, or --build-script".to_string())
+    }
+}
+
+
+/// Reads a `.env` file, describing any failure with its path.
+fn dotenv(path: &Path) -> Result<DotenvFile, String> {
+    DotenvFile::from_path(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+
+/// Reads a file, describing any failure with its path.
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
@@ -0,0 +1,32 @@
+/// Parses a human-friendly byte count such as `10MB`, `512KiB`, `1G`, or `4096`.
+///
+/// A value is a whole number, optionally followed by a unit. The units `KB`, `MB`, `GB`, and `TB`
+/// are powers of 1000, while `KiB`, `MiB`, `GiB`, and `TiB`, as well as the single letters
+/// `K`, `M`, `G`, and `T`, are powers of 1024. Units are case-insensitive, and may be
+/// separated from the number by whitespace. A plain number, or the unit `B`, is a count of bytes.
+/// Returns `None` if the value is malformed or overflows.
+pub(crate) fn parse(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+
+    let digits = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    if digits == 0 {
+        return None;
+    }
+
+    let amount = raw[..digits].parse::<u64>().ok()?;
+
+    let multiplier: u64 = match raw[digits..].trim_start().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1000,
+        "mb" => 1000u64.pow(2),
+        "gb" => 1000u64.pow(3),
+        "tb" => 1000u64.pow(4),
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        _ => return None,
+    };
+
+    amount.checked_mul(multiplier)
+}
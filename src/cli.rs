@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use clap::{Arg, ArgMatches};
+
+use crate::{Envar, EnvarSet, EnvarSpec};
+
+
+/// Builds a command-line argument for a declared variable, such as `--database-url` for `DATABASE_URL`.
+///
+/// The argument's id is the unprefixed variable name. Values are taken as plain strings,
+/// and validated when the set is loaded. A boolean can also be passed as a bare flag, meaning `true`.
+pub(crate) fn arg(env_var: &EnvarSpec, prefix: &str) -> Arg {
+    let key = env_var.key();
+    let mut arg = Arg::new(key.to_string())
+        .long(key.to_lowercase().replace('_', "-"))
+        .value_name(key.to_string())
+        .num_args(1)
+        .required(false);
+
+    let mut help = match env_var.description() {
+        Some(description) => format!("{} ", description),
+        None => String::new(),
+    };
+    help.push_str(&format!("[env: {}{}]", prefix, key));
+    arg = arg.help(help);
+
+    if matches!(env_var.envar(), Envar::Bool(_)) {
+        arg = arg.num_args(0..=1).default_missing_value("true");
+    }
+
+    arg
+}
+
+
+/// Collects the values passed on the command line, keyed by the prefixed name they override.
+pub(crate) fn overrides(set: &EnvarSet, matches: &ArgMatches) -> HashMap<String, String> {
+    set.env_vars().iter()
+        .filter_map(|env_var| {
+            let val = matches.try_get_one::<String>(env_var.key()).ok().flatten()?;
+            Some((format!("{}{}", set.prefix(), env_var.key()), val.clone()))
+        })
+        .collect()
+}
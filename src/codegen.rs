@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+use crate::LoadedEnvar;
+
+
+/// Renders a loaded environment variable as a typed `pub const` item.
+///
+/// Values whose types cannot be built in a const context,
+/// such as URLs and versions, are rendered as `&str`.
+pub(crate) fn const_item(env_var: &LoadedEnvar) -> String {
+    let (ty, expr) = match env_var {
+        LoadedEnvar::Bool(_, val)       => ("bool", val.to_string()),
+        LoadedEnvar::U8(_, val)         => ("u8", val.to_string()),
+        LoadedEnvar::U16(_, val)        => ("u16", val.to_string()),
+        LoadedEnvar::U32(_, val)        => ("u32", val.to_string()),
+        LoadedEnvar::U64(_, val)        => ("u64", val.to_string()),
+        LoadedEnvar::U128(_, val)       => ("u128", val.to_string()),
+        LoadedEnvar::Usize(_, val)      => ("usize", val.to_string()),
+        LoadedEnvar::I8(_, val)         => ("i8", val.to_string()),
+        LoadedEnvar::I16(_, val)        => ("i16", val.to_string()),
+        LoadedEnvar::I32(_, val)        => ("i32", val.to_string()),
+        LoadedEnvar::I64(_, val)        => ("i64", val.to_string()),
+        LoadedEnvar::I128(_, val)       => ("i128", val.to_string()),
+        LoadedEnvar::Isize(_, val)      => ("isize", val.to_string()),
+        LoadedEnvar::F32(_, val)        => ("f32", float(*val as f64, format!("{:?}", val), "f32")),
+        LoadedEnvar::F64(_, val)        => ("f64", float(*val, format!("{:?}", val), "f64")),
+        LoadedEnvar::Char(_, val)       => ("char", format!("{:?}", val)),
+        LoadedEnvar::NonZeroU16(_, val) => ("::std::num::NonZeroU16", non_zero("NonZeroU16", val.get() as u64)),
+        LoadedEnvar::NonZeroU32(_, val) => ("::std::num::NonZeroU32", non_zero("NonZeroU32", val.get() as u64)),
+        LoadedEnvar::NonZeroU64(_, val) => ("::std::num::NonZeroU64", non_zero("NonZeroU64", val.get())),
+        LoadedEnvar::IpAddr(_, val)     => ("::std::net::IpAddr", ip_addr(val)),
+        LoadedEnvar::SocketAddr(_, val) => ("::std::net::SocketAddr", socket_addr(val)),
+        LoadedEnvar::Duration(_, val)   => ("::std::time::Duration", format!("::std::time::Duration::new({}, {})", val.as_secs(), val.subsec_nanos())),
+        LoadedEnvar::ByteSize(_, val)   => ("u64", val.to_string()),
+        LoadedEnvar::Percent(_, val)    => ("f64", format!("{:?}", val)),
+        LoadedEnvar::Hex(_, val)        => ("&[u8]", bytes(val)),
+        LoadedEnvar::List(_, val)       => ("&[&str]", format!("&{:?}", val)),
+        #[cfg(feature = "base64")]
+        LoadedEnvar::Base64(_, val)     => ("&[u8]", bytes(val)),
+        _ => ("&str", format!("{:?}", env_var.value_string())),
+    };
+
+    format!("pub const {}: {} = {};\n", ident(env_var.key()), ty, expr)
+}
+
+
+/// Turns an environment variable name into a valid Rust identifier.
+fn ident(key: &str) -> String {
+    let ident: String = key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    match ident.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => ident,
+        _ => format!("_{}", ident),
+    }
+}
+
+
+/// Renders a float literal, using the associated constants for non-finite values.
+fn float(val: f64, literal: String, ty: &str) -> String {
+    if val.is_nan() {
+        format!("{}::NAN", ty)
+    } else if val.is_infinite() {
+        format!("{}::{}", ty, if val > 0.0 { "INFINITY" } else { "NEG_INFINITY" })
+    } else {
+        literal
+    }
+}
+
+
+/// Renders a const expression building a non-zero integer.
+fn non_zero(ty: &str, val: u64) -> String {
+    format!("match ::std::num::{}::new({}) {{ Some(val) => val, None => unreachable!() }}", ty, val)
+}
+
+
+/// Renders a const expression building an IP address.
+fn ip_addr(val: &std::net::IpAddr) -> String {
+    match val {
+        std::net::IpAddr::V4(ip) => format!("::std::net::IpAddr::V4({})", ipv4(ip)),
+        std::net::IpAddr::V6(ip) => format!("::std::net::IpAddr::V6({})", ipv6(ip)),
+    }
+}
+
+
+/// Renders a const expression building a socket address.
+fn socket_addr(val: &std::net::SocketAddr) -> String {
+    match val {
+        std::net::SocketAddr::V4(addr) => format!(
+            "::std::net::SocketAddr::V4(::std::net::SocketAddrV4::new({}, {}))",
+            ipv4(addr.ip()), addr.port(),
+        ),
+        std::net::SocketAddr::V6(addr) => format!(
+            "::std::net::SocketAddr::V6(::std::net::SocketAddrV6::new({}, {}, {}, {}))",
+            ipv6(addr.ip()), addr.port(), addr.flowinfo(), addr.scope_id(),
+        ),
+    }
+}
+
+
+/// Renders a const expression building an IPv4 address.
+fn ipv4(ip: &std::net::Ipv4Addr) -> String {
+    let [a, b, c, d] = ip.octets();
+    format!("::std::net::Ipv4Addr::new({}, {}, {}, {})", a, b, c, d)
+}
+
+
+/// Renders a const expression building an IPv6 address.
+fn ipv6(ip: &std::net::Ipv6Addr) -> String {
+    let segments: Vec<String> = ip.segments().iter().map(|segment| format!("{:#x}", segment)).collect();
+    format!("::std::net::Ipv6Addr::new({})", segments.join(", "))
+}
+
+
+/// Renders a byte slice literal.
+fn bytes(val: &[u8]) -> String {
+    let mut out = String::from("&[");
+    for (i, byte) in val.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        let _ = write!(out, "{:#04x}", byte);
+    }
+    out.push(']');
+    out
+}
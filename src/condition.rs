@@ -0,0 +1,119 @@
+//! Conditions under which a variable is required, read from the variables Cargo sets for build scripts.
+
+
+/// A condition that must hold for a variable to be required.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Condition {
+    /// A feature of the crate being built is enabled, as reported by `CARGO_FEATURE_<NAME>`.
+    Feature(String),
+
+    /// The target has a cfg option set to a value, as reported by `CARGO_CFG_<NAME>`,
+    /// which lists the values separated by commas.
+    Cfg(String, String),
+
+    /// The build uses a profile, as reported by `PROFILE`.
+    Profile(Profile),
+}
+
+
+/// The Cargo profile a crate is built with, as reported to build scripts by the `PROFILE` variable.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::Profile;
+///
+/// std::env::set_var("PROFILE", "release");
+///
+/// assert_eq!(Profile::current(), Profile::Release);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// An unoptimized build, such as `cargo build` or `cargo test`.
+    Debug,
+
+    /// An optimized build, such as `cargo build --release`.
+    Release,
+}
+
+
+impl Condition {
+    /// Returns whether the condition holds for the current build.
+    pub(crate) fn holds(&self) -> bool {
+        match self {
+            Condition::Feature(feature) => std::env::var_os(feature_var(feature)).is_some(),
+            Condition::Cfg(name, value) => {
+                let values = std::env::var(format!("CARGO_CFG_{}", name.to_uppercase())).unwrap_or_default();
+                values.split(',').any(|set| set == value)
+            },
+            Condition::Profile(profile) => Profile::current() == *profile,
+        }
+    }
+
+    /// Parses a condition as displayed, such as ``feature `telemetry` `` or ``cfg `target_os = "linux"` ``.
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        if let Some(feature) = text.strip_prefix("feature `") {
+            return Some(Condition::Feature(feature.strip_suffix('`')?.to_string()));
+        }
+
+        if let Some(profile) = text.strip_prefix("profile `") {
+            return Profile::from_name(profile.strip_suffix('`')?).map(Condition::Profile);
+        }
+
+        let (name, value) = text.strip_prefix("cfg `")?.strip_suffix("\"`")?.split_once(" = \"")?;
+        Some(Condition::Cfg(name.to_string(), value.to_string()))
+    }
+}
+
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Feature(feature) => write!(f, "feature `{}`", feature),
+            Condition::Cfg(name, value) => write!(f, "cfg `{} = \"{}\"`", name, value),
+            Condition::Profile(profile) => write!(f, "profile `{}`", profile),
+        }
+    }
+}
+
+
+impl Profile {
+    /// Returns the profile of the current build: from `PROFILE` in a build script,
+    /// or else `Debug` if this crate was compiled with debug assertions, and `Release` otherwise.
+    ///
+    /// Cargo sets `PROFILE` to `release` for the `release` profile and profiles inheriting from it,
+    /// and to `debug` for every other profile.
+    pub fn current() -> Self {
+        match std::env::var("PROFILE").ok().and_then(|name| Profile::from_name(&name)) {
+            Some(profile) => profile,
+            None if cfg!(debug_assertions) => Profile::Debug,
+            None => Profile::Release,
+        }
+    }
+
+    /// Returns the profile named `debug` or `release`.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "debug" => Some(Profile::Debug),
+            "release" => Some(Profile::Release),
+            _ => None,
+        }
+    }
+}
+
+
+impl std::fmt::Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Profile::Debug => write!(f, "debug"),
+            Profile::Release => write!(f, "release"),
+        }
+    }
+}
+
+
+/// Returns the variable Cargo sets when `feature` is enabled, such as `CARGO_FEATURE_TLS_RUSTLS` for `tls-rustls`.
+fn feature_var(feature: &str) -> String {
+    format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"))
+}
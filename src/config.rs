@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::LoadedEnvar;
+
+
+/// Environment variables loaded at runtime, keyed by variable name.
+///
+/// Created by `EnvarSet::load_runtime`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{EnvarConfig, LoadedEnvar};
+///
+/// let config: EnvarConfig = [
+///     LoadedEnvar::String("HOST".into(), "localhost".to_string()),
+///     LoadedEnvar::U16("PORT".into(), 8080),
+/// ].into_iter().collect();
+///
+/// assert_eq!(config.get("PORT").and_then(LoadedEnvar::as_u16), Some(8080));
+/// assert_eq!(config.get_as::<String>("HOST").as_deref(), Some("localhost"));
+/// assert!(config.get("SECURE").is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EnvarConfig<'a> {
+    env_vars: HashMap<String, LoadedEnvar<'a>>,
+}
+
+
+impl<'a> EnvarConfig<'a> {
+    /// Returns the loaded variable with the given name.
+    pub fn get(&self, key: &str) -> Option<&LoadedEnvar<'a>> {
+        self.env_vars.get(key)
+    }
+
+    /// Returns the value of the variable with the given name, parsed into `T`.
+    ///
+    /// The value is parsed from its exported string form (see `LoadedEnvar::value_string`),
+    /// so this returns `None` if the variable is missing or doesn't parse into `T`.
+    pub fn get_as<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key)?.value_string().parse().ok()
+    }
+
+    /// Returns whether a variable with the given name was loaded.
+    pub fn contains(&self, key: &str) -> bool {
+        self.env_vars.contains_key(key)
+    }
+
+    /// Returns the number of loaded variables.
+    pub fn len(&self) -> usize {
+        self.env_vars.len()
+    }
+
+    /// Returns whether no variables were loaded.
+    pub fn is_empty(&self) -> bool {
+        self.env_vars.is_empty()
+    }
+
+    /// Returns an iterator over the loaded variables, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &LoadedEnvar<'a>> {
+        self.env_vars.values()
+    }
+}
+
+
+impl<'a> FromIterator<LoadedEnvar<'a>> for EnvarConfig<'a> {
+    fn from_iter<I: IntoIterator<Item = LoadedEnvar<'a>>>(iter: I) -> Self {
+        EnvarConfig {
+            env_vars: iter.into_iter().map(|env_var| (env_var.key().to_string(), env_var)).collect(),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Integration with the `config` crate, so a loaded `EnvarConfig` can be layered with other sources.
+
+use ::config::{ConfigError, Map, Source, Value, ValueKind};
+
+use crate::{EnvarConfig, LoadedEnvar};
+
+
+/// Provides the loaded variables to `config`, keyed by their lowercased name.
+///
+/// The variables have already been loaded and validated, so collecting never fails.
+/// Booleans and numbers become typed values, lists become arrays, and everything else,
+/// including secrets, becomes a string. Each value records the variable it came from as its origin,
+/// so `config` errors name the offending variable. Requires the `config` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarSet};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// std::env::set_var("CONFIG_SOURCE_DOC_PORT", "8080");
+///
+/// let env_vars = EnvarSet::with_prefix("CONFIG_SOURCE_DOC_")
+///     .var(Envar::u16("PORT"))
+///     .load_runtime()
+///     .unwrap();
+///
+/// let settings: Settings = config::Config::builder()
+///     .set_default("host", "localhost").unwrap()
+///     .set_default("port", 3000).unwrap()
+///     .add_source(env_vars)
+///     .build().unwrap()
+///     .try_deserialize().unwrap();
+///
+/// assert_eq!(settings.host, "localhost");
+/// assert_eq!(settings.port, 8080);
+/// ```
+impl Source for EnvarConfig<'static> {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        Ok(self.iter().map(|env_var| (env_var.key().to_lowercase(), value(env_var))).collect())
+    }
+}
+
+
+/// Converts a loaded variable into a `config` value, keeping booleans and numbers typed.
+fn value(env_var: &LoadedEnvar) -> Value {
+    let kind = match env_var {
+        LoadedEnvar::Bool(_, val)       => ValueKind::from(*val),
+        LoadedEnvar::U8(_, val)         => ValueKind::from(*val),
+        LoadedEnvar::U16(_, val)        => ValueKind::from(*val),
+        LoadedEnvar::U32(_, val)        => ValueKind::from(*val),
+        LoadedEnvar::U64(_, val)        => ValueKind::from(*val),
+        LoadedEnvar::U128(_, val)       => ValueKind::from(*val),
+        LoadedEnvar::Usize(_, val)      => ValueKind::from(*val as u64),
+        LoadedEnvar::I8(_, val)         => ValueKind::from(*val),
+        LoadedEnvar::I16(_, val)        => ValueKind::from(*val),
+        LoadedEnvar::I32(_, val)        => ValueKind::from(*val),
+        LoadedEnvar::I64(_, val)        => ValueKind::from(*val),
+        LoadedEnvar::I128(_, val)       => ValueKind::from(*val),
+        LoadedEnvar::Isize(_, val)      => ValueKind::from(*val as i64),
+        LoadedEnvar::F32(_, val)        => ValueKind::from(*val as f64),
+        LoadedEnvar::F64(_, val)        => ValueKind::from(*val),
+        LoadedEnvar::Percent(_, val)    => ValueKind::from(*val),
+        LoadedEnvar::ByteSize(_, val)   => ValueKind::from(*val),
+        LoadedEnvar::NonZeroU16(_, val) => ValueKind::from(val.get()),
+        LoadedEnvar::NonZeroU32(_, val) => ValueKind::from(val.get()),
+        LoadedEnvar::NonZeroU64(_, val) => ValueKind::from(val.get()),
+        LoadedEnvar::List(_, val)       => ValueKind::from(val.clone()),
+        _ => ValueKind::from(env_var.value_string()),
+    };
+
+    Value::new(Some(&format!("environment variable `{}`", env_var.key())), kind)
+}
@@ -0,0 +1,247 @@
+//! A `serde` deserializer reading a struct from environment variables, one per field.
+
+use serde::de::value::{SeqDeserializer, StrDeserializer, StringDeserializer};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+
+use crate::{EnvarError, ProcessEnv, Source};
+
+
+/// Deserializes `T` from the process environment.
+///
+/// Each field of `T` is read from the environment variable named after it in `SCREAMING_SNAKE_CASE`,
+/// so `database_url` is read from `DATABASE_URL`. Renamed fields use their serialized name,
+/// uppercased in the same way. Fields whose variable is not set are left to `serde`,
+/// so `Option` fields become `None`, fields marked `#[serde(default)]` take their default,
+/// and any other field is reported as `EnvarError::Missing`.
+///
+/// Values are parsed like `Envar` values: numbers and booleans are trimmed,
+/// booleans accept spellings such as `yes` and `1`, and sequences are comma-separated.
+/// Requires the `serde` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     from_env_doc_host: String,
+///     from_env_doc_port: u16,
+///     from_env_doc_features: Vec<String>,
+///     from_env_doc_timeout: Option<u64>,
+/// }
+///
+/// std::env::set_var("FROM_ENV_DOC_HOST", "localhost");
+/// std::env::set_var("FROM_ENV_DOC_PORT", "8080");
+/// std::env::set_var("FROM_ENV_DOC_FEATURES", "search,billing");
+///
+/// let config: Config = easy_envar::from_env().unwrap();
+///
+/// assert_eq!(config.from_env_doc_host, "localhost");
+/// assert_eq!(config.from_env_doc_port, 8080);
+/// assert_eq!(config.from_env_doc_features, ["search", "billing"]);
+/// assert_eq!(config.from_env_doc_timeout, None);
+/// ```
+pub fn from_env<T: DeserializeOwned>() -> Result<T, EnvarError> {
+    from_source(&ProcessEnv)
+}
+
+
+/// Deserializes `T` like `from_env`, but reads the values from `source`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::EnvarError;
+/// use easy_envar::testing::MockEnv;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let env = MockEnv::new().var("PORT", "http");
+///
+/// let result = easy_envar::from_source::<Config>(&env);
+///
+/// assert!(matches!(result, Err(EnvarError::ParseError { key, .. }) if key == "PORT"));
+/// ```
+pub fn from_source<T: DeserializeOwned>(source: &dyn Source) -> Result<T, EnvarError> {
+    T::deserialize(Env { source })
+}
+
+
+impl de::Error for EnvarError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        EnvarError::Deserialize { message: msg.to_string() }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        EnvarError::Missing { key: field.to_uppercase(), description: None, suggestion: None }
+    }
+}
+
+
+/// Deserializes a struct or map from every variable in a source.
+struct Env<'s> {
+    source: &'s dyn Source,
+}
+
+
+impl<'de> de::Deserializer<'de> for Env<'_> {
+    type Error = EnvarError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EnvarError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EnvarError> {
+        let names = self.source.names();
+        let entries = names.iter()
+            .map(|name| Ok((name.clone(), self.source.var(name)?)))
+            .collect::<Result<Vec<_>, EnvarError>>()?;
+
+        visitor.visit_map(de::value::MapDeserializer::new(entries.into_iter()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, EnvarError> {
+        visitor.visit_map(Fields { source: self.source, fields: fields.iter(), pending: None })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+
+/// Visits the fields of a struct whose variables are set.
+struct Fields<'s> {
+    source: &'s dyn Source,
+    fields: std::slice::Iter<'static, &'static str>,
+    pending: Option<Value>,
+}
+
+
+impl<'de> MapAccess<'de> for Fields<'_> {
+    type Error = EnvarError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, EnvarError> {
+        for field in self.fields.by_ref() {
+            let key = field.to_uppercase();
+
+            match self.source.var(&key) {
+                Err(EnvarError::Missing { .. }) => continue,
+                raw => {
+                    self.pending = Some(Value { key, raw: raw? });
+                    let field: StrDeserializer<EnvarError> = field.into_deserializer();
+                    return seed.deserialize(field).map(Some);
+                },
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, EnvarError> {
+        match self.pending.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::custom("value requested before key")),
+        }
+    }
+}
+
+
+/// Deserializes the raw value of a single variable.
+struct Value {
+    key: String,
+    raw: String,
+}
+
+
+impl Value {
+    /// Parses the trimmed value, reporting failures as `EnvarError::ParseError`.
+    fn parse<T: std::str::FromStr>(self, expected_type: &'static str) -> Result<T, EnvarError> {
+        crate::parse(&self.key, crate::trim_value(&self.raw).to_string(), expected_type)
+    }
+}
+
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EnvarError> {
+                visitor.$visit(self.parse::<$ty>(stringify!($ty))?)
+            }
+        )*
+    };
+}
+
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = EnvarError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EnvarError> {
+        visitor.visit_string(self.raw)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EnvarError> {
+        match crate::parse_bool(crate::trim_value(&self.raw)) {
+            Some(val) => visitor.visit_bool(val),
+            None => Err(EnvarError::ParseError { key: self.key, raw: self.raw, expected_type: "bool", description: None, location: None }),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_char => visit_char: char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EnvarError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, EnvarError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EnvarError> {
+        let key = self.key;
+        let items = crate::split(&self.raw, ',').into_iter().map(|raw| Value { key: key.clone(), raw });
+
+        visitor.visit_seq(SeqDeserializer::new(items))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, EnvarError> {
+        let raw: StringDeserializer<EnvarError> = self.raw.into_deserializer();
+        visitor.visit_enum(raw)
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+
+impl<'de> IntoDeserializer<'de, EnvarError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
@@ -0,0 +1,598 @@
+//! Reading declarations back from a `.env` template or a build script,
+//! for checking a `.env` file without compiling the project.
+
+use std::ops::Bound;
+
+use crate::condition::{Condition, Profile};
+use crate::{Envar, EnvarSet, EnvarSpec, LoadLevel, NumberFormat};
+#[cfg(feature = "toml")]
+use crate::EnvarError;
+
+
+impl EnvarSet<'static> {
+    /// Reads the declarations from a `.env` template, as written by `write_example`.
+    ///
+    /// Each variable's type, default, optionality, description, and constraints are read
+    /// from the comments above it. Unknown types are declared as `Envar::String`,
+    /// so they are still required, and constraints that can't be restored are skipped.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let path = std::env::temp_dir().join("from_example_doc.env.example");
+    ///
+    /// EnvarSet::new()
+    ///     .var(Envar::string("HOST").describe("the public host name"))
+    ///     .var(Envar::u16("PORT").or_default(8080).range(1024..=65535))
+    ///     .write_example(&path)
+    ///     .unwrap();
+    ///
+    /// let set = EnvarSet::from_example(&std::fs::read_to_string(&path).unwrap());
+    ///
+    /// assert_eq!(set.env_vars()[0].description(), Some("the public host name"));
+    /// assert!(matches!(set.env_vars()[1].envar(), Envar::U16(key) if key == "PORT"));
+    /// assert_eq!(set.env_vars()[1].default(), Some("8080"));
+    /// assert_eq!(set.env_vars()[1].constraints()[0].description(), "must be in range 1024..=65535");
+    /// ```
+    pub fn from_example(contents: &str) -> Self {
+        let mut set = EnvarSet::new();
+        let mut comments: Vec<&str> = Vec::new();
+
+        for line in contents.lines().map(str::trim) {
+            if let Some(comment) = line.strip_prefix('#') {
+                comments.push(comment.trim());
+                continue;
+            }
+
+            let Some((key, _)) = line.split_once('=') else {
+                comments.clear();
+                continue;
+            };
+
+            set = set.var(example_spec(key.trim(), &comments));
+            comments.clear();
+        }
+
+        set
+    }
+
+    /// Reads the declarations from the source of a build script, without compiling it.
+    ///
+    /// Every `Envar::u16("NAME")` or `Envar::U16("NAME".into())` expression is declared, together with the options chained onto it,
+    /// such as `or_default`, `warn_if_missing`, `secret`, `describe`, and `range`, when their arguments are literals.
+    /// The prefix is read from the first `EnvarSet::with_prefix("...")`.
+    /// Unknown variants are declared as `Envar::String`, and a default that isn't a literal
+    /// makes the variable optional.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet, LoadLevel};
+    ///
+    /// let build_rs = r#"
+    ///     let env_vars = EnvarSet::with_prefix("APP_")
+    ///         .var(Envar::u16("PORT").or_default(8080))
+    ///         .var(Envar::string("API_KEY").secret().describe("the billing API key"))
+    ///         .var(Envar::one_of("LOG_LEVEL", ["debug", "info"]).warn_if_missing())
+    ///         .var(Envar::non_zero_u16("WORKERS"));
+    /// "#;
+    ///
+    /// let set = EnvarSet::from_build_script(build_rs);
+    ///
+    /// assert_eq!(set.prefix(), "APP_");
+    /// assert_eq!(set.env_vars()[0].default(), Some("8080"));
+    /// assert!(set.env_vars()[1].is_secret());
+    /// assert!(matches!(set.env_vars()[2].envar(), Envar::OneOf(key, allowed) if key == "LOG_LEVEL" && *allowed == ["debug", "info"]));
+    /// assert_eq!(set.env_vars()[2].load_level(), LoadLevel::Warn);
+    /// assert!(matches!(set.env_vars()[3].envar(), Envar::NonZeroU16(key) if key == "WORKERS"));
+    /// ```
+    pub fn from_build_script(contents: &str) -> Self {
+        let prefix = contents.find("with_prefix(")
+            .and_then(|i| string_literal(contents[i + "with_prefix(".len()..].trim_start()))
+            .map(|(prefix, _)| prefix)
+            .unwrap_or_default();
+
+        let mut set = EnvarSet::with_prefix(prefix);
+
+        for (i, _) in contents.match_indices("Envar::") {
+            let preceded_by_ident = contents[..i].chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_');
+
+            if !preceded_by_ident {
+                if let Some(spec) = script_spec(&contents[i + "Envar::".len()..]) {
+                    set = set.var(spec);
+                }
+            }
+        }
+
+        set
+    }
+}
+
+
+#[cfg(feature = "toml")]
+impl EnvarSet<'static> {
+    /// Reads the declarations from an `envar.toml` manifest.
+    ///
+    /// The manifest has an optional `prefix`, and a `[[var]]` table per variable, in declaration order.
+    /// Each variable has a `name`, and optionally a `type` (a `type_name` such as `u16` or a variant name
+    /// such as `NonZeroU16`, `string` by default), a `default`, a `description`, and the options
+    /// `optional`, `required_if_feature`, `required_on = 'target_os = "linux"'`, `required_in = "release"`, `secret`, `aliases`, `fallbacks`, `case_insensitive`, `trim`, and `number_format = "human"`.
+    /// `one_of = [...]` declares an `Envar::OneOf`, and the constraints are `range = "1024..=65535"`,
+    /// `non_empty`, `strict_bool`, `must_exist`, `must_be_file`, `must_be_dir`, and `schemes = [...]`.
+    /// Requires the `toml` feature.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(EnvarSet)`: the declared variables.
+    /// - `Err(EnvarError::InvalidManifest)`: if the file can't be read, isn't valid TOML,
+    ///   or has an unknown field or type.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet, LoadLevel};
+    ///
+    /// let path = std::env::temp_dir().join("from_manifest_doc.toml");
+    /// std::fs::write(&path, r#"
+    ///     prefix = "APP_"
+    ///
+    ///     [[var]]
+    ///     name = "PORT"
+    ///     type = "u16"
+    ///     default = 8080
+    ///     range = "1024..=65535"
+    ///     description = "the HTTP port"
+    ///
+    ///     [[var]]
+    ///     name = "LOG_LEVEL"
+    ///     one_of = ["debug", "info"]
+    ///     optional = true
+    /// "#).unwrap();
+    ///
+    /// let set = EnvarSet::from_manifest(&path).unwrap();
+    ///
+    /// assert_eq!(set.prefix(), "APP_");
+    /// assert!(matches!(set.env_vars()[0].envar(), Envar::U16(key) if key == "PORT"));
+    /// assert_eq!(set.env_vars()[0].default(), Some("8080"));
+    /// assert_eq!(set.env_vars()[0].description(), Some("the HTTP port"));
+    /// assert!(matches!(set.env_vars()[1].envar(), Envar::OneOf(key, allowed) if key == "LOG_LEVEL" && *allowed == ["debug", "info"]));
+    /// assert_eq!(set.env_vars()[1].load_level(), LoadLevel::Warn);
+    /// ```
+    pub fn from_manifest(path: impl AsRef<std::path::Path>) -> Result<Self, EnvarError> {
+        let path = path.as_ref();
+        let invalid = |message: String| EnvarError::InvalidManifest { path: path.to_path_buf(), message };
+
+        let contents = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let manifest = contents.parse::<toml::Table>().map_err(|e| invalid(e.message().to_string()))?;
+
+        if let Some(field) = manifest.keys().find(|field| *field != "prefix" && *field != "var") {
+            return Err(invalid(format!("unknown field `{}`", field)));
+        }
+
+        let mut set = match manifest.get("prefix") {
+            Some(toml::Value::String(prefix)) => EnvarSet::with_prefix(prefix.as_str()),
+            Some(_) => return Err(invalid("`prefix` must be a string".to_string())),
+            None => EnvarSet::new(),
+        };
+
+        let vars = match manifest.get("var") {
+            Some(toml::Value::Array(vars)) => vars.as_slice(),
+            Some(_) => return Err(invalid("`var` must be an array of tables".to_string())),
+            None => &[],
+        };
+
+        for var in vars {
+            match var {
+                toml::Value::Table(var) => set = set.var(manifest_spec(var).map_err(invalid)?),
+                _ => return Err(invalid("`var` must be an array of tables".to_string())),
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+
+/// Builds a variable declared in a `[[var]]` table of an `envar.toml` manifest.
+#[cfg(feature = "toml")]
+fn manifest_spec(var: &toml::Table) -> Result<EnvarSpec<'static>, String> {
+    let key = match var.get("name") {
+        Some(toml::Value::String(name)) => name.as_str(),
+        _ => return Err("every `[[var]]` needs a `name` string".to_string()),
+    };
+
+    let string = |field: &str, val: &toml::Value| match val {
+        toml::Value::String(val) => Ok(val.clone()),
+        _ => Err(format!("`{}` of {} must be a string", field, key)),
+    };
+    let flag = |field: &str, val: &toml::Value| match val {
+        toml::Value::Boolean(val) => Ok(*val),
+        _ => Err(format!("`{}` of {} must be a boolean", field, key)),
+    };
+    let strings = |field: &str, val: &toml::Value| match val {
+        toml::Value::Array(items) => items.iter().map(|item| string(field, item)).collect::<Result<Vec<_>, _>>(),
+        _ => Err(format!("`{}` of {} must be an array of strings", field, key)),
+    };
+
+    let envar = match (var.get("type"), var.get("one_of")) {
+        (_, Some(allowed)) => {
+            let allowed = strings("one_of", allowed)?.into_iter().map(Into::into).collect();
+            Envar::OneOf(key.to_string().into(), allowed)
+        },
+        (Some(type_name), None) => {
+            let type_name = string("type", type_name)?;
+            Envar::from_type_name(key.to_string(), &type_name).ok_or_else(|| format!("unknown type `{}` of {}", type_name, key))?
+        },
+        (None, None) => Envar::String(key.to_string().into()),
+    };
+
+    let mut spec = EnvarSpec::from(envar);
+
+    for (field, val) in var {
+        spec = match field.as_str() {
+            "name" | "type" | "one_of" => spec,
+            "default" => match val {
+                toml::Value::String(default) => spec.or_default(default),
+                toml::Value::Integer(default) => spec.or_default(default),
+                toml::Value::Float(default) => spec.or_default(default),
+                toml::Value::Boolean(default) => spec.or_default(default),
+                _ => return Err(format!("`default` of {} must be a string, number, or boolean", key)),
+            },
+            "description" => spec.describe(string(field, val)?),
+            "optional" if flag(field, val)? => spec.warn_if_missing(),
+            "required_if_feature" => spec.required_if_feature(string(field, val)?),
+            "required_in" => match Profile::from_name(&string(field, val)?) {
+                Some(profile) => spec.required_in(profile),
+                None => return Err(format!("`required_in` of {} must be \"debug\" or \"release\"", key)),
+            },
+            "required_on" => match string(field, val)?.split_once('=') {
+                Some((name, value)) => spec.required_on(name.trim(), value.trim().trim_matches('"')),
+                None => return Err(format!("`required_on` of {} must be a cfg option such as 'target_os = \"linux\"'", key)),
+            },
+            "secret" if flag(field, val)? => spec.secret(),
+            "aliases" => strings(field, val)?.into_iter().fold(spec, EnvarSpec::deprecated_alias),
+            "fallbacks" => strings(field, val)?.into_iter().fold(spec, EnvarSpec::or_env),
+            "case_insensitive" if flag(field, val)? => spec.case_insensitive(),
+            "trim" => spec.trim(flag(field, val)?),
+            "number_format" => match string(field, val)?.as_str() {
+                "human" => spec.number_format(NumberFormat::human()),
+                "strict" => spec.number_format(NumberFormat::new()),
+                other => return Err(format!("unknown `number_format` `{}` of {}", other, key)),
+            },
+            "range" => match range_bounds(&string(field, val)?) {
+                Some(bounds) => spec.range(bounds),
+                None => return Err(format!("`range` of {} must be a range such as \"1024..=65535\"", key)),
+            },
+            "non_empty" if flag(field, val)? => spec.non_empty(),
+            "strict_bool" if flag(field, val)? => spec.strict_bool(),
+            "must_exist" if flag(field, val)? => spec.must_exist(),
+            "must_be_file" if flag(field, val)? => spec.must_be_file(),
+            "must_be_dir" if flag(field, val)? => spec.must_be_dir(),
+            #[cfg(feature = "url")]
+            "schemes" => spec.schemes(strings(field, val)?),
+            #[cfg(not(feature = "url"))]
+            "schemes" => return Err(format!("`schemes` of {} requires the `url` feature", key)),
+            "optional" | "secret" | "case_insensitive" | "non_empty" | "strict_bool" | "must_exist" | "must_be_file" | "must_be_dir" => spec,
+            _ => return Err(format!("unknown field `{}` of {}", field, key)),
+        };
+    }
+
+    Ok(spec)
+}
+
+
+/// Builds a variable declared in a `.env` template from the comments above it.
+fn example_spec(key: &str, comments: &[&str]) -> EnvarSpec<'static> {
+    // The type line is the only one listing whether the variable is required.
+    let Some((type_line, (type_name, default, optional))) = comments.iter()
+        .enumerate()
+        .find_map(|(i, comment)| type_line(comment).map(|parsed| (i, parsed)))
+    else {
+        return EnvarSpec::from(Envar::String(key.to_string().into()));
+    };
+
+    let envar = Envar::from_type_name(key.to_string(), type_name).unwrap_or_else(|| Envar::String(key.to_string().into()));
+    let mut spec = EnvarSpec::from(envar);
+
+    // Secret defaults are masked in templates, so only their optionality can be restored.
+    spec = match default {
+        Some("***") => spec.secret().warn_if_missing(),
+        Some(default) => spec.or_default(default),
+        None if optional => spec.warn_if_missing(),
+        None => spec,
+    };
+
+    if type_line > 0 {
+        spec = spec.describe(comments[..type_line].join(" "));
+    }
+
+    for comment in &comments[type_line + 1..] {
+        spec = match (comment.strip_prefix("formerly "), comment.strip_prefix("falls back to ")) {
+            (Some(alias), _) => spec.deprecated_alias(alias),
+            (_, Some(fallbacks)) => fallbacks.split(", ").fold(spec, |spec, key| spec.or_env(key.to_string())),
+            _ => match comment.strip_prefix("required only with ").and_then(Condition::parse) {
+                Some(condition) => spec.required_if(condition),
+                None => constrain(spec, comment),
+            },
+        };
+    }
+
+    spec
+}
+
+
+/// Splits a template's type line, such as `u16, optional, default: 8080`,
+/// into the type name, the default, and whether the variable is optional.
+fn type_line(comment: &str) -> Option<(&str, Option<&str>, bool)> {
+    if let Some(type_name) = comment.strip_suffix(", required") {
+        return Some((type_name, None, false));
+    }
+    if let Some((type_name, default)) = comment.split_once(", optional, default: ") {
+        return Some((type_name, Some(default), true));
+    }
+    comment.strip_suffix(", optional").map(|type_name| (type_name, None, true))
+}
+
+
+/// Restores the constraint with the given description, as listed in a template.
+fn constrain(spec: EnvarSpec<'static>, description: &str) -> EnvarSpec<'static> {
+    match description {
+        "must not be empty" => spec.non_empty(),
+        "must be `true` or `false`" => spec.strict_bool(),
+        "must be an existing path" => spec.must_exist(),
+        "must be an existing file" => spec.must_be_file(),
+        "must be an existing directory" => spec.must_be_dir(),
+        _ => {
+            if let Some(bounds) = description.strip_prefix("must be in range ").and_then(range_bounds) {
+                return spec.range(bounds);
+            }
+
+            #[cfg(feature = "url")]
+            if let Some(schemes) = description.strip_prefix("must use one of the schemes: ") {
+                return spec.schemes(schemes.split(", ").map(str::to_string).collect::<Vec<_>>());
+            }
+
+            spec
+        },
+    }
+}
+
+
+/// Parses a range such as `1024..=65535`, `..10`, or `0.5..`, comparing values as `f64`.
+fn range_bounds(range: &str) -> Option<(Bound<f64>, Bound<f64>)> {
+    let (start, end) = range.split_once("..")?;
+
+    let start = match start.trim() {
+        "" => Bound::Unbounded,
+        start => Bound::Included(number(start)?),
+    };
+    let end = match end.trim().strip_prefix('=') {
+        Some(end) => Bound::Included(number(end)?),
+        None if end.trim().is_empty() => Bound::Unbounded,
+        None => Bound::Excluded(number(end)?),
+    };
+
+    Some((start, end))
+}
+
+
+/// Parses a numeric literal, such as `65_535u16`, as an `f64`.
+fn number(literal: &str) -> Option<f64> {
+    number_literal(literal.trim())?.parse().ok()
+}
+
+
+/// Strips the `_` separators and the type suffix, such as `u16`, from a numeric literal,
+/// returning `None` if `literal` is not a number.
+fn number_literal(literal: &str) -> Option<String> {
+    let literal = literal.replace('_', "");
+    let end = literal.find(['u', 'i', 'f']).unwrap_or(literal.len());
+    let number = &literal[..end];
+
+    let digits = number.strip_prefix('-').unwrap_or(number);
+    let is_number = digits.starts_with(|c: char| c.is_ascii_digit()) && digits.chars().all(|c| c.is_ascii_digit() || c == '.');
+
+    is_number.then(|| number.to_string())
+}
+
+
+/// Builds a variable from the source following `Envar::` in a build script, such as `u16("PORT").or_default(8080)`.
+fn script_spec(source: &str) -> Option<EnvarSpec<'static>> {
+    let variant_len = source.find(|c: char| !c.is_alphanumeric() && c != '_')?;
+    let (variant, rest) = source.split_at(variant_len);
+    let (args, mut rest) = call_args(rest)?;
+
+    let key = string_literal(args.first()?)?.0;
+    let variant = variant_name(variant);
+
+    let envar = match (variant.as_str(), args.get(1)) {
+        ("OneOf", Some(allowed)) => Envar::OneOf(key.into(), string_literals(allowed).into_iter().map(Into::into).collect()),
+        _ => Envar::from_type_name(key.clone(), &variant).unwrap_or(Envar::String(key.into())),
+    };
+
+    let mut spec = EnvarSpec::from(envar);
+
+    // Apply the chained options, such as `.or_default(8080)`, until the expression ends.
+    while let Some(call) = rest.trim_start().strip_prefix('.') {
+        let name_len = call.find(|c: char| !c.is_alphanumeric() && c != '_')?;
+        let (name, call) = call.split_at(name_len);
+        let (args, after) = call_args(call)?;
+        let arg = args.first().map(String::as_str).unwrap_or_default();
+
+        spec = match name {
+            "or_default" => match literal(arg) {
+                Some(default) => spec.or_default(default),
+                None => spec.warn_if_missing(),
+            },
+            "warn_if_missing" => spec.warn_if_missing(),
+            "level" if arg.ends_with("Warn") => spec.level(LoadLevel::Warn),
+            "required_if_feature" => match string_literal(arg) {
+                Some((feature, _)) => spec.required_if_feature(feature),
+                None => spec,
+            },
+            "required_in" => match Profile::from_name(&arg.rsplit("::").next().unwrap_or_default().to_lowercase()) {
+                Some(profile) => spec.required_in(profile),
+                None => spec,
+            },
+            "or_default_in" => match (Profile::from_name(&arg.rsplit("::").next().unwrap_or_default().to_lowercase()), args.get(1).and_then(|default| literal(default))) {
+                (Some(profile), Some(default)) => spec.or_default_in(profile, default),
+                _ => spec,
+            },
+            "required_on" => match (string_literal(arg), args.get(1).and_then(|value| string_literal(value))) {
+                (Some((name, _)), Some((value, _))) => spec.required_on(name, value),
+                _ => spec,
+            },
+            "secret" => spec.secret(),
+            "describe" => match string_literal(arg) {
+                Some((description, _)) => spec.describe(description),
+                None => spec,
+            },
+            "deprecated_alias" => match string_literal(arg) {
+                Some((alias, _)) => spec.deprecated_alias(alias),
+                None => spec,
+            },
+            "or_env" => match string_literal(arg) {
+                Some((key, _)) => spec.or_env(key),
+                None => spec,
+            },
+            "range" => match range_bounds(arg) {
+                Some(bounds) => spec.range(bounds),
+                None => spec,
+            },
+            "non_empty" => spec.non_empty(),
+            "strict_bool" => spec.strict_bool(),
+            "must_exist" => spec.must_exist(),
+            "must_be_file" => spec.must_be_file(),
+            "must_be_dir" => spec.must_be_dir(),
+            #[cfg(feature = "url")]
+            "schemes" => spec.schemes(string_literals(arg)),
+            "case_insensitive" => spec.case_insensitive(),
+            "trim" => spec.trim(arg.trim() == "true"),
+            "number_format" if arg.contains("human") => spec.number_format(NumberFormat::human()),
+            _ => spec,
+        };
+
+        rest = after;
+    }
+
+    Some(spec)
+}
+
+
+/// Returns the variant created by the constructor function `name`, such as `NonZeroU16` for `non_zero_u16`,
+/// or `name` itself if it is a variant name.
+fn variant_name(name: &str) -> String {
+    if name == "secret_string" {
+        return "Secret".to_string();
+    }
+
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+
+/// Splits the parenthesized arguments at the start of `source` at top-level commas,
+/// returning them trimmed together with the source after the closing parenthesis.
+fn call_args(source: &str) -> Option<(Vec<String>, &str)> {
+    let source = source.trim_start().strip_prefix('(')?;
+
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut depth = 0;
+    let mut i = 0;
+
+    while let Some(c) = source[i..].chars().next() {
+        match c {
+            '"' => {
+                let (_, len) = string_literal(&source[i..])?;
+                arg.push_str(&source[i..i + len]);
+                i += len;
+                continue;
+            },
+            '(' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => {
+                if !arg.trim().is_empty() {
+                    args.push(arg.trim().to_string());
+                }
+                return Some((args, &source[i + 1..]));
+            },
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(std::mem::take(&mut arg).trim().to_string());
+                i += 1;
+                continue;
+            },
+            _ => {},
+        }
+
+        arg.push(c);
+        i += c.len_utf8();
+    }
+
+    None
+}
+
+
+/// Parses the string literal at the start of `source`,
+/// returning its unescaped contents and its length in the source, including the quotes.
+fn string_literal(source: &str) -> Option<(String, usize)> {
+    let mut chars = source.strip_prefix('"')?.char_indices();
+    let mut val = String::new();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((val, i + 2)),
+            '\\' => match chars.next()?.1 {
+                'n' => val.push('\n'),
+                't' => val.push('\t'),
+                escaped => val.push(escaped),
+            },
+            _ => val.push(c),
+        }
+    }
+
+    None
+}
+
+
+/// Collects every string literal in `source`, such as the values of `&["debug", "info"]`.
+fn string_literals(source: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find('"') {
+        match string_literal(&rest[start..]) {
+            Some((literal, len)) => {
+                literals.push(literal);
+                rest = &rest[start + len..];
+            },
+            None => break,
+        }
+    }
+
+    literals
+}
+
+
+/// Returns the value of a string, number, boolean, or character literal, or `None` for any other expression.
+fn literal(source: &str) -> Option<String> {
+    if let Some((val, len)) = string_literal(source) {
+        return (len == source.len()).then_some(val);
+    }
+
+    match source {
+        "true" | "false" => Some(source.to_string()),
+        _ if source.len() == 3 && source.starts_with('\'') && source.ends_with('\'') => Some(source[1..2].to_string()),
+        _ => number_literal(source),
+    }
+}
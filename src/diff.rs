@@ -0,0 +1,149 @@
+use std::collections::BTreeSet;
+
+use crate::Source;
+
+
+/// The differences between two sources, such as the process environment and a `.env` file.
+///
+/// Created by `diff` or `EnvarSet::diff`. Its `Display` output lists one key per line,
+/// prefixed with `+` if it was added, `-` if it was removed, and `~` if its value changed.
+/// Values of secret variables are masked as `***`.
+#[derive(Debug, Clone, Default)]
+pub struct SourceDiff {
+    entries: Vec<DiffEntry>,
+}
+
+
+/// A variable whose value differs between two sources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffEntry {
+    key: String,
+    change: Change,
+}
+
+
+/// How a variable differs between two sources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The variable is only set in the right source.
+    Added(String),
+
+    /// The variable is only set in the left source.
+    Removed(String),
+
+    /// The variable is set in both sources, to different values.
+    Changed {
+        /// The value in the left source.
+        from: String,
+        /// The value in the right source.
+        to: String,
+    },
+}
+
+
+/// Compares every variable set in `left` or `right`, such as the process environment and `.env.production`.
+///
+/// Only sources that list their names, such as `ProcessEnv`, `DotenvFile`, and `HashMap`s, can be compared.
+/// Without declarations to tell which variables are secret, values are masked as `***`
+/// if the name contains `KEY`, `SECRET`, `TOKEN`, `PASSWORD`, `PASSWD`, `CREDENTIAL`, or `PRIVATE`,
+/// in any case. `EnvarSet::diff` masks the declared secrets instead.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Change, DotenvFile};
+///
+/// let local = std::env::temp_dir().join("diff_doc_local.env");
+/// let staging = std::env::temp_dir().join("diff_doc_staging.env");
+/// std::fs::write(&local, "PORT=3000\nDEBUG=true\nAPI_KEY=local\n").unwrap();
+/// std::fs::write(&staging, "PORT=8080\nAPI_KEY=staging\nREGION=eu\n").unwrap();
+///
+/// let diff = easy_envar::diff(&DotenvFile::from_path(&local).unwrap(), &DotenvFile::from_path(&staging).unwrap());
+///
+/// assert_eq!(diff.entries()[0].key(), "API_KEY");
+/// assert_eq!(diff.entries()[0].change(), &Change::Changed { from: "***".to_string(), to: "***".to_string() });
+/// assert_eq!(diff.to_string(), "~ API_KEY: *** -> ***\n- DEBUG=true\n~ PORT: 3000 -> 8080\n+ REGION=eu\n");
+/// ```
+pub fn diff(left: &dyn Source, right: &dyn Source) -> SourceDiff {
+    let keys: BTreeSet<String> = left.names().into_iter().chain(right.names()).collect();
+    SourceDiff::new(keys, left, right, looks_secret)
+}
+
+
+/// Returns whether a variable name suggests that its value is a secret.
+fn looks_secret(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    ["KEY", "SECRET", "TOKEN", "PASSWORD", "PASSWD", "CREDENTIAL", "PRIVATE"].iter().any(|word| key.contains(word))
+}
+
+
+impl SourceDiff {
+    /// Compares the given keys between the two sources, masking the values of keys for which `is_secret` holds.
+    pub(crate) fn new<I>(keys: I, left: &dyn Source, right: &dyn Source, is_secret: impl Fn(&str) -> bool) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let entries = keys.into_iter()
+            .filter_map(|key| {
+                let mask = |val: String| if is_secret(&key) { "***".to_string() } else { val };
+
+                let change = match (value(left, &key), value(right, &key)) {
+                    (Some(from), Some(to)) if from == to => return None,
+                    (Some(from), Some(to)) => Change::Changed { from: mask(from), to: mask(to) },
+                    (Some(from), None) => Change::Removed(mask(from)),
+                    (None, Some(to)) => Change::Added(mask(to)),
+                    (None, None) => return None,
+                };
+
+                Some(DiffEntry { key, change })
+            })
+            .collect();
+
+        SourceDiff { entries }
+    }
+
+    /// Returns the differing variables, sorted by name.
+    pub fn entries(&self) -> &[DiffEntry] {
+        &self.entries
+    }
+
+    /// Returns whether both sources agree on every compared variable.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+
+/// Reads a value for comparison, treating values that aren't valid unicode as unset.
+fn value(source: &dyn Source, key: &str) -> Option<String> {
+    source.var(key).ok()
+}
+
+
+impl DiffEntry {
+    /// Returns the name of the variable.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns how the variable differs.
+    pub fn change(&self) -> &Change {
+        &self.change
+    }
+}
+
+
+impl std::fmt::Display for SourceDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            match &entry.change {
+                Change::Added(val) => writeln!(f, "+ {}={}", entry.key, val)?,
+                Change::Removed(val) => writeln!(f, "- {}={}", entry.key, val)?,
+                Change::Changed { from, to } => writeln!(f, "~ {}: {} -> {}", entry.key, from, to)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+
+/// The syntax selected with `set_directive_syntax`, as a `DirectiveSyntax` discriminant.
+static SYNTAX: AtomicU8 = AtomicU8::new(DirectiveSyntax::Auto as u8);
+
+
+/// The policy selected with `set_secret_export`, as a `SecretExport` discriminant.
+static SECRET_EXPORT: AtomicU8 = AtomicU8::new(SecretExport::Warn as u8);
+
+
+/// The first Cargo version that reads the `cargo::` prefix.
+const MODERN_SINCE: (u32, u32) = (1, 77);
+
+
+/// The prefix of the Cargo build directives easy-envar prints, such as `cargo:rustc-env=KEY=value`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{DirectiveSyntax, LoadedEnvar};
+///
+/// easy_envar::set_directive_syntax(DirectiveSyntax::Modern);
+///
+/// let mut out = Vec::new();
+/// LoadedEnvar::U16("PORT".into(), 8080).export_to(&mut out).unwrap();
+///
+/// assert_eq!(String::from_utf8(out).unwrap(), "cargo::rustc-env=PORT=8080\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum DirectiveSyntax {
+    /// `cargo::` when the build script runs under Cargo 1.77 or later, and the package's `rust-version`,
+    /// if it sets one, is 1.77 or later too, since Cargo rejects `cargo::` in packages that claim to support older versions.
+    /// `cargo:` otherwise, including outside of build scripts.
+    #[default]
+    Auto,
+
+    /// `cargo:`, read by every version of Cargo.
+    Legacy,
+
+    /// `cargo::`, read by Cargo 1.77 and later, which reports unknown directives instead of ignoring them.
+    Modern,
+}
+
+
+/// Selects the prefix of the build directives printed by `export` and the other `export` methods,
+/// and of the `cargo:warning` directives printed while loading. Defaults to `DirectiveSyntax::Auto`.
+pub fn set_directive_syntax(syntax: DirectiveSyntax) {
+    SYNTAX.store(syntax as u8, Ordering::Relaxed);
+}
+
+
+/// How `export` treats a `Secret` variable, whose `cargo:rustc-env` directive embeds the value in the compiled binary,
+/// where anyone holding the binary can read it with `strings`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{LoadedEnvar, Secret, SecretExport};
+///
+/// let key = LoadedEnvar::Secret("API_KEY".into(), Secret::new("hunter2".to_string()));
+///
+/// let mut out = Vec::new();
+/// key.export_to(&mut out).unwrap();
+///
+/// assert!(String::from_utf8(out).unwrap().starts_with("cargo:warning=API_KEY is secret"));
+///
+/// easy_envar::set_secret_export(SecretExport::Deny);
+///
+/// let mut out = Vec::new();
+/// assert!(key.export_to(&mut out).is_err());
+/// assert!(out.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum SecretExport {
+    /// Exports the value, with a `cargo:warning` suggesting to load it at runtime instead.
+    #[default]
+    Warn,
+
+    /// Refuses to export the value: `export` panics, failing the build script, and `EnvarSet::export_all`
+    /// returns `EnvarError::SecretExported` without exporting anything.
+    Deny,
+
+    /// Exports the value silently, for secrets that are meant to ship with the binary.
+    Allow,
+}
+
+
+/// Selects how `export` and `EnvarSet::export_all` treat `Secret` variables. Defaults to `SecretExport::Warn`.
+pub fn set_secret_export(policy: SecretExport) {
+    SECRET_EXPORT.store(policy as u8, Ordering::Relaxed);
+}
+
+
+/// Returns the policy selected with `set_secret_export`.
+pub(crate) fn secret_export() -> SecretExport {
+    match SECRET_EXPORT.load(Ordering::Relaxed) {
+        p if p == SecretExport::Deny as u8 => SecretExport::Deny,
+        p if p == SecretExport::Allow as u8 => SecretExport::Allow,
+        _ => SecretExport::Warn,
+    }
+}
+
+
+/// Prints a Cargo build directive of any kind, such as `rustc-link-arg` or `metadata`,
+/// with the prefix selected by `set_directive_syntax`.
+///
+/// # Panics
+///
+/// If `kind` or `value` contains a line break, which would end the directive early and start another,
+/// or if stdout is closed.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// // build.rs
+/// // Prints `cargo:rustc-link-arg=-Wl,--as-needed`.
+/// easy_envar::emit_directive("rustc-link-arg", "-Wl,--as-needed");
+/// ```
+pub fn emit_directive(kind: &str, value: impl Display) {
+    print(|out| emit_directive_to(out, kind, value));
+}
+
+
+/// Writes a directive like `emit_directive` to `out` instead of stdout.
+///
+/// ***
+/// # Returns
+///
+/// - `Ok(())`: if the directive was written.
+/// - `Err(std::io::Error)`: if writing to `out` failed, or, with the `InvalidInput` kind,
+///   if `kind` or `value` contains a line break.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// let mut out = Vec::new();
+/// easy_envar::emit_directive_to(&mut out, "metadata", "include=/opt/include").unwrap();
+///
+/// assert_eq!(String::from_utf8(out).unwrap(), "cargo:metadata=include=/opt/include\n");
+///
+/// assert!(easy_envar::emit_directive_to(Vec::new(), "rustc-link-lib", "z\ncargo:rustc-link-arg=-evil").is_err());
+/// ```
+pub fn emit_directive_to(mut out: impl Write, kind: &str, value: impl Display) -> std::io::Result<()> {
+    let value = value.to_string();
+    if kind.contains(['\n', '\r']) || value.contains(['\n', '\r']) {
+        let message = format!("the `{}` directive contains a line break", kind.escape_debug());
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, message));
+    }
+
+    writeln!(out, "{}{}={}", prefix(), kind, value)
+}
+
+
+/// Returns the prefix to print before a directive, such as `rustc-env=`.
+pub(crate) fn prefix() -> &'static str {
+    let modern = match SYNTAX.load(Ordering::Relaxed) {
+        s if s == DirectiveSyntax::Legacy as u8 => false,
+        s if s == DirectiveSyntax::Modern as u8 => true,
+        _ => {
+            static DETECTED: OnceLock<bool> = OnceLock::new();
+            *DETECTED.get_or_init(detect_modern)
+        },
+    };
+
+    if modern { "cargo::" } else { "cargo:" }
+}
+
+
+/// Returns whether this is a build script whose Cargo and package both allow the `cargo::` prefix.
+fn detect_modern() -> bool {
+    if std::env::var_os("OUT_DIR").is_none() || std::env::var_os("TARGET").is_none() {
+        return false;
+    }
+
+    let package_allows = match std::env::var("CARGO_PKG_RUST_VERSION") {
+        Ok(rust_version) if !rust_version.is_empty() => version(&rust_version).is_some_and(|v| v >= MODERN_SINCE),
+        _ => true,
+    };
+
+    package_allows && cargo_version().is_some_and(|v| v >= MODERN_SINCE)
+}
+
+
+/// Returns the major and minor version of the Cargo running the build script, from `$CARGO --version`.
+fn cargo_version() -> Option<(u32, u32)> {
+    let cargo = std::env::var_os("CARGO")?;
+    let output = std::process::Command::new(cargo).arg("--version").output().ok()?;
+
+    // Such as `cargo 1.82.0 (8f40fc59f 2024-08-21)`.
+    version(String::from_utf8_lossy(&output.stdout).split_whitespace().nth(1)?)
+}
+
+
+/// Parses the major and minor parts of a version such as `1.77` or `1.82.0-nightly`.
+fn version(text: &str) -> Option<(u32, u32)> {
+    let mut parts = text.split(['.', '-']);
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
+
+/// Writes directives to stdout with `write`, panicking if it fails, like `println!`.
+pub(crate) fn print(write: impl FnOnce(&mut dyn Write) -> std::io::Result<()>) {
+    if let Err(e) = write(&mut std::io::stdout().lock()) {
+        panic!("failed to print a build directive: {}", e);
+    }
+}
+
+
+/// Returns `val` as it can be passed in a `rustc-env` directive.
+///
+/// Directives end at a line break, so line breaks are escaped as `\n` and `\r`, and backslashes as `\\`,
+/// to be decoded with `unescape_exported`. Every value is escaped the same way, so any value round-trips.
+pub(crate) fn rustc_env_value(val: &str) -> Cow<'_, str> {
+    if !val.contains(['\\', '\n', '\r']) {
+        return Cow::Borrowed(val);
+    }
+
+    let mut escaped = String::with_capacity(val.len() + 8);
+    for c in val.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
+
+/// Decodes a value exported by `LoadedEnvar::export`, as read with `env!` in the compiled crate.
+///
+/// Cargo reads build script directives line by line, so `export` escapes each line break as `\n` or `\r`,
+/// and each backslash as `\\`. This reverses exactly that, so the decoded value is the one that was loaded,
+/// whether it spanned several lines, such as a PEM certificate, or contained backslashes, such as a Windows path.
+/// Values without line breaks or backslashes are exported as they are, and decode to themselves.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::LoadedEnvar;
+///
+/// let values = [
+///     "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----",
+///     r"C:\new\dir",
+///     "plain",
+/// ];
+///
+/// for val in values {
+///     // build.rs
+///     let mut out = Vec::new();
+///     LoadedEnvar::String("VALUE".into(), val.to_string()).export_to(&mut out).unwrap();
+///
+///     // main.rs, reading `env!("VALUE")`
+///     let directive = String::from_utf8(out).unwrap();
+///     let exported = directive.trim_end_matches('\n').split_once("VALUE=").unwrap().1;
+///
+///     assert_eq!(easy_envar::unescape_exported(exported), val);
+/// }
+/// ```
+///
+/// A Windows path is exported with its backslashes doubled:
+///
+/// ```rust
+/// use easy_envar::LoadedEnvar;
+///
+/// let mut out = Vec::new();
+/// LoadedEnvar::String("DATA_DIR".into(), r"C:\new\dir".to_string()).export_to(&mut out).unwrap();
+///
+/// assert_eq!(String::from_utf8(out).unwrap(), "cargo:rustc-env=DATA_DIR=C:\\\\new\\\\dir\n");
+/// ```
+pub fn unescape_exported(val: &str) -> String {
+    let mut unescaped = String::with_capacity(val.len());
+    let mut chars = val.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('\\') => unescaped.push('\\'),
+            // Not produced by `export`, so kept as it is.
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            },
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
@@ -0,0 +1,322 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+
+/// A suspicious but valid line in a `.env` file, such as a key defined twice.
+///
+/// Returned by `DotenvFile::warnings`, and printed when the file is loaded with `init`:
+/// as `cargo:warning` directives in a build script, or to stderr otherwise.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::DotenvFile;
+///
+/// let path = std::env::temp_dir().join("dotenv_warning_doc.env");
+/// std::fs::write(&path, "PORT=80\nPORT=8080\n").unwrap();
+///
+/// let file = DotenvFile::from_path(&path).unwrap();
+///
+/// assert_eq!(file.warnings()[0].line(), 2);
+/// assert_eq!(file.warnings()[0].to_string(), "line 2: `PORT` is already defined on line 1, so this definition is ignored");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvWarning {
+    line: usize,
+    message: String,
+}
+
+
+/// A syntax error in a `.env` file, at a 1-based line and column.
+#[derive(Debug)]
+pub(crate) struct SyntaxError {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) message: String,
+}
+
+
+/// A key and its value, with the line defining it.
+pub(crate) struct Entry {
+    pub(crate) key: String,
+    pub(crate) val: String,
+    pub(crate) line: usize,
+}
+
+
+/// The entries of a `.env` file, in file order, keeping only the first definition of each key.
+pub(crate) struct Parsed {
+    pub(crate) entries: Vec<Entry>,
+    pub(crate) warnings: Vec<DotenvWarning>,
+}
+
+
+impl DotenvWarning {
+    /// Returns the 1-based line the warning is about.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns what is suspicious about the line.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+
+impl std::fmt::Display for DotenvWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+
+/// Parses the contents of a `.env` file.
+///
+/// Each line is blank, a `#` comment, or `KEY=value`, optionally prefixed with `export`.
+/// Values may be unquoted, single-quoted (read literally), or double-quoted (with escapes),
+/// and quoted values may span several lines. `$KEY` and `${KEY}` in unquoted and double-quoted values
+/// are replaced by the variable in the process environment, or else by an earlier entry in the file.
+pub(crate) fn parse(contents: &str) -> Result<Parsed, SyntaxError> {
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents).replace("\r\n", "\n");
+    let mut parser = Parser { chars: contents.chars().peekable(), line: 1, column: 1, entries: Vec::new(), warnings: Vec::new() };
+
+    while parser.entry()? {}
+
+    Ok(Parsed { entries: parser.entries, warnings: parser.warnings })
+}
+
+
+/// Reads entries, tracking the position for errors.
+struct Parser<'c> {
+    chars: Peekable<Chars<'c>>,
+    line: usize,
+    column: usize,
+    entries: Vec<Entry>,
+    warnings: Vec<DotenvWarning>,
+}
+
+
+impl Parser<'_> {
+    /// Reads the next line holding an entry, returning `false` at the end of the file.
+    fn entry(&mut self) -> Result<bool, SyntaxError> {
+        loop {
+            self.skip_blanks();
+            match self.chars.peek() {
+                None => return Ok(false),
+                Some('\n') => { self.bump(); },
+                Some('#') => self.skip_line(),
+                Some(_) => break,
+            }
+        }
+
+        let line = self.line;
+        let mut key = self.key()?;
+        self.skip_blanks();
+
+        // `export` is a prefix, unless it is the key itself.
+        if key == "export" && self.chars.peek() != Some(&'=') {
+            key = self.key()?;
+            self.skip_blanks();
+        }
+
+        if self.chars.peek() != Some(&'=') {
+            return Err(self.error(format!("expected `=` after `{}`", key)));
+        }
+        self.bump();
+        self.skip_blanks();
+
+        let val = self.value()?;
+
+        match self.entries.iter().find(|entry| entry.key == key) {
+            Some(first) => {
+                let message = format!("`{}` is already defined on line {}, so this definition is ignored", key, first.line);
+                self.warnings.push(DotenvWarning { line, message });
+            },
+            None => self.entries.push(Entry { key, val, line }),
+        }
+
+        Ok(true)
+    }
+
+    /// Reads a variable name: a letter or `_`, then letters, digits, `_`, or `.`.
+    fn key(&mut self) -> Result<String, SyntaxError> {
+        match self.chars.peek().copied() {
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {},
+            Some(c) => return Err(self.error(format!("expected a variable name, found `{}`", c.escape_debug()))),
+            None => return Err(self.error("expected a variable name".to_string())),
+        }
+
+        let mut key = String::new();
+        while let Some(&c) = self.chars.peek().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.')) {
+            key.push(c);
+            self.bump();
+        }
+
+        Ok(key)
+    }
+
+    /// Reads a value up to the end of its line, or of its last line if a quoted part spans several.
+    fn value(&mut self) -> Result<String, SyntaxError> {
+        let mut val = String::new();
+
+        // Like a comment line, a value starting with `#` is a comment.
+        if self.chars.peek() == Some(&'#') {
+            self.skip_line();
+            return Ok(val);
+        }
+
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                '\n' => break,
+                ' ' | '\t' => {
+                    self.skip_blanks();
+                    match self.chars.peek().copied() {
+                        None | Some('\n') => break,
+                        Some('#') => {
+                            self.skip_line();
+                            break;
+                        },
+                        Some(c) => {
+                            let message = format!("unexpected `{}` after a space; quote values that contain spaces", c.escape_debug());
+                            return Err(self.error(message));
+                        },
+                    }
+                },
+                '\'' => self.single_quoted(&mut val)?,
+                '"' => self.double_quoted(&mut val)?,
+                '\\' => self.escape(&mut val)?,
+                '$' => self.substitute(&mut val)?,
+                c => {
+                    val.push(c);
+                    self.bump();
+                },
+            }
+        }
+
+        Ok(val)
+    }
+
+    /// Reads a single-quoted part of a value, literally.
+    fn single_quoted(&mut self, val: &mut String) -> Result<(), SyntaxError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+
+        loop {
+            match self.bump() {
+                Some('\'') => return Ok(()),
+                Some(c) => val.push(c),
+                None => return Err(SyntaxError { line, column, message: "unterminated single quote".to_string() }),
+            }
+        }
+    }
+
+    /// Reads a double-quoted part of a value, with escapes and substitutions.
+    fn double_quoted(&mut self, val: &mut String) -> Result<(), SyntaxError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+
+        loop {
+            match self.chars.peek() {
+                Some('"') => {
+                    self.bump();
+                    return Ok(());
+                },
+                Some('\\') => self.escape(val)?,
+                Some('$') => self.substitute(val)?,
+                Some(&c) => {
+                    val.push(c);
+                    self.bump();
+                },
+                None => return Err(SyntaxError { line, column, message: "unterminated double quote".to_string() }),
+            }
+        }
+    }
+
+    /// Reads a backslash and the character it escapes.
+    fn escape(&mut self, val: &mut String) -> Result<(), SyntaxError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+
+        let escaped = match self.bump() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some(c @ ('\\' | '\'' | '"' | '$' | ' ')) => c,
+            Some(c) => return Err(SyntaxError { line, column, message: format!("invalid escape `\\{}`", c.escape_debug()) }),
+            None => return Err(SyntaxError { line, column, message: "a backslash ends the file".to_string() }),
+        };
+
+        val.push(escaped);
+        Ok(())
+    }
+
+    /// Reads `$KEY` or `${KEY}`, pushing the variable's value.
+    fn substitute(&mut self, val: &mut String) -> Result<(), SyntaxError> {
+        let (line, column) = (self.line, self.column);
+        self.bump();
+
+        let braced = self.chars.peek() == Some(&'{');
+        if braced {
+            self.bump();
+        }
+
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek().filter(|c| c.is_ascii_alphanumeric() || **c == '_') {
+            name.push(c);
+            self.bump();
+        }
+
+        if braced {
+            if self.bump() != Some('}') {
+                return Err(SyntaxError { line, column, message: "unterminated `${`".to_string() });
+            }
+        } else if name.is_empty() {
+            // A `$` not followed by a name is kept as it is.
+            val.push('$');
+            return Ok(());
+        }
+
+        match std::env::var(&name).ok().or_else(|| self.entries.iter().find(|entry| entry.key == name).map(|entry| entry.val.clone())) {
+            Some(substituted) => val.push_str(&substituted),
+            None => self.warnings.push(DotenvWarning {
+                line,
+                message: format!("`${}` is not set, so it is replaced with an empty string", name),
+            }),
+        }
+
+        Ok(())
+    }
+
+    /// Skips spaces and tabs.
+    fn skip_blanks(&mut self) {
+        while matches!(self.chars.peek(), Some(' ' | '\t')) {
+            self.bump();
+        }
+    }
+
+    /// Skips to the end of the line, leaving the line break.
+    fn skip_line(&mut self) {
+        while self.chars.peek().is_some_and(|c| *c != '\n') {
+            self.bump();
+        }
+    }
+
+    /// Consumes the next character, advancing the position.
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Reports a syntax error at the current position.
+    fn error(&self, message: String) -> SyntaxError {
+        SyntaxError { line: self.line, column: self.column, message }
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+
+/// Parses a human-friendly duration such as `30s`, `5m`, `1h30m`, or `1500` (milliseconds).
+///
+/// A value is either a plain number of milliseconds, or one or more
+/// `<number><unit>` segments where the unit is `ms`, `s`, `m`, `h`, or `d`.
+/// Returns `None` if the value is malformed or overflows.
+pub(crate) fn parse(raw: &str) -> Option<Duration> {
+    let raw = raw.trim();
+
+    if let Ok(millis) = raw.parse::<u64>() {
+        return Some(Duration::from_millis(millis));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = raw;
+
+    if rest.is_empty() {
+        return None;
+    }
+
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            return None;
+        }
+        let amount = rest[..digits].parse::<u64>().ok()?;
+        rest = &rest[digits..];
+
+        let unit = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let segment = match &rest[..unit] {
+            "ms" => Duration::from_millis(amount),
+            "s" => Duration::from_secs(amount),
+            "m" => Duration::from_secs(amount.checked_mul(60)?),
+            "h" => Duration::from_secs(amount.checked_mul(60 * 60)?),
+            "d" => Duration::from_secs(amount.checked_mul(24 * 60 * 60)?),
+            _ => return None,
+        };
+        rest = &rest[unit..];
+
+        total = total.checked_add(segment)?;
+    }
+
+    Some(total)
+}
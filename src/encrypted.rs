@@ -0,0 +1,188 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::{DotenvFile, EnvarError};
+
+
+/// The variable holding the age identity used to decrypt encrypted `.env` files.
+const KEY_VAR: &str = "EASY_ENVAR_KEY";
+
+/// The variable holding the path of an age identity file, used if `EASY_ENVAR_KEY` isn't set.
+const KEY_FILE_VAR: &str = "EASY_ENVAR_KEY_FILE";
+
+
+/// The age identity to decrypt with.
+enum Key {
+    /// The identity itself, such as `AGE-SECRET-KEY-1...`.
+    Inline(String),
+    /// The path of a file of identities, such as one written by `age-keygen`.
+    File(PathBuf),
+}
+
+
+impl DotenvFile {
+    /// Reads a `.env` file encrypted with age or SOPS, such as `.env.enc`, decrypting it in memory.
+    ///
+    /// The age identity is read from `EASY_ENVAR_KEY`, or else from the file named by `EASY_ENVAR_KEY_FILE`.
+    /// Files encrypted as a whole with `age --encrypt` (binary or `--armor`) are decrypted with the `age` command,
+    /// and `.env` files encrypted with `sops --encrypt` with the `sops` command,
+    /// so the matching command must be installed. The plaintext is piped back and never written to disk.
+    /// Requires the `encrypted` feature.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(DotenvFile)`: the decrypted entries.
+    /// - `Err(EnvarError::Decryption)`: if no key is set, the file is not encrypted with age or SOPS,
+    ///   or the command is missing or fails to decrypt it.
+    /// - `Err(EnvarError::Syntax)`: if the decrypted file is not a valid `.env` file.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // EASY_ENVAR_KEY=AGE-SECRET-KEY-1... cargo run
+    /// use easy_envar::{DotenvFile, Envar, EnvarSet};
+    ///
+    /// let secrets = DotenvFile::from_encrypted_path(".env.enc").unwrap();
+    ///
+    /// let vars = EnvarSet::new()
+    ///     .var(Envar::string("DATABASE_URL").secret())
+    ///     .load_from(&secrets)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// A file that isn't encrypted is rejected rather than read as plaintext:
+    ///
+    /// ```rust
+    /// use easy_envar::{DotenvFile, EnvarError};
+    ///
+    /// let path = std::env::temp_dir().join("encrypted_doc_plain.env");
+    /// std::fs::write(&path, "API_KEY=hunter2\n").unwrap();
+    ///
+    /// let error = DotenvFile::from_encrypted_path(&path).unwrap_err();
+    ///
+    /// assert!(matches!(error, EnvarError::Decryption { .. }));
+    /// assert!(error.to_string().ends_with("is not encrypted with age or SOPS"));
+    /// ```
+    pub fn from_encrypted_path(path: impl AsRef<Path>) -> Result<Self, EnvarError> {
+        let path = path.as_ref();
+        let contents = decrypt(path)?;
+        DotenvFile::from_contents(&contents, path)
+    }
+}
+
+
+/// Loads a `.env` file encrypted with age or SOPS into the process environment, like `init_from_path`.
+///
+/// The file is decrypted in memory as with `DotenvFile::from_encrypted_path`,
+/// and like `init`, variables that are already set in the environment are not overridden.
+/// Requires the `encrypted` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust,no_run
+/// // build.rs, with EASY_ENVAR_KEY_FILE=~/.config/easy-envar/key.txt
+/// use easy_envar::{Envar, EnvarSet};
+///
+/// easy_envar::init_encrypted(".env.enc").unwrap();
+///
+/// EnvarSet::new()
+///     .var(Envar::string("API_KEY").secret())
+///     .load()
+///     .unwrap();
+/// ```
+pub fn init_encrypted(path: impl AsRef<Path>) -> Result<PathBuf, EnvarError> {
+    let path = path.as_ref();
+    let file = DotenvFile::from_encrypted_path(path)?;
+    file.apply();
+    crate::source::remember(file);
+    Ok(path.to_path_buf())
+}
+
+
+/// Decrypts the file at `path` with the command matching its format.
+fn decrypt(path: &Path) -> Result<String, EnvarError> {
+    let error = |message: String| EnvarError::Decryption { path: path.to_path_buf(), message };
+
+    let contents = std::fs::read(path).map_err(|e| error(e.to_string()))?;
+    if !is_age(&contents) && !is_sops(&contents) {
+        return Err(error("is not encrypted with age or SOPS".to_string()));
+    }
+
+    let key = key().ok_or_else(|| error(format!("neither `{}` nor `{}` is set", KEY_VAR, KEY_FILE_VAR)))?;
+
+    let (mut command, stdin) = if is_age(&contents) {
+        let mut command = Command::new("age");
+        command.arg("--decrypt");
+
+        // The inline identity is passed on stdin, so it never appears in the process list.
+        let stdin = match key {
+            Key::Inline(identity) => {
+                command.args(["--identity", "-"]);
+                Some(identity)
+            },
+            Key::File(file) => {
+                command.arg("--identity").arg(file);
+                None
+            },
+        };
+
+        command.arg(path);
+        (command, stdin)
+    } else {
+        let mut command = Command::new("sops");
+        command.args(["--decrypt", "--input-type", "dotenv", "--output-type", "dotenv"]).arg(path);
+
+        match key {
+            Key::Inline(identity) => command.env("SOPS_AGE_KEY", identity),
+            Key::File(file) => command.env("SOPS_AGE_KEY_FILE", file),
+        };
+
+        (command, None)
+    };
+
+    let program = command.get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| error(format!("could not run `{}`, is it installed? {}", program, e)))?;
+
+    if let (Some(identity), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(identity.as_bytes()).map_err(|e| error(e.to_string()))?;
+    }
+
+    let output = child.wait_with_output().map_err(|e| error(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(error(format!("`{}` failed: {}", program, stderr.trim())));
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| error("the decrypted file is not valid unicode".to_string()))
+}
+
+
+/// Returns the identity from `EASY_ENVAR_KEY`, or else the identity file from `EASY_ENVAR_KEY_FILE`.
+fn key() -> Option<Key> {
+    match std::env::var(KEY_VAR) {
+        Ok(identity) if !identity.trim().is_empty() => Some(Key::Inline(identity)),
+        _ => std::env::var_os(KEY_FILE_VAR).map(|file| Key::File(PathBuf::from(file))),
+    }
+}
+
+
+/// Returns whether the contents are an age file, in binary or armored form.
+fn is_age(contents: &[u8]) -> bool {
+    contents.starts_with(b"age-encryption.org/v1") || contents.starts_with(b"-----BEGIN AGE ENCRYPTED FILE-----")
+}
+
+
+/// Returns whether the contents are a `.env` file encrypted by SOPS, which records its metadata as `sops_*` entries.
+fn is_sops(contents: &[u8]) -> bool {
+    String::from_utf8_lossy(contents).lines().any(|line| line.starts_with("sops_version="))
+}
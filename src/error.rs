@@ -0,0 +1,369 @@
+use crate::Location;
+
+
+/// Represents a failure while loading environment variables.
+///
+/// Every variant carries enough context (such as the variable name)
+/// to produce a useful message on its own.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarError};
+///
+/// std::env::set_var("ERROR_DOC_PORT", "not_a_number");
+///
+/// match Envar::u16("ERROR_DOC_PORT").load() {
+///     Err(EnvarError::ParseError { key, raw, expected_type, .. }) => {
+///         assert_eq!(key, "ERROR_DOC_PORT");
+///         assert_eq!(raw, "not_a_number");
+///         assert_eq!(expected_type, "u16");
+///     },
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+///
+/// A missing variable reports a similarly named variable that is set, since it is usually a typo:
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarError};
+/// use easy_envar::testing::MockEnv;
+///
+/// let env = MockEnv::new().var("DATABSE_URL", "postgres://localhost/app");
+///
+/// let error = Envar::string("DATABASE_URL").load_from(&env).unwrap_err();
+///
+/// assert!(matches!(&error, EnvarError::Missing { suggestion: Some(name), .. } if name == "DATABSE_URL"));
+/// assert_eq!(
+///     error.to_string(),
+///     "environment variable `DATABASE_URL` is not set; did you mean `DATABSE_URL` -> `DATABASE_URL`?",
+/// );
+/// ```
+#[derive(Debug)]
+pub enum EnvarError {
+    /// The environment variable is not set.
+    Missing {
+        /// The environment variable name.
+        key: String,
+        /// The variable's description, if one was attached with `describe`.
+        description: Option<String>,
+        /// A similarly named variable that is set, such as `DATABSE_URL` for `DATABASE_URL`,
+        /// which is likely a typo for this one.
+        suggestion: Option<String>,
+    },
+
+    /// The environment variable is set, but its value is not valid unicode.
+    NotUnicode {
+        /// The environment variable name.
+        key: String,
+        /// The variable's description, if one was attached with `describe`.
+        description: Option<String>,
+    },
+
+    /// The environment variable's value could not be parsed into the expected type.
+    ParseError {
+        /// The environment variable name.
+        key: String,
+        /// The raw value that failed to parse.
+        raw: String,
+        /// The name of the type the value was expected to parse into.
+        expected_type: &'static str,
+        /// The variable's description, if one was attached with `describe`.
+        description: Option<String>,
+        /// The `.env` line that defines the value, if the source knows it.
+        location: Option<Location>,
+    },
+
+    /// The environment variable's value was parsed, but violates a constraint.
+    ConstraintViolation {
+        /// The environment variable name.
+        key: String,
+        /// The raw value that violated the constraint.
+        raw: String,
+        /// A description of the violated constraint, such as `must not be empty`.
+        constraint: String,
+        /// The variable's description, if one was attached with `describe`.
+        description: Option<String>,
+        /// The `.env` line that defines the value, if the source knows it.
+        location: Option<Location>,
+    },
+
+    /// The environment variable is defined in the `.env` file, but was never declared.
+    Undeclared {
+        /// The environment variable name.
+        key: String,
+    },
+
+    /// The loaded values break a rule spanning several variables, added with `EnvarSet::rule`.
+    RuleViolation {
+        /// Why the rule failed, as returned by the rule.
+        message: String,
+    },
+
+    /// The value refers to a variable that isn't set, as `${NAME}`, with `EnvarSpec::expand`.
+    UndefinedReference {
+        /// The name of the variable whose value holds the reference.
+        key: String,
+        /// The name of the variable that isn't set.
+        reference: String,
+    },
+
+    /// The value refers back to itself through `${NAME}` references, with `EnvarSpec::expand`.
+    ReferenceCycle {
+        /// The environment variable name.
+        key: String,
+        /// The names along the cycle, starting and ending with the same name.
+        cycle: Vec<String>,
+    },
+
+    /// A secret variable would have been embedded in the binary by `export`,
+    /// while `set_secret_export(SecretExport::Deny)` is in effect.
+    SecretExported {
+        /// The environment variable name.
+        key: String,
+    },
+
+    /// The `.env` file could not be found or read.
+    DotenvError {
+        /// The path of the file, or where it was looked for if it wasn't found.
+        path: std::path::PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+
+    /// The `.env` file is not valid.
+    Syntax {
+        /// The path of the file, if it was read from one.
+        path: Option<std::path::PathBuf>,
+        /// The 1-based line of the error.
+        line: usize,
+        /// The 1-based column of the error, in characters.
+        column: usize,
+        /// What is wrong.
+        message: String,
+    },
+
+    /// `global::init` was called after the global configuration was already initialized.
+    #[cfg(feature = "global")]
+    AlreadyInitialized,
+
+    /// A value could not be deserialized by `from_env`, such as an unknown enum variant.
+    #[cfg(feature = "serde")]
+    Deserialize {
+        /// The message reported by `serde`.
+        message: String,
+    },
+
+    /// An `envar.toml` manifest could not be read, or doesn't declare valid variables.
+    #[cfg(feature = "toml")]
+    InvalidManifest {
+        /// The path of the manifest.
+        path: std::path::PathBuf,
+        /// What is wrong with the manifest.
+        message: String,
+    },
+
+    /// An encrypted `.env` file could not be decrypted.
+    #[cfg(feature = "encrypted")]
+    Decryption {
+        /// The path of the encrypted file.
+        path: std::path::PathBuf,
+        /// Why the file could not be decrypted.
+        message: String,
+    },
+
+    /// The value of a variable could not be fetched from the remote backend it was declared with,
+    /// such as AWS Secrets Manager.
+    #[cfg(feature = "remote")]
+    Remote {
+        /// The environment variable name.
+        key: String,
+        /// The name of the backend, such as `AWS Secrets Manager`.
+        backend: &'static str,
+        /// Whether the credentials were rejected, the secret doesn't exist, or the backend failed.
+        kind: crate::RemoteErrorKind,
+        /// The message reported by the backend.
+        message: String,
+    },
+
+    /// An `HttpSource` could not be fetched, and had no cache file to fall back to.
+    #[cfg(feature = "http")]
+    Fetch {
+        /// The requested URL.
+        url: String,
+        /// Whether the request was rejected, the page doesn't exist, or the endpoint failed.
+        kind: crate::RemoteErrorKind,
+        /// What went wrong.
+        message: String,
+    },
+
+    /// A `.env` file could not be watched for changes.
+    #[cfg(feature = "watch")]
+    Watch {
+        /// The path of the watched file.
+        path: std::path::PathBuf,
+        /// Why the file could not be watched.
+        message: String,
+    },
+
+    /// A generated file could not be written.
+    Io {
+        /// The path of the file that could not be written.
+        path: std::path::PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+}
+
+
+impl std::fmt::Display for EnvarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvarError::Missing { key, description, suggestion: Some(suggestion) } => {
+                write!(f, "{} is not set; did you mean `{}` -> `{}`?", Subject(key, description), suggestion, key)
+            },
+            EnvarError::Missing { key, description, suggestion: None } => {
+                write!(f, "{} is not set", Subject(key, description))
+            },
+            EnvarError::NotUnicode { key, description } => {
+                write!(f, "{} is not valid unicode", Subject(key, description))
+            },
+            EnvarError::ParseError { key, raw, expected_type, description, .. } => {
+                write!(f, "{} has value {:?}, which is not a valid {}", Subject(key, description), raw, expected_type)
+            },
+            EnvarError::ConstraintViolation { key, raw, constraint, description, .. } => {
+                write!(f, "{} has value {:?}, but {}", Subject(key, description), raw, constraint)
+            },
+            EnvarError::Undeclared { key } => {
+                write!(f, "environment variable `{}` is defined in the .env file, but never declared", key)
+            },
+            EnvarError::RuleViolation { message } => {
+                write!(f, "configuration rule violated: {}", message)
+            },
+            EnvarError::UndefinedReference { key, reference } => {
+                write!(f, "environment variable `{}` refers to `${{{}}}`, which is not set", key, reference)
+            },
+            EnvarError::ReferenceCycle { key, cycle } => {
+                write!(f, "environment variable `{}` refers back to itself: {}", key, cycle.join(" -> "))
+            },
+            EnvarError::SecretExported { key } => {
+                write!(f, "environment variable `{}` is secret, and exporting it would embed it in the binary; load it at runtime instead", key)
+            },
+            EnvarError::DotenvError { path, source } => {
+                write!(f, "failed to load the .env file `{}`: {}", path.display(), source)
+            },
+            EnvarError::Syntax { path: Some(path), line, column, message } => {
+                write!(f, "invalid .env file `{}` at line {}, column {}: {}", path.display(), line, column, message)
+            },
+            EnvarError::Syntax { path: None, line, column, message } => {
+                write!(f, "invalid .env file at line {}, column {}: {}", line, column, message)
+            },
+            #[cfg(feature = "global")]
+            EnvarError::AlreadyInitialized => {
+                write!(f, "the global configuration is already initialized")
+            },
+            #[cfg(feature = "serde")]
+            EnvarError::Deserialize { message } => {
+                write!(f, "failed to deserialize the environment: {}", message)
+            },
+            #[cfg(feature = "toml")]
+            EnvarError::InvalidManifest { path, message } => {
+                write!(f, "invalid manifest `{}`: {}", path.display(), message)
+            },
+            #[cfg(feature = "encrypted")]
+            EnvarError::Decryption { path, message } => {
+                write!(f, "failed to decrypt `{}`: {}", path.display(), message)
+            },
+            #[cfg(feature = "remote")]
+            EnvarError::Remote { key, backend, message, .. } => {
+                write!(f, "environment variable `{}` could not be fetched from {}: {}", key, backend, message)
+            },
+            #[cfg(feature = "http")]
+            EnvarError::Fetch { url, message, .. } => {
+                write!(f, "failed to fetch configuration from `{}`: {}", url, message)
+            },
+            #[cfg(feature = "watch")]
+            EnvarError::Watch { path, message } => {
+                write!(f, "failed to watch `{}`: {}", path.display(), message)
+            },
+            EnvarError::Io { path, source } => {
+                write!(f, "failed to write `{}`: {}", path.display(), source)
+            },
+        }
+    }
+}
+
+
+impl EnvarError {
+    /// Attaches a variable's description to the error, if it reports on a single variable.
+    pub(crate) fn with_description(mut self, val: Option<&str>) -> Self {
+        match &mut self {
+            EnvarError::Missing { description, .. } |
+            EnvarError::NotUnicode { description, .. } |
+            EnvarError::ParseError { description, .. } |
+            EnvarError::ConstraintViolation { description, .. } => {
+                *description = val.map(str::to_string);
+            },
+            _ => {},
+        }
+        self
+    }
+
+    /// Attaches the location of the bad value to the error, if it reports on a value.
+    pub(crate) fn with_location(mut self, val: Option<Location>) -> Self {
+        match &mut self {
+            EnvarError::ParseError { location, .. } |
+            EnvarError::ConstraintViolation { location, .. } => {
+                *location = val;
+            },
+            _ => {},
+        }
+        self
+    }
+
+    /// Returns the `.env` line that defines the value this error reports on, if it is known.
+    ///
+    /// Only set for `ParseError` and `ConstraintViolation`, when the value was read
+    /// from a `DotenvFile` or from a file loaded into the environment by `init`.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            EnvarError::ParseError { location, .. } |
+            EnvarError::ConstraintViolation { location, .. } => location.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Attaches a did-you-mean suggestion to a `Missing` error, from the names defined in `source`.
+    pub(crate) fn with_suggestion(mut self, source: &dyn crate::Source) -> Self {
+        if let EnvarError::Missing { key, suggestion, .. } = &mut self {
+            *suggestion = crate::suggest::closest(key, &source.names());
+        }
+        self
+    }
+}
+
+
+/// Formats the variable an error is about, such as ``environment variable `PORT` (the HTTP port)``.
+struct Subject<'e>(&'e str, &'e Option<String>);
+
+
+impl std::fmt::Display for Subject<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.1 {
+            Some(description) => write!(f, "environment variable `{}` ({})", self.0, description),
+            None => write!(f, "environment variable `{}`", self.0),
+        }
+    }
+}
+
+
+impl std::error::Error for EnvarError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EnvarError::DotenvError { source, .. } => Some(source),
+            EnvarError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
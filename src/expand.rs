@@ -0,0 +1,60 @@
+//! Expansion of `${NAME}` references in values, for variables marked with `EnvarSpec::expand`.
+
+use crate::{EnvarError, Source};
+
+
+/// Replaces every `${NAME}` in `raw`, the value of `key`, with the value of `NAME` in `source`,
+/// itself expanded. `$${` is kept as a literal `${`, and any other `$` is kept as it is.
+pub(crate) fn expand(source: &dyn Source, key: &str, raw: &str) -> Result<String, EnvarError> {
+    expand_within(source, raw, &mut vec![key.to_string()])
+}
+
+
+/// Expands `raw`, the value of the last name in `chain`, which lists the names being expanded.
+fn expand_within(source: &dyn Source, raw: &str, chain: &mut Vec<String>) -> Result<String, EnvarError> {
+    let mut expanded = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("$${") {
+            expanded.push_str("${");
+            rest = after;
+            continue;
+        }
+
+        let reference = rest.strip_prefix("${").and_then(|after| Some((after, after.find('}')?)));
+        let Some((after, end)) = reference else {
+            // A `$` not starting a reference, or an unterminated `${`, is kept as it is.
+            expanded.push('$');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let name = &after[..end];
+        rest = &after[end + 1..];
+
+        if let Some(index) = chain.iter().position(|key| key == name) {
+            let mut cycle = chain[index..].to_vec();
+            cycle.push(name.to_string());
+            return Err(EnvarError::ReferenceCycle { key: chain[0].clone(), cycle });
+        }
+
+        let value = match source.var(name) {
+            Err(EnvarError::Missing { .. }) => {
+                let referrer = chain.last().cloned().unwrap_or_default();
+                return Err(EnvarError::UndefinedReference { key: referrer, reference: name.to_string() });
+            },
+            value => value?,
+        };
+
+        chain.push(name.to_string());
+        expanded.push_str(&expand_within(source, &value, chain)?);
+        chain.pop();
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
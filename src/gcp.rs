@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::remote::{not_installed, parallel, Failure, RemoteErrorKind};
+
+
+/// Fetches secrets from Google Cloud Secret Manager with the `gcloud` command, one call per secret and up to `concurrency` calls at once.
+///
+/// Each id is a secret name, read from the project configured for `gcloud`, or a full resource name
+/// such as `projects/my-project/secrets/db-password`, optionally ending in `/versions/3`.
+/// The latest version is read unless one is given. Credentials are resolved by the command as usual.
+pub(crate) fn fetch(ids: &[&str], concurrency: usize) -> HashMap<String, Result<String, Failure>> {
+    let values = parallel(ids, concurrency, |id| access(id));
+    ids.iter().map(|id| id.to_string()).zip(values).collect()
+}
+
+
+/// Returns the project and account `gcloud` uses for secrets that don't name a project,
+/// from the environment or else from its active configuration.
+pub(crate) fn context() -> String {
+    let project = std::env::var("CLOUDSDK_CORE_PROJECT").ok().or_else(|| configured("project"));
+    let account = std::env::var("CLOUDSDK_CORE_ACCOUNT").ok().or_else(|| configured("account"));
+
+    format!("project={};account={}", project.unwrap_or_default(), account.unwrap_or_default())
+}
+
+
+/// Reads `property` from the active `gcloud` configuration, such as `~/.config/gcloud/configurations/config_default`.
+fn configured(property: &str) -> Option<String> {
+    #[cfg(windows)]
+    let home = std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("gcloud"));
+    #[cfg(not(windows))]
+    let home = std::env::var_os("HOME").map(|dir| PathBuf::from(dir).join(".config").join("gcloud"));
+
+    let dir = std::env::var_os("CLOUDSDK_CONFIG").map(PathBuf::from).or(home)?;
+    let name = std::env::var("CLOUDSDK_ACTIVE_CONFIG_NAME").ok()
+        .or_else(|| std::fs::read_to_string(dir.join("active_config")).ok())
+        .map_or_else(|| "default".to_string(), |name| name.trim().to_string());
+
+    let config = std::fs::read_to_string(dir.join("configurations").join(format!("config_{}", name))).ok()?;
+    config.lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == property)
+        .map(|(_, val)| val.trim().to_string())
+}
+
+
+/// Reads one version of a secret.
+fn access(id: &str) -> Result<String, Failure> {
+    let (project, rest) = match id.strip_prefix("projects/").and_then(|rest| rest.split_once("/secrets/")) {
+        Some((project, rest)) => (Some(project), rest),
+        None => (None, id),
+    };
+    let (secret, version) = rest.split_once("/versions/").unwrap_or((rest, "latest"));
+
+    let mut command = Command::new("gcloud");
+    command.args(["secrets", "versions", "access", version, "--quiet"]).arg(format!("--secret={}", secret));
+
+    if let Some(project) = project {
+        command.arg(format!("--project={}", project));
+    }
+
+    let output = command.output().map_err(|e| not_installed("gcloud", "Google Cloud CLI", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Failure { kind: classify(&stderr), message: stderr.trim().to_string() });
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| Failure {
+        kind: RemoteErrorKind::Unavailable,
+        message: "the secret is not valid unicode".to_string(),
+    })
+}
+
+
+/// Tells authentication failures and missing secrets apart from the command's error output.
+fn classify(stderr: &str) -> RemoteErrorKind {
+    const UNAUTHORIZED: [&str; 4] = ["PERMISSION_DENIED", "UNAUTHENTICATED", "Reauthentication", "do not currently have an active account"];
+
+    if UNAUTHORIZED.iter().any(|code| stderr.contains(code)) {
+        RemoteErrorKind::Unauthorized
+    } else if stderr.contains("NOT_FOUND") {
+        RemoteErrorKind::NotFound
+    } else {
+        RemoteErrorKind::Unavailable
+    }
+}
@@ -0,0 +1,110 @@
+//! A process-wide configuration handle, initialized once at startup.
+//!
+//! Requires the `global` feature.
+//!
+//! ***
+//! # Examples
+//!
+//! ```rust
+//! use easy_envar::{global, Envar, EnvarSet};
+//!
+//! std::env::set_var("GLOBAL_DOC_PORT", "8080");
+//!
+//! global::init(&EnvarSet::new().var(Envar::u16("GLOBAL_DOC_PORT"))).unwrap();
+//!
+//! assert_eq!(global::get_as::<u16>("GLOBAL_DOC_PORT"), Some(8080));
+//! ```
+
+use std::sync::OnceLock;
+
+use crate::{EnvarConfig, EnvarError, EnvarSet, LoadedEnvar};
+
+
+static CONFIG: OnceLock<EnvarConfig<'static>> = OnceLock::new();
+
+
+/// Loads every environment variable in `set` with `EnvarSet::load_runtime`,
+/// and stores the result as the global configuration.
+///
+/// ***
+/// # Returns
+///
+/// - `Ok(&EnvarConfig)`: the stored configuration.
+/// - `Err(Vec<EnvarError>)`: every load error in declaration order,
+///   or a single `EnvarError::AlreadyInitialized` if `init` was already called successfully.
+pub fn init(set: &EnvarSet<'static>) -> Result<&'static EnvarConfig<'static>, Vec<EnvarError>> {
+    if CONFIG.get().is_some() {
+        return Err(vec![EnvarError::AlreadyInitialized]);
+    }
+
+    let loaded = set.load_runtime()?;
+
+    CONFIG.set(loaded).map_err(|_| vec![EnvarError::AlreadyInitialized])?;
+
+    Ok(config())
+}
+
+
+/// Returns the global configuration.
+///
+/// ***
+/// # Panics
+///
+/// Panics if `init` has not been called successfully.
+pub fn config() -> &'static EnvarConfig<'static> {
+    try_config().expect("easy_envar::global::init must be called before accessing the global configuration")
+}
+
+
+/// Returns the global configuration, or `None` if `init` has not been called successfully.
+pub fn try_config() -> Option<&'static EnvarConfig<'static>> {
+    CONFIG.get()
+}
+
+
+/// Returns the loaded variable with the given name.
+///
+/// See `EnvarConfig::get`. Panics if `init` has not been called successfully.
+pub fn get(key: &str) -> Option<&'static LoadedEnvar<'static>> {
+    config().get(key)
+}
+
+
+/// Returns the value of the variable with the given name, parsed into `T`.
+///
+/// See `EnvarConfig::get_as`. Panics if `init` has not been called successfully.
+pub fn get_as<T: std::str::FromStr>(key: &str) -> Option<T> {
+    config().get_as(key)
+}
+
+
+/// Returns the value of the variable with the given name if it is a `String` or `Custom` variable.
+///
+/// Panics if `init` has not been called successfully.
+pub fn get_str(key: &str) -> Option<&'static str> {
+    get(key)?.as_str()
+}
+
+
+/// Returns the value of the variable with the given name if it is a `Bool` variable.
+///
+/// Panics if `init` has not been called successfully.
+pub fn get_bool(key: &str) -> Option<bool> {
+    get(key)?.as_bool()
+}
+
+
+/// Returns the value of the variable with the given name if it is a `U16` variable.
+///
+/// Panics if `init` has not been called successfully.
+pub fn get_u16(key: &str) -> Option<u16> {
+    get(key)?.as_u16()
+}
+
+
+/// Returns the value of the variable with the given name if it is a `U32` variable.
+///
+/// Panics if `init` has not been called successfully.
+pub fn get_u32(key: &str) -> Option<u32> {
+    get(key)?.as_u32()
+}
@@ -0,0 +1,123 @@
+use crate::{EnvarSet, EnvarSpec, LoadLevel};
+
+
+/// A batch of related environment variables sharing a name prefix and options, such as every `DB_*` setting.
+///
+/// Added to a set with `EnvarSet::group`, which declares each variable under the group's prefix,
+/// so `Envar::string("HOST")` in a group prefixed with `DB_` is loaded and exported as `DB_HOST`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarGroup, EnvarSet};
+///
+/// std::env::set_var("GROUP_DOC_DB_HOST", "db.internal");
+///
+/// let database = EnvarGroup::new("GROUP_DOC_DB_")
+///     .describe("Database")
+///     .var(Envar::string("HOST").describe("the server's host name"))
+///     .var(Envar::u16("PORT").or_default(5432))
+///     .var(Envar::string("PASSWORD").warn_if_missing())
+///     .secret();
+///
+/// let set = EnvarSet::new().group(database);
+///
+/// assert_eq!(set.env_vars()[0].key(), "GROUP_DOC_DB_HOST");
+/// assert_eq!(set.env_vars()[0].description(), Some("Database: the server's host name"));
+/// assert!(set.env_vars()[2].is_secret());
+///
+/// let config = set.load_runtime().unwrap();
+///
+/// assert_eq!(config.get_as::<u16>("GROUP_DOC_DB_PORT"), Some(5432));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EnvarGroup<'a> {
+    prefix: String,
+    description: Option<String>,
+    level: Option<LoadLevel>,
+    secret: bool,
+    env_vars: Vec<EnvarSpec<'a>>,
+}
+
+
+impl<'a> EnvarGroup<'a> {
+    /// Creates an empty group whose variables are named with `prefix` followed by their declared names.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        EnvarGroup { prefix: prefix.into(), ..EnvarGroup::default() }
+    }
+
+    /// Adds an environment variable to the group.
+    pub fn var(mut self, env_var: impl Into<EnvarSpec<'a>>) -> Self {
+        self.env_vars.push(env_var.into());
+        self
+    }
+
+    /// Adds every given environment variable to the group.
+    pub fn vars<I>(mut self, env_vars: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<EnvarSpec<'a>>,
+    {
+        self.env_vars.extend(env_vars.into_iter().map(Into::into));
+        self
+    }
+
+    /// Prefixes the description of every variable with `namespace`, such as `Database: the server's host name`,
+    /// or uses it as the description of variables without one.
+    pub fn describe(mut self, namespace: impl Into<String>) -> Self {
+        self.description = Some(namespace.into());
+        self
+    }
+
+    /// Sets how a missing value is handled for every variable that doesn't set its own level.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarGroup, EnvarSet, LoadLevel};
+    ///
+    /// let set = EnvarSet::new().group(
+    ///     EnvarGroup::new("GROUP_LEVEL_DOC_CACHE_")
+    ///         .level(LoadLevel::Warn)
+    ///         .var(Envar::string("URL"))
+    ///         .var(Envar::u32("TTL").level(LoadLevel::Required)),
+    /// );
+    ///
+    /// assert_eq!(set.env_vars()[0].load_level(), LoadLevel::Warn);
+    /// assert_eq!(set.env_vars()[1].load_level(), LoadLevel::Required);
+    /// ```
+    pub fn level(mut self, level: LoadLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Shorthand for `level(LoadLevel::Warn)`, making the group's variables optional by default.
+    pub fn warn_if_missing(self) -> Self {
+        self.level(LoadLevel::Warn)
+    }
+
+    /// Masks the values of every variable in the group, as `EnvarSpec::secret` does.
+    pub fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+
+    /// Returns the variables with the group's settings applied.
+    fn into_specs(self) -> impl Iterator<Item = EnvarSpec<'a>> {
+        let EnvarGroup { prefix, description, level, secret, env_vars } = self;
+
+        env_vars.into_iter().map(move |env_var| env_var.grouped(&prefix, description.as_deref(), level, secret))
+    }
+}
+
+
+impl<'a> EnvarSet<'a> {
+    /// Adds every variable in `group` to the set, with the group's prefix and options applied.
+    ///
+    /// The set's own prefix, if any, still comes first when looking the variables up.
+    pub fn group(self, group: EnvarGroup<'a>) -> Self {
+        self.vars(group.into_specs())
+    }
+}
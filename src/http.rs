@@ -0,0 +1,340 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use base64::Engine;
+
+use crate::{EnvarError, Origin, RemoteErrorKind, Source};
+
+
+/// Key-values fetched from an HTTP endpoint or a Consul KV prefix, for configuration kept outside the environment.
+///
+/// Built with `HttpSource::get` or `HttpSource::consul`, then fetched once with `HttpSourceBuilder::fetch`.
+/// Requires the `http` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use std::io::{BufRead, BufReader, Write};
+/// use easy_envar::{Envar, EnvarSet, HttpSource};
+///
+/// // A stand-in for a configuration server, answering one request.
+/// let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+/// let url = format!("http://{}/config", listener.local_addr().unwrap());
+///
+/// std::thread::spawn(move || {
+///     let (stream, _) = listener.accept().unwrap();
+///     let mut reader = BufReader::new(stream);
+///     let mut line = String::new();
+///     while reader.read_line(&mut line).unwrap() > 2 { line.clear(); }
+///
+///     let body = r#"{"PORT": 8080, "HOST": "example.com"}"#;
+///     write!(reader.get_mut(), "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+/// });
+///
+/// let cache = std::env::temp_dir().join("http_source_doc.json");
+/// let config = HttpSource::get(&url).cache_file(&cache).fetch().unwrap();
+///
+/// let loaded = EnvarSet::new()
+///     .var(Envar::u16("PORT"))
+///     .var(Envar::string("HOST"))
+///     .load_from(&config)
+///     .unwrap();
+///
+/// assert_eq!(loaded[1].as_str(), Some("example.com"));
+/// assert!(!config.is_stale());
+///
+/// // The server is gone, so the values are read from the cache file instead.
+/// let config = HttpSource::get(&url).retries(0).cache_file(&cache).fetch().unwrap();
+///
+/// assert!(config.is_stale());
+/// assert!(EnvarSet::new().var(Envar::u16("PORT")).load_from(&config).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpSource {
+    description: String,
+    entries: HashMap<String, String>,
+    stale: bool,
+}
+
+
+/// Options for fetching an `HttpSource`.
+///
+/// Created by `HttpSource::get` or `HttpSource::consul`.
+#[derive(Debug, Clone)]
+pub struct HttpSourceBuilder {
+    endpoint: Endpoint,
+    timeout: Duration,
+    retries: u32,
+    cache_file: Option<PathBuf>,
+    headers: Vec<(String, String)>,
+}
+
+
+/// Where the key-values are fetched from, and how the response is read.
+#[derive(Debug, Clone)]
+enum Endpoint {
+    /// A URL answering with a flat JSON object or a `.env` file.
+    Url(String),
+
+    /// A prefix in Consul's KV store, on the agent at `address`.
+    Consul { address: String, prefix: String },
+}
+
+
+impl HttpSource {
+    /// Fetches from `url`, which must answer with a flat JSON object of names to values, or with a `.env` file.
+    ///
+    /// JSON numbers and booleans are read as their text. Nested objects and arrays are read as JSON text.
+    pub fn get(url: impl Into<String>) -> HttpSourceBuilder {
+        HttpSourceBuilder::new(Endpoint::Url(url.into()))
+    }
+
+    /// Fetches every key under `prefix` from the Consul agent at `address`, such as `http://127.0.0.1:8500`.
+    ///
+    /// Each key is named by the part after `prefix`, with `/` replaced by `_`,
+    /// so `app/DATABASE_URL` under the prefix `app/` is read as `DATABASE_URL`.
+    /// The ACL token is read from `CONSUL_HTTP_TOKEN`, if it is set.
+    pub fn consul(address: impl Into<String>, prefix: impl Into<String>) -> HttpSourceBuilder {
+        let mut builder = HttpSourceBuilder::new(Endpoint::Consul { address: address.into(), prefix: prefix.into() });
+
+        if let Ok(token) = std::env::var("CONSUL_HTTP_TOKEN") {
+            builder = builder.header("X-Consul-Token", token);
+        }
+
+        builder
+    }
+
+    /// Returns whether the values were read from the cache file, because the endpoint could not be reached.
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+}
+
+
+impl HttpSourceBuilder {
+    /// Creates the default options: a five-second timeout, two retries, and no cache file.
+    fn new(endpoint: Endpoint) -> Self {
+        HttpSourceBuilder {
+            endpoint,
+            timeout: Duration::from_secs(5),
+            retries: 2,
+            cache_file: None,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Sets how long each attempt may take, including connecting. Defaults to five seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many times a failed request is retried, with a doubling delay from 200 milliseconds. Defaults to two.
+    ///
+    /// Only unreachable endpoints and server errors are retried. A rejected token or a missing page is not.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Saves fetched values to `path`, and reads them from it when the endpoint can't be reached,
+    /// so a build or a restart works offline with the last known configuration.
+    ///
+    /// The file holds every value in plain text, so keep it out of version control.
+    pub fn cache_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.cache_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sends a header with every request, such as `Authorization`.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Fetches the key-values, retrying as configured and falling back to the cache file.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(HttpSource)`: the fetched values, or the cached ones if the endpoint could not be reached.
+    /// - `Err(EnvarError::Fetch)`: if the request was rejected, the page doesn't exist,
+    ///   or the endpoint could not be reached and there is no readable cache file.
+    pub fn fetch(self) -> Result<HttpSource, EnvarError> {
+        let description = self.endpoint.to_string();
+
+        match self.fetch_entries() {
+            Ok(entries) => {
+                if let Some(path) = &self.cache_file {
+                    // A cache that can't be written only matters once the endpoint is down.
+                    let _ = write_cache(path, &entries);
+                }
+                Ok(HttpSource { description, entries, stale: false })
+            },
+            Err(error @ EnvarError::Fetch { kind: RemoteErrorKind::Unavailable, .. }) => {
+                match self.cache_file.as_deref().and_then(read_cache) {
+                    Some(entries) => Ok(HttpSource { description, entries, stale: true }),
+                    None => Err(error),
+                }
+            },
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Fetches like `fetch`, as a future run on Tokio's blocking thread pool,
+    /// so retries and timeouts don't stall the runtime's other tasks.
+    ///
+    /// Must be awaited within a Tokio runtime. Requires the `tokio` feature.
+    #[cfg(feature = "tokio")]
+    pub async fn fetch_async(self) -> Result<HttpSource, EnvarError> {
+        let url = self.endpoint.url();
+
+        match tokio::task::spawn_blocking(move || self.fetch()).await {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => Err(EnvarError::Fetch { url, kind: RemoteErrorKind::Unavailable, message: "the runtime shut down".to_string() }),
+        }
+    }
+
+    /// Requests the endpoint until it answers, or the retries run out.
+    fn fetch_entries(&self) -> Result<HashMap<String, String>, EnvarError> {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let url = self.endpoint.url();
+        let mut delay = Duration::from_millis(200);
+
+        for attempt in 0..=self.retries {
+            let request = self.headers.iter().fold(agent.get(&url), |request, (name, value)| request.set(name, value));
+
+            let (kind, message) = match request.call() {
+                Ok(response) => {
+                    let body = response.into_string().map_err(|e| self.error(RemoteErrorKind::Unavailable, e.to_string()))?;
+                    return self.endpoint.parse(&body).map_err(|message| self.error(RemoteErrorKind::Unavailable, message));
+                },
+                Err(ureq::Error::Status(404, _)) if matches!(self.endpoint, Endpoint::Consul { .. }) => return Ok(HashMap::new()),
+                Err(ureq::Error::Status(code @ (401 | 403), _)) => return Err(self.error(RemoteErrorKind::Unauthorized, format!("status {}", code))),
+                Err(ureq::Error::Status(404, _)) => return Err(self.error(RemoteErrorKind::NotFound, "status 404".to_string())),
+                Err(ureq::Error::Status(code, _)) if code < 500 => return Err(self.error(RemoteErrorKind::Unavailable, format!("status {}", code))),
+                Err(ureq::Error::Status(code, _)) => (RemoteErrorKind::Unavailable, format!("status {}", code)),
+                Err(ureq::Error::Transport(e)) => (RemoteErrorKind::Unavailable, e.to_string()),
+            };
+
+            if attempt == self.retries {
+                return Err(self.error(kind, message));
+            }
+
+            std::thread::sleep(delay);
+            delay *= 2;
+        }
+
+        unreachable!("the last attempt returns")
+    }
+
+    /// Reports a failure to fetch from this endpoint.
+    fn error(&self, kind: RemoteErrorKind, message: String) -> EnvarError {
+        EnvarError::Fetch { url: self.endpoint.url(), kind, message }
+    }
+}
+
+
+impl Endpoint {
+    /// Returns the URL to request.
+    fn url(&self) -> String {
+        match self {
+            Endpoint::Url(url) => url.clone(),
+            Endpoint::Consul { address, prefix } => {
+                format!("{}/v1/kv/{}?recurse=true", address.trim_end_matches('/'), prefix.trim_start_matches('/'))
+            },
+        }
+    }
+
+    /// Reads the key-values from a response body.
+    fn parse(&self, body: &str) -> Result<HashMap<String, String>, String> {
+        match self {
+            Endpoint::Url(_) => match serde_json::from_str::<serde_json::Value>(body) {
+                Ok(serde_json::Value::Object(object)) => Ok(object.into_iter().map(|(key, val)| (key, json_text(val))).collect()),
+                Ok(_) => Err("the response is not a JSON object".to_string()),
+                Err(_) => match crate::dotenv::parse(body) {
+                    Ok(parsed) => Ok(parsed.entries.into_iter().map(|entry| (entry.key, entry.val)).collect()),
+                    Err(e) => Err(format!("the response is neither JSON nor a .env file: line {}, column {}: {}", e.line, e.column, e.message)),
+                },
+            },
+            Endpoint::Consul { prefix, .. } => {
+                let entries: Vec<serde_json::Value> = serde_json::from_str(body).map_err(|e| e.to_string())?;
+                let mut values = HashMap::new();
+
+                for entry in entries {
+                    let Some(key) = entry["Key"].as_str() else { continue };
+                    let name = key.strip_prefix(prefix.trim_start_matches('/')).unwrap_or(key).trim_start_matches('/');
+
+                    // Keys ending in `/` are folders.
+                    if name.is_empty() || name.ends_with('/') {
+                        continue;
+                    }
+
+                    let value = match entry["Value"].as_str() {
+                        Some(encoded) => base64::engine::general_purpose::STANDARD.decode(encoded)
+                            .ok()
+                            .and_then(|bytes| String::from_utf8(bytes).ok())
+                            .ok_or_else(|| format!("the value of `{}` is not valid unicode", key))?,
+                        None => String::new(),
+                    };
+
+                    values.insert(name.replace('/', "_"), value);
+                }
+
+                Ok(values)
+            },
+        }
+    }
+}
+
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Endpoint::Url(url) => write!(f, "`{}`", url),
+            Endpoint::Consul { address, prefix } => write!(f, "Consul `{}` at `{}`", prefix, address),
+        }
+    }
+}
+
+
+/// Returns a JSON value as an environment variable value: strings as-is, anything else as JSON text.
+fn json_text(val: serde_json::Value) -> String {
+    match val {
+        serde_json::Value::String(val) => val,
+        serde_json::Value::Null => String::new(),
+        val => val.to_string(),
+    }
+}
+
+
+/// Saves fetched values as a JSON object.
+fn write_cache(path: &Path, entries: &HashMap<String, String>) -> std::io::Result<()> {
+    let sorted: BTreeMap<_, _> = entries.iter().collect();
+    let json = serde_json::to_string_pretty(&sorted).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+
+/// Reads values saved by `write_cache`, if the file exists and is valid.
+fn read_cache(path: &Path) -> Option<HashMap<String, String>> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+
+impl Source for HttpSource {
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        self.entries.var(key)
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.entries.names()
+    }
+
+    fn origin(&self, _key: &str) -> Origin {
+        Origin::Remote(self.description.clone())
+    }
+}
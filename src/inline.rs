@@ -0,0 +1,40 @@
+//! Parsing and single-line rendering of structured values passed through one environment variable.
+
+
+/// Parses inline TOML, either a document such as `host = "db"` or a single value such as `{ host = "db" }`.
+#[cfg(feature = "toml")]
+pub(crate) fn parse_toml(raw: &str) -> Option<toml::Value> {
+    if let Ok(table) = raw.parse::<toml::Table>() {
+        return Some(toml::Value::Table(table));
+    }
+
+    let mut wrapper = format!("value = {}", raw).parse::<toml::Table>().ok()?;
+    wrapper.remove("value")
+}
+
+
+/// Renders a YAML value in flow style, such as `{host: "db", ports: [80, 443]}`,
+/// so it fits on a single line.
+#[cfg(feature = "yaml")]
+pub(crate) fn yaml_flow(val: &serde_yaml::Value) -> String {
+    use serde_yaml::Value;
+
+    match val {
+        Value::Null => "null".to_string(),
+        Value::Bool(val) => val.to_string(),
+        Value::Number(val) => val.to_string(),
+        // A JSON string is also a valid double-quoted YAML scalar.
+        Value::String(val) => serde_json::Value::String(val.clone()).to_string(),
+        Value::Sequence(items) => {
+            let items: Vec<String> = items.iter().map(yaml_flow).collect();
+            format!("[{}]", items.join(", "))
+        },
+        Value::Mapping(entries) => {
+            let entries: Vec<String> = entries.iter()
+                .map(|(key, val)| format!("{}: {}", yaml_flow(key), yaml_flow(val)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        },
+        Value::Tagged(tagged) => format!("{} {}", tagged.tag, yaml_flow(&tagged.value)),
+    }
+}
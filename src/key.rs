@@ -0,0 +1,158 @@
+use std::borrow::Cow;
+
+use crate::Origin;
+
+
+/// The name of a loaded variable, together with where its value was loaded from, and whether it is secret.
+///
+/// This is the first field of every `LoadedEnvar` variant. It dereferences to the name,
+/// and converts from the same types as the name of an `Envar`, so loaded variables can be built by hand
+/// as `LoadedEnvar::U16("PORT".into(), 8080)`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{LoadedEnvar, LoadedKey};
+///
+/// let key = LoadedKey::from("PORT");
+///
+/// assert_eq!(key.as_str(), "PORT");
+/// assert_eq!(key.origin(), None);
+///
+/// if let LoadedEnvar::U16(key, _) = LoadedEnvar::U16(key, 8080) {
+///     assert!(key.starts_with("PO"));
+/// }
+/// ```
+#[derive(Clone)]
+pub struct LoadedKey<'a> {
+    name: Cow<'a, str>,
+    origin: Option<Origin>,
+    secret: bool,
+}
+
+
+impl<'a> LoadedKey<'a> {
+    /// Returns the variable name.
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns where the value was loaded from, or `None` if the variable was built by hand.
+    pub fn origin(&self) -> Option<Origin> {
+        self.origin.clone()
+    }
+
+    /// Returns whether the variable was declared secret with `EnvarSpec::secret`,
+    /// so its value is masked wherever the crate prints it, even though it keeps its type.
+    pub fn is_secret(&self) -> bool {
+        self.secret
+    }
+
+    /// Returns the variable name, dropping the origin.
+    pub fn into_name(self) -> Cow<'a, str> {
+        self.name
+    }
+
+    /// Records where the value was loaded from.
+    pub(crate) fn set_origin(&mut self, origin: Origin) {
+        self.origin = Some(origin);
+    }
+
+    /// Marks the value as secret.
+    pub(crate) fn set_secret(&mut self) {
+        self.secret = true;
+    }
+}
+
+
+impl std::ops::Deref for LoadedKey<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.name
+    }
+}
+
+
+impl AsRef<str> for LoadedKey<'_> {
+    fn as_ref(&self) -> &str {
+        &self.name
+    }
+}
+
+
+impl std::borrow::Borrow<str> for LoadedKey<'_> {
+    fn borrow(&self) -> &str {
+        &self.name
+    }
+}
+
+
+impl<'a> From<Cow<'a, str>> for LoadedKey<'a> {
+    fn from(name: Cow<'a, str>) -> Self {
+        LoadedKey { name, origin: None, secret: false }
+    }
+}
+
+
+impl<'a> From<&'a str> for LoadedKey<'a> {
+    fn from(name: &'a str) -> Self {
+        LoadedKey::from(Cow::Borrowed(name))
+    }
+}
+
+
+impl From<String> for LoadedKey<'_> {
+    fn from(name: String) -> Self {
+        LoadedKey::from(Cow::Owned(name))
+    }
+}
+
+
+/// Compares names only, so loaded variables compare equal regardless of where they were loaded from.
+impl PartialEq for LoadedKey<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+
+impl Eq for LoadedKey<'_> {}
+
+
+impl PartialEq<str> for LoadedKey<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.name == other
+    }
+}
+
+
+impl PartialEq<&str> for LoadedKey<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.name == *other
+    }
+}
+
+
+impl std::hash::Hash for LoadedKey<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state)
+    }
+}
+
+
+/// Formats as the name alone.
+impl std::fmt::Debug for LoadedKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.name, f)
+    }
+}
+
+
+/// Formats as the name alone.
+impl std::fmt::Display for LoadedKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
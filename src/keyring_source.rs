@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use crate::remote::{Failure, RemoteErrorKind};
+
+
+/// Reads passwords from the OS keyring: the macOS Keychain, the Windows Credential Manager,
+/// or the Secret Service (GNOME Keyring, KWallet) on Linux.
+///
+/// Each id is a service and a user, joined by `#`. Entries that don't exist are left out,
+/// so the variable is reported missing, or falls back to its default, as if it had no keyring entry declared.
+pub(crate) fn fetch(ids: &[&str]) -> HashMap<String, Result<String, Failure>> {
+    ids.iter()
+        .filter_map(|id| {
+            let (service, user) = id.split_once('#').unwrap_or((id, ""));
+            let password = ::keyring::Entry::new(service, user).and_then(|entry| entry.get_password());
+
+            let value = match password {
+                Ok(password) => Ok(password),
+                Err(::keyring::Error::NoEntry) => return None,
+                Err(e @ ::keyring::Error::NoStorageAccess(_)) => Err(Failure {
+                    kind: RemoteErrorKind::Unauthorized,
+                    message: e.to_string(),
+                }),
+                Err(e) => Err(Failure { kind: RemoteErrorKind::Unavailable, message: e.to_string() }),
+            };
+
+            Some((id.to_string(), value))
+        })
+        .collect()
+}
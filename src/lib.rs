@@ -1,26 +1,137 @@
+mod audit;
+#[cfg(feature = "aws")]
+mod aws;
+#[cfg(feature = "azure")]
+mod azure;
+mod bytesize;
+#[cfg(feature = "clap")]
+mod cli;
+mod codegen;
+mod condition;
+mod config;
+#[cfg(feature = "config")]
+mod config_source;
+#[cfg(feature = "serde")]
+mod de;
+mod declare;
+mod diff;
+mod directive;
+mod dotenv;
+mod duration;
+#[cfg(feature = "gcp")]
+mod gcp;
+mod group;
+mod key;
+#[cfg(feature = "encrypted")]
+mod encrypted;
+mod error;
+mod expand;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(any(feature = "toml", feature = "yaml"))]
+mod inline;
+#[cfg(feature = "keyring")]
+mod keyring_source;
+mod macros;
+mod manifest;
+mod number;
+mod origin;
+mod persist;
+#[cfg(feature = "figment")]
+mod provider;
+#[cfg(feature = "remote")]
+mod remote;
+#[cfg(feature = "remote")]
+mod remote_cache;
+#[cfg(feature = "pretty-errors")]
+mod report;
+#[cfg(feature = "schema")]
+mod schema;
+mod secret;
+#[cfg(feature = "serde")]
+mod ser;
+mod set;
+mod source;
+mod spec;
+mod suggest;
+#[cfg(feature = "tracing")]
+mod trace;
+mod value;
+#[cfg(feature = "vault")]
+mod vault;
+#[cfg(feature = "watch")]
+mod watch;
+
+pub use audit::{AuditEntry, AuditReport, AuditStatus};
+pub use condition::Profile;
+pub use config::EnvarConfig;
+#[cfg(feature = "serde")]
+pub use de::{from_env, from_source};
+pub use diff::{diff, Change, DiffEntry, SourceDiff};
+pub use directive::{emit_directive, emit_directive_to, set_directive_syntax, set_secret_export, unescape_exported, DirectiveSyntax, SecretExport};
+pub use dotenv::DotenvWarning;
+#[cfg(feature = "encrypted")]
+pub use encrypted::init_encrypted;
+pub use error::EnvarError;
+pub use group::EnvarGroup;
+pub use key::LoadedKey;
+#[cfg(feature = "http")]
+pub use http::{HttpSource, HttpSourceBuilder};
+pub use manifest::Shell;
+pub use number::NumberFormat;
+pub use origin::Origin;
+pub use persist::{set, set_in_path};
+#[cfg(feature = "remote")]
+pub use remote::RemoteErrorKind;
+#[cfg(feature = "pretty-errors")]
+pub use report::Report;
+pub use secret::Secret;
+#[cfg(feature = "serde")]
+pub use ser::{Redact, Unredacted};
+pub use set::EnvarSet;
+pub use source::{DotenvFile, Location, ProcessEnv, Source, SourceStack};
+pub use spec::{Constraint, EnvarSpec, LoadLevel, RangeValue};
+pub use value::LoadedValue;
+#[cfg(feature = "watch")]
+pub use watch::{Watch, WatchedConfig};
+
+#[cfg(feature = "derive")]
+pub use easy_envar_derive::Envar;
+
+pub mod testing;
+
+#[cfg(feature = "global")]
+pub mod global;
+
+
 /// Defines environment variables.
+///
+/// Each variant has a constructor function named after it in snake case, such as `Envar::u16` for `Envar::U16`,
+/// which takes the name as a `&str` or a `String`. `Envar::Secret` is created with `Envar::secret_string`.
 /// 
 /// ***
 /// # Examples
 /// 
 /// ```rust,no_run
 /// // build.rs
-/// use easy_envar::Envar;
+/// use easy_envar::{Envar, EnvarSet};
 /// 
 /// fn main() {
 ///     easy_envar::init().unwrap();
 /// 
-///     let env_vars = [
-///         Envar::String("HOST"),
-///         Envar::U16("PORT"),
-///         Envar::U32("DATA"),
-///         Envar::Bool("SECURE"),
-///     ];
+///     let env_vars = EnvarSet::new()
+///         .var(Envar::string("HOST"))
+///         .var(Envar::u16("PORT"))
+///         .var(Envar::u32("DATA"))
+///         .var(Envar::bool("SECURE"));
 /// }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Envar<'a> {
     /// A boolean type environment variable.
+    ///
+    /// Accepts `true`, `yes`, `y`, `on`, and `1` as true, and `false`, `no`, `n`, `off`, and `0` as false,
+    /// in any case. Use `EnvarSpec::strict_bool` to only accept `true` and `false`.
     /// 
     /// ***
     /// # Examples
@@ -28,9 +139,9 @@ pub enum Envar<'a> {
     /// ```rust
     /// use easy_envar::Envar;
     /// 
-    /// let env_var = Envar::Bool("VAR_NAME");
+    /// let env_var = Envar::bool("VAR_NAME");
     /// ```
-    Bool(&'a str),
+    Bool(std::borrow::Cow<'a, str>),
 
     /// A string type environment variable.
     /// 
@@ -40,9 +151,21 @@ pub enum Envar<'a> {
     /// ```rust
     /// use easy_envar::Envar;
     /// 
-    /// let env_var = Envar::String("VAR_NAME");
+    /// let env_var = Envar::string("VAR_NAME");
+    /// ```
+    String(std::borrow::Cow<'a, str>),
+
+    /// A u8 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::u8("VAR_NAME");
     /// ```
-    String(&'a str),
+    U8(std::borrow::Cow<'a, str>),
 
     /// A u16 type environment variable.
     /// 
@@ -52,9 +175,9 @@ pub enum Envar<'a> {
     /// ```rust
     /// use easy_envar::Envar;
     /// 
-    /// let env_var = Envar::U16("VAR_NAME");
+    /// let env_var = Envar::u16("VAR_NAME");
     /// ```
-    U16(&'a str),
+    U16(std::borrow::Cow<'a, str>),
 
     /// A u32 type environment variable.
     /// 
@@ -64,170 +187,3135 @@ pub enum Envar<'a> {
     /// ```rust
     /// use easy_envar::Envar;
     /// 
-    /// let env_var = Envar::U32("VAR_NAME");
+    /// let env_var = Envar::u32("VAR_NAME");
     /// ```
-    U32(&'a str),
-}
+    U32(std::borrow::Cow<'a, str>),
 
+    /// A u64 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::u64("VAR_NAME");
+    /// ```
+    U64(std::borrow::Cow<'a, str>),
 
-/// Represents an environment variable whose value has already been loaded.
-///
-/// ***
-/// # Examples
-///
-/// ```rust,no_run
-/// use easy_envar::{Envar, LoadedEnvar};
-///
-/// let env_var: Envar = Envar::String("VAR_NAME");
-///
-/// let loaded_env_var: LoadedEnvar = env_var.load().unwrap();
-/// ```
-#[derive(Debug)]
-pub enum LoadedEnvar<'a> {
-    /// A loaded `String` environment variable.
-    ///
-    /// The first field is the environment variable name.
-    /// The second field is the string value that was loaded.
-    String(&'a str, String),
+    /// A u128 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::u128("VAR_NAME");
+    /// ```
+    U128(std::borrow::Cow<'a, str>),
 
-    /// A loaded `bool` environment variable.
-    ///
-    /// The first field is the environment variable name.
-    /// The second field is the boolean value that was loaded.
-    Bool(&'a str, bool),
+    /// A usize type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::usize("VAR_NAME");
+    /// ```
+    Usize(std::borrow::Cow<'a, str>),
 
-    /// A loaded `u16` environment variable.
-    ///
-    /// The first field is the environment variable name.
-    /// The second field is the `u16` value that was loaded.
-    U16(&'a str, u16),
+    /// A i8 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::i8("VAR_NAME");
+    /// ```
+    I8(std::borrow::Cow<'a, str>),
 
-    /// A loaded `u32` environment variable.
-    ///
-    /// The first field is the environment variable name.
-    /// The second field is the `u32` value that was loaded.
-    U32(&'a str, u32),
-}
+    /// A i16 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::i16("VAR_NAME");
+    /// ```
+    I16(std::borrow::Cow<'a, str>),
 
+    /// A i32 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::i32("VAR_NAME");
+    /// ```
+    I32(std::borrow::Cow<'a, str>),
 
-impl<'a> Envar<'a> {
-    /// Loads the environment variable's value from the system environment,
-    /// then attempts to parse it into the corresponding data type.
-    ///
+    /// A i64 type environment variable.
+    /// 
     /// ***
-    /// # Returns
-    ///
-    /// - `Ok(LoadedEnvar)`: if the value is successfully retrieved and parsed.
-    /// - `Err(..)`: if the environment variable is missing or the value is invalid for the expected type.
-    ///
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::i64("VAR_NAME");
+    /// ```
+    I64(std::borrow::Cow<'a, str>),
+
+    /// A i128 type environment variable.
+    /// 
     /// ***
     /// # Examples
-    ///
-    /// ```rust,no_run
-    /// // build.rs
+    /// 
+    /// ```rust
     /// use easy_envar::Envar;
-    ///
-    /// fn main() {
-    ///     easy_envar::init().unwrap();
-    ///
-    ///     let env_var = Envar::String("VAR_NAME");
-    ///
-    ///     env_var.load().unwrap();
-    /// }
+    /// 
+    /// let env_var = Envar::i128("VAR_NAME");
     /// ```
-    pub fn load(&self) -> Result<LoadedEnvar, Box<dyn std::error::Error>> {
-        let key = match self {
-            Envar::String(key) |
-            Envar::Bool(key) |
-            Envar::U16(key) |
-            Envar::U32(key) => *key,
-        };
+    I128(std::borrow::Cow<'a, str>),
 
-        let raw = std::env::var(key)?;
+    /// A isize type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::isize("VAR_NAME");
+    /// ```
+    Isize(std::borrow::Cow<'a, str>),
 
-        match self {
-            Envar::String(_) => {
-                let val = raw;
-                Ok(LoadedEnvar::String(key, val))
-            },
-            Envar::Bool(_) => {
-                let val = raw.parse::<bool>()?;
-                Ok(LoadedEnvar::Bool(key, val))
-            },
-            Envar::U16(_) => {
-                let val = raw.parse::<u16>()?;
-                Ok(LoadedEnvar::U16(key, val))
-            },
-            Envar::U32(_) => {
-                let val = raw.parse::<u32>()?;
-                Ok(LoadedEnvar::U32(key, val))
-            }
-        }
-    }
-}
+    /// A f32 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::f32("VAR_NAME");
+    /// ```
+    F32(std::borrow::Cow<'a, str>),
 
+    /// A f64 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::f64("VAR_NAME");
+    /// ```
+    F64(std::borrow::Cow<'a, str>),
 
-impl<'a> LoadedEnvar<'a> {
-    /// Exports this loaded environment variable as a Cargo build directive (`cargo::rustc-env`).
-    ///
-    /// When invoked in a build script, this method prints a line that instructs
-    /// Cargo to set the specified environment variable for subsequent compiler
-    /// invocations. This is useful for scenarios where you need to make certain
-    /// environment variables available at compile time (e.g., via `env!` in your code).
-    ///
+    /// A char type environment variable, such as `,`.
+    /// 
     /// ***
     /// # Examples
-    ///
+    /// 
     /// ```rust
-    /// // build.rs
-    /// use easy_envar::LoadedEnvar;
+    /// use easy_envar::Envar;
     /// 
-    /// fn main() {
-    ///     let loaded_var = LoadedEnvar::String("VAR_NAME", "some_value".to_string());
-    ///     loaded_var.export();
-    /// }
+    /// let env_var = Envar::char("VAR_NAME");
     /// ```
+    Char(std::borrow::Cow<'a, str>),
+
+    /// A non-zero u16 type environment variable.
     /// 
     /// ***
+    /// # Examples
     /// 
-    /// ```rust,ignore
-    /// # use easy_envar::LoadedEnvar;
-    /// # let loaded_var = LoadedEnvar::String("VAR_NAME", "some_value".to_string());
-    /// # loaded_var.export();
+    /// ```rust
+    /// use easy_envar::Envar;
     /// 
-    /// // main.rs
+    /// let env_var = Envar::non_zero_u16("VAR_NAME");
+    /// ```
+    NonZeroU16(std::borrow::Cow<'a, str>),
+
+    /// A non-zero u32 type environment variable.
     /// 
-    /// fn main() {
-    ///     let value = std::env!("VAR_NAME");
-    ///     assert_eq!(value, "some_value".to_string());
-    /// }
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::non_zero_u32("VAR_NAME");
     /// ```
-    pub fn export(&self) {
-        let (key, val) = match self {
-            LoadedEnvar::String(key, val) => (*key, val.clone()),
-            LoadedEnvar::Bool(key, val)   => (*key, val.to_string()),
-            LoadedEnvar::U16(key, val)    => (*key, val.to_string()),
-            LoadedEnvar::U32(key, val) => (*key, val.to_string()),
-        };
-        println!("cargo:rustc-env={}={}", key, val);
-    }
-}
+    NonZeroU32(std::borrow::Cow<'a, str>),
 
+    /// A non-zero u64 type environment variable.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::non_zero_u64("VAR_NAME");
+    /// ```
+    NonZeroU64(std::borrow::Cow<'a, str>),
 
-/// Loads the `.env` file from the root directory of your project.
-/// 
-/// This function simply calls `dotenvy::dotenv()`.
-///
-/// ***
-/// # Examples
-///
-/// ```rust,no_run
-/// // build.rs
-///
-/// fn main() {
-///     easy_envar::init().unwrap();
-/// }
-/// ```
-pub fn init() -> Result<std::path::PathBuf, dotenvy::Error> {
-    dotenvy::dotenv()
-}
\ No newline at end of file
+    /// An `IpAddr` type environment variable, such as `0.0.0.0`.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::ip_addr("VAR_NAME");
+    /// ```
+    IpAddr(std::borrow::Cow<'a, str>),
+
+    /// A `SocketAddr` type environment variable, such as `0.0.0.0:8080`.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::socket_addr("VAR_NAME");
+    /// ```
+    SocketAddr(std::borrow::Cow<'a, str>),
+
+    /// A `Duration` type environment variable, such as `30s`, `5m`, or `1h30m`.
+    ///
+    /// Accepts one or more `<number><unit>` segments, where the unit is
+    /// `ms`, `s`, `m`, `h`, or `d`. A plain number is read as milliseconds.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use std::time::Duration;
+    /// 
+    /// std::env::set_var("DURATION_DOC_TIMEOUT", "1h30m");
+    /// 
+    /// let loaded = Envar::duration("DURATION_DOC_TIMEOUT").load().unwrap();
+    /// 
+    /// assert!(matches!(loaded, LoadedEnvar::Duration(_, val) if val == Duration::from_secs(90 * 60)));
+    /// ```
+    Duration(std::borrow::Cow<'a, str>),
+
+    /// A byte count environment variable, such as `10MB`, `512KiB`, or `1G`, loaded as a `u64`.
+    ///
+    /// `KB`, `MB`, `GB`, and `TB` are powers of 1000, while `KiB`, `MiB`, `GiB`, and `TiB`,
+    /// as well as the single letters `K`, `M`, `G`, and `T`, are powers of 1024.
+    /// Units are case-insensitive, and a plain number is a count of bytes.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("BYTE_SIZE_DOC_MAX_UPLOAD", "512KiB");
+    ///
+    /// let loaded = Envar::byte_size("BYTE_SIZE_DOC_MAX_UPLOAD").load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::ByteSize(_, 524_288)));
+    /// ```
+    ByteSize(std::borrow::Cow<'a, str>),
+
+    /// A percentage environment variable, such as `85%` or `0.85`, loaded as an `f64` fraction.
+    ///
+    /// A value ending in `%` is divided by 100. Either way, the result must lie in `[0, 1]`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("PERCENT_DOC_CACHE_FILL", "85%");
+    ///
+    /// let loaded = Envar::percent("PERCENT_DOC_CACHE_FILL").load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::Percent(_, val) if val == 0.85));
+    ///
+    /// std::env::set_var("PERCENT_DOC_CACHE_FILL", "120%");
+    ///
+    /// assert!(Envar::percent("PERCENT_DOC_CACHE_FILL").load().is_err());
+    /// ```
+    Percent(std::borrow::Cow<'a, str>),
+
+    /// A hex-encoded bytes environment variable, such as `deadbeef`.
+    ///
+    /// Both upper and lower case digits are accepted.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// 
+    /// std::env::set_var("HEX_DOC_SIGNING_KEY", "DEADbeef");
+    /// 
+    /// let loaded = Envar::hex("HEX_DOC_SIGNING_KEY").load().unwrap();
+    /// 
+    /// assert!(matches!(loaded, LoadedEnvar::Hex(_, key) if key == [0xde, 0xad, 0xbe, 0xef]));
+    /// ```
+    Hex(std::borrow::Cow<'a, str>),
+
+    /// A base64-encoded bytes environment variable, such as `3q2+7w==`.
+    ///
+    /// Uses the standard alphabet with padding. Requires the `base64` feature.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::base64("VAR_NAME");
+    /// ```
+    #[cfg(feature = "base64")]
+    Base64(std::borrow::Cow<'a, str>),
+
+    /// A path type environment variable, such as `certs/server.pem`.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::path("VAR_NAME");
+    /// ```
+    Path(std::borrow::Cow<'a, str>),
+
+    /// A semantic version environment variable, such as `1.4.0`.
+    ///
+    /// Requires the `semver` feature.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::version("VAR_NAME");
+    /// ```
+    #[cfg(feature = "semver")]
+    Version(std::borrow::Cow<'a, str>),
+
+    /// A semantic version requirement environment variable, such as `>=1.2, <2`.
+    ///
+    /// Requires the `semver` feature.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::version_req("VAR_NAME");
+    /// ```
+    #[cfg(feature = "semver")]
+    VersionReq(std::borrow::Cow<'a, str>),
+
+    /// An RFC 3339 timestamp environment variable, such as `2024-06-01T02:00:00Z`.
+    ///
+    /// Requires the `chrono` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("DATE_TIME_DOC_WINDOW_START", "2024-06-01T02:00:00+09:00");
+    ///
+    /// let loaded = Envar::date_time("DATE_TIME_DOC_WINDOW_START").load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::DateTime(_, val) if val.timestamp() == 1_717_174_800));
+    /// ```
+    #[cfg(feature = "chrono")]
+    DateTime(std::borrow::Cow<'a, str>),
+
+    /// A calendar date environment variable, such as `2024-06-01`.
+    ///
+    /// Requires the `chrono` feature.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::date("VAR_NAME");
+    /// ```
+    #[cfg(feature = "chrono")]
+    Date(std::borrow::Cow<'a, str>),
+
+    /// A log level filter environment variable, such as `info` or `off`.
+    ///
+    /// Parsed into a `log::LevelFilter`, case-insensitively. Requires the `log` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("LOG_LEVEL_DOC_LEVEL", "Debug");
+    ///
+    /// let loaded = Envar::log_level("LOG_LEVEL_DOC_LEVEL").load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::LogLevel(_, log::LevelFilter::Debug)));
+    ///
+    /// std::env::set_var("LOG_LEVEL_DOC_LEVEL", "verbose");
+    ///
+    /// assert!(Envar::log_level("LOG_LEVEL_DOC_LEVEL").load().is_err());
+    /// ```
+    #[cfg(feature = "log")]
+    LogLevel(std::borrow::Cow<'a, str>),
+
+    /// A JSON environment variable, such as `{"retries": 3}`, parsed into a `serde_json::Value`.
+    ///
+    /// Requires the `serde_json` feature. Use `Envar::json_as` to validate against a specific type instead.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("JSON_DOC_EXTRA_CONFIG", r#"{"retries": 3}"#);
+    ///
+    /// let loaded = Envar::json("JSON_DOC_EXTRA_CONFIG").load().unwrap();
+    ///
+    /// assert_eq!(loaded.as_json().unwrap()["retries"], 3);
+    /// ```
+    #[cfg(feature = "serde_json")]
+    Json(std::borrow::Cow<'a, str>),
+
+    /// An inline TOML environment variable, such as `{ host = "db", port = 5432 }`.
+    ///
+    /// Either a TOML document, such as `host = "db"`, or a single TOML value is accepted.
+    /// Requires the `toml` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("TOML_DOC_DATABASE", r#"{ host = "db", port = 5432 }"#);
+    ///
+    /// let loaded = Envar::toml("TOML_DOC_DATABASE").load().unwrap();
+    ///
+    /// assert!(matches!(&loaded, LoadedEnvar::Toml(_, val) if val["port"].as_integer() == Some(5432)));
+    /// assert_eq!(loaded.value_string(), r#"{ host = "db", port = 5432 }"#);
+    /// ```
+    #[cfg(feature = "toml")]
+    Toml(std::borrow::Cow<'a, str>),
+
+    /// An inline YAML environment variable, such as `{host: db, port: 5432}`.
+    ///
+    /// Requires the `yaml` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("YAML_DOC_DATABASE", "{host: db, ports: [5432, 5433]}");
+    ///
+    /// let loaded = Envar::yaml("YAML_DOC_DATABASE").load().unwrap();
+    ///
+    /// assert!(matches!(&loaded, LoadedEnvar::Yaml(_, val) if val["ports"][1].as_u64() == Some(5433)));
+    /// assert_eq!(loaded.value_string(), r#"{"host": "db", "ports": [5432, 5433]}"#);
+    /// ```
+    #[cfg(feature = "yaml")]
+    Yaml(std::borrow::Cow<'a, str>),
+
+    /// A URL type environment variable, such as `postgres://localhost/db`.
+    ///
+    /// Requires the `url` feature.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::url("VAR_NAME");
+    /// ```
+    #[cfg(feature = "url")]
+    Url(std::borrow::Cow<'a, str>),
+
+    /// A secret string type environment variable, such as an API key.
+    ///
+    /// Loads as `LoadedEnvar::Secret`, whose value is masked in `Debug` and `Display` output.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::secret_string("VAR_NAME");
+    /// ```
+    Secret(std::borrow::Cow<'a, str>),
+
+    /// A string type environment variable restricted to a fixed set of values.
+    ///
+    /// Loads as `LoadedEnvar::String`. A value outside the set is reported as
+    /// `EnvarError::ConstraintViolation`, listing the allowed values.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError};
+    /// 
+    /// std::env::set_var("ONE_OF_DOC_LOG_LEVEL", "verbose");
+    /// 
+    /// let result = Envar::one_of("ONE_OF_DOC_LOG_LEVEL", ["trace", "debug", "info", "warn", "error"]).load();
+    /// 
+    /// assert!(matches!(result, Err(EnvarError::ConstraintViolation { .. })));
+    /// ```
+    OneOf(std::borrow::Cow<'a, str>, Vec<std::borrow::Cow<'a, str>>),
+
+    /// A comma-separated list environment variable, such as `a.com, b.com`.
+    ///
+    /// Items are trimmed, and empty items are skipped.
+    /// Use `Envar::delimiter` to split on a different character.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::list("VAR_NAME");
+    /// ```
+    List(std::borrow::Cow<'a, str>),
+
+    /// A delimited list environment variable whose items are of any type implementing `FromStr`.
+    ///
+    /// Use `Envar::list_of` to create this variant.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::list_of::<u16>("VAR_NAME");
+    /// ```
+    ListOf(std::borrow::Cow<'a, str>, ListType),
+
+    /// An environment variable of any type implementing `FromStr`.
+    /// 
+    /// Use `Envar::parse` to create this variant.
+    /// 
+    /// ***
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// 
+    /// let env_var = Envar::parse::<std::net::Ipv6Addr>("VAR_NAME");
+    /// ```
+    Custom(std::borrow::Cow<'a, str>, CustomType),
+}
+
+
+/// Describes the target type of an `Envar::Custom` variable, and how to parse a value into it.
+///
+/// Any type implementing `FromStr` can be used, and the parsed value is kept in the loaded `CustomValue`.
+#[derive(Clone)]
+pub struct CustomType {
+    name: &'static str,
+    parse: CustomParser,
+}
+
+
+/// Parses a raw value into the target type of an `Envar::Custom` variable,
+/// failing with the message of a parser given to `Envar::custom`, if any.
+type CustomParser = std::sync::Arc<dyn Fn(&str) -> Result<CustomParsed, Option<String>> + Send + Sync>;
+
+/// A parsed `Envar::Custom` value, of the variable's target type.
+type CustomParsed = std::sync::Arc<dyn std::any::Any + Send + Sync>;
+
+
+impl CustomType {
+    /// Creates a descriptor for the type `T`.
+    pub fn of<T: std::str::FromStr + Send + Sync + 'static>() -> Self {
+        CustomType {
+            name: std::any::type_name::<T>(),
+            parse: std::sync::Arc::new(|raw| match raw.parse::<T>() {
+                Ok(val) => Ok(std::sync::Arc::new(val)),
+                Err(_) => Err(None),
+            }),
+        }
+    }
+
+    /// Creates a descriptor for the type `T`, parsed from JSON.
+    #[cfg(feature = "serde_json")]
+    pub fn json<T: serde::de::DeserializeOwned + Send + Sync + 'static>() -> Self {
+        CustomType {
+            name: std::any::type_name::<T>(),
+            parse: std::sync::Arc::new(|raw| match serde_json::from_str::<T>(raw) {
+                Ok(val) => Ok(std::sync::Arc::new(val)),
+                Err(_) => Err(None),
+            }),
+        }
+    }
+
+    /// Returns the name of the target type.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+
+impl std::fmt::Debug for CustomType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomType").field(&self.name).finish()
+    }
+}
+
+
+/// The value of a loaded `Envar::Custom` variable: the raw value, and the value of the target type it was parsed into.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, LoadedEnvar};
+/// use easy_envar::testing::MockEnv;
+/// use std::net::Ipv6Addr;
+///
+/// let loaded = Envar::parse::<Ipv6Addr>("ADDR").load_from(&MockEnv::new().var("ADDR", "::1")).unwrap();
+///
+/// if let LoadedEnvar::Custom(_, val) = &loaded {
+///     assert_eq!(val.raw(), "::1");
+///     assert!(val.get::<Ipv6Addr>().unwrap().is_loopback());
+///     assert!(val.get::<String>().is_none());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CustomValue {
+    raw: String,
+    parsed: CustomParsed,
+}
+
+
+impl CustomValue {
+    /// Returns the raw value, as it was read and as it is exported.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the parsed value, or `None` if `T` is not the variable's target type.
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.parsed.downcast_ref()
+    }
+
+    /// Takes the parsed value out, or returns `None` if `T` is not the variable's target type.
+    pub(crate) fn into_parsed<T: Send + Sync + 'static>(self) -> Option<T> {
+        let parsed = self.parsed.downcast::<T>().ok()?;
+        std::sync::Arc::try_unwrap(parsed).ok()
+    }
+}
+
+
+impl std::fmt::Debug for CustomValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomValue").field(&self.raw).finish()
+    }
+}
+
+
+/// Describes the item type and delimiter of an `Envar::ListOf` variable.
+#[derive(Debug, Clone, Copy)]
+pub struct ListType {
+    item: &'static str,
+    check: fn(&str) -> bool,
+    delimiter: char,
+}
+
+
+impl ListType {
+    /// Creates a descriptor for a list of `T`, separated by `delimiter`.
+    pub fn of<T: std::str::FromStr>(delimiter: char) -> Self {
+        ListType {
+            item: std::any::type_name::<T>(),
+            check: |raw| raw.parse::<T>().is_ok(),
+            delimiter,
+        }
+    }
+
+    /// Returns the name of the item type.
+    pub fn item(&self) -> &'static str {
+        self.item
+    }
+
+    /// Returns the character that separates items.
+    pub fn delimiter(&self) -> char {
+        self.delimiter
+    }
+}
+
+
+/// Represents an environment variable whose value has already been loaded.
+///
+/// ***
+/// # Examples
+///
+/// ```rust,no_run
+/// use easy_envar::{Envar, LoadedEnvar};
+///
+/// let env_var: Envar = Envar::string("VAR_NAME");
+///
+/// let loaded_env_var: LoadedEnvar = env_var.load().unwrap();
+/// ```
+#[derive(Clone)]
+pub enum LoadedEnvar<'a> {
+    /// A loaded `String` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the string value that was loaded.
+    String(LoadedKey<'a>, String),
+
+    /// A loaded `bool` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the boolean value that was loaded.
+    Bool(LoadedKey<'a>, bool),
+
+    /// A loaded `u8` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `u8` value that was loaded.
+    U8(LoadedKey<'a>, u8),
+
+    /// A loaded `u16` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `u16` value that was loaded.
+    U16(LoadedKey<'a>, u16),
+
+    /// A loaded `u32` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `u32` value that was loaded.
+    U32(LoadedKey<'a>, u32),
+
+    /// A loaded `u64` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `u64` value that was loaded.
+    U64(LoadedKey<'a>, u64),
+
+    /// A loaded `u128` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `u128` value that was loaded.
+    U128(LoadedKey<'a>, u128),
+
+    /// A loaded `usize` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `usize` value that was loaded.
+    Usize(LoadedKey<'a>, usize),
+
+    /// A loaded `i8` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `i8` value that was loaded.
+    I8(LoadedKey<'a>, i8),
+
+    /// A loaded `i16` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `i16` value that was loaded.
+    I16(LoadedKey<'a>, i16),
+
+    /// A loaded `i32` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `i32` value that was loaded.
+    I32(LoadedKey<'a>, i32),
+
+    /// A loaded `i64` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `i64` value that was loaded.
+    I64(LoadedKey<'a>, i64),
+
+    /// A loaded `i128` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `i128` value that was loaded.
+    I128(LoadedKey<'a>, i128),
+
+    /// A loaded `isize` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `isize` value that was loaded.
+    Isize(LoadedKey<'a>, isize),
+
+    /// A loaded `f32` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `f32` value that was loaded.
+    F32(LoadedKey<'a>, f32),
+
+    /// A loaded `f64` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `f64` value that was loaded.
+    F64(LoadedKey<'a>, f64),
+
+    /// A loaded `char` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `char` value that was loaded.
+    Char(LoadedKey<'a>, char),
+
+    /// A loaded `std::num::NonZeroU16` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `std::num::NonZeroU16` value that was loaded.
+    NonZeroU16(LoadedKey<'a>, std::num::NonZeroU16),
+
+    /// A loaded `std::num::NonZeroU32` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `std::num::NonZeroU32` value that was loaded.
+    NonZeroU32(LoadedKey<'a>, std::num::NonZeroU32),
+
+    /// A loaded `std::num::NonZeroU64` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `std::num::NonZeroU64` value that was loaded.
+    NonZeroU64(LoadedKey<'a>, std::num::NonZeroU64),
+
+    /// A loaded `std::net::IpAddr` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `std::net::IpAddr` value that was loaded.
+    IpAddr(LoadedKey<'a>, std::net::IpAddr),
+
+    /// A loaded `std::net::SocketAddr` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `std::net::SocketAddr` value that was loaded.
+    SocketAddr(LoadedKey<'a>, std::net::SocketAddr),
+
+    /// A loaded `std::time::Duration` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `std::time::Duration` value that was loaded.
+    ///
+    /// Exported as a whole number of milliseconds.
+    Duration(LoadedKey<'a>, std::time::Duration),
+
+    /// A loaded `Envar::ByteSize` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the number of bytes that was loaded.
+    ///
+    /// Exported as a plain number of bytes.
+    ByteSize(LoadedKey<'a>, u64),
+
+    /// A loaded `Envar::Percent` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the fraction that was loaded, between `0.0` and `1.0`.
+    ///
+    /// Exported as the fraction, so `85%` is exported as `0.85`.
+    Percent(LoadedKey<'a>, f64),
+
+    /// A loaded `Envar::Hex` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the decoded bytes, which are exported as lowercase hex.
+    Hex(LoadedKey<'a>, Vec<u8>),
+
+    /// A loaded `Envar::Base64` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the decoded bytes, which are exported as base64.
+    #[cfg(feature = "base64")]
+    Base64(LoadedKey<'a>, Vec<u8>),
+
+    /// A loaded `std::path::PathBuf` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `std::path::PathBuf` value that was loaded.
+    Path(LoadedKey<'a>, std::path::PathBuf),
+
+    /// A loaded `semver::Version` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `semver::Version` value that was loaded.
+    #[cfg(feature = "semver")]
+    Version(LoadedKey<'a>, semver::Version),
+
+    /// A loaded `semver::VersionReq` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `semver::VersionReq` value that was loaded.
+    #[cfg(feature = "semver")]
+    VersionReq(LoadedKey<'a>, semver::VersionReq),
+
+    /// A loaded `Envar::DateTime` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the timestamp that was loaded, keeping its UTC offset.
+    ///
+    /// Exported in RFC 3339 format.
+    #[cfg(feature = "chrono")]
+    DateTime(LoadedKey<'a>, chrono::DateTime<chrono::FixedOffset>),
+
+    /// A loaded `Envar::Date` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `chrono::NaiveDate` value that was loaded.
+    #[cfg(feature = "chrono")]
+    Date(LoadedKey<'a>, chrono::NaiveDate),
+
+    /// A loaded `Envar::LogLevel` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `log::LevelFilter` value that was loaded.
+    ///
+    /// Exported in lower case, such as `info`.
+    #[cfg(feature = "log")]
+    LogLevel(LoadedKey<'a>, log::LevelFilter),
+
+    /// A loaded `Envar::Json` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `serde_json::Value` that was loaded.
+    ///
+    /// Exported as compact JSON.
+    #[cfg(feature = "serde_json")]
+    Json(LoadedKey<'a>, serde_json::Value),
+
+    /// A loaded `Envar::Toml` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `toml::Value` that was loaded, which is a table if a document was given.
+    ///
+    /// Exported as a single-line TOML value, such as `{ host = "db" }`.
+    #[cfg(feature = "toml")]
+    Toml(LoadedKey<'a>, toml::Value),
+
+    /// A loaded `Envar::Yaml` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `serde_yaml::Value` that was loaded.
+    ///
+    /// Exported as single-line, flow-style YAML, such as `{host: "db"}`.
+    #[cfg(feature = "yaml")]
+    Yaml(LoadedKey<'a>, serde_yaml::Value),
+
+    /// A loaded `url::Url` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the `url::Url` value that was loaded.
+    #[cfg(feature = "url")]
+    Url(LoadedKey<'a>, url::Url),
+
+    /// A loaded `Envar::Secret` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the masked value that was loaded.
+    Secret(LoadedKey<'a>, Secret),
+
+    /// A loaded `Envar::List` or `Envar::ListOf` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the trimmed items, which are exported joined by commas.
+    List(LoadedKey<'a>, Vec<String>),
+
+    /// A loaded `Envar::Custom` environment variable.
+    ///
+    /// The first field is the environment variable name.
+    /// The second field is the raw value together with the value of the target type it was parsed into.
+    Custom(LoadedKey<'a>, CustomValue),
+}
+
+
+/// Binds the name of any `Envar` variant, by reference or by mutable reference depending on `$envar`.
+macro_rules! envar_key {
+    ($envar:expr) => {
+        match $envar {
+            Envar::String(key) |
+            Envar::Bool(key) |
+            Envar::U8(key) |
+            Envar::U16(key) |
+            Envar::U32(key) |
+            Envar::U64(key) |
+            Envar::U128(key) |
+            Envar::Usize(key) |
+            Envar::I8(key) |
+            Envar::I16(key) |
+            Envar::I32(key) |
+            Envar::I64(key) |
+            Envar::I128(key) |
+            Envar::Isize(key) |
+            Envar::F32(key) |
+            Envar::F64(key) |
+            Envar::Char(key) |
+            Envar::NonZeroU16(key) |
+            Envar::NonZeroU32(key) |
+            Envar::NonZeroU64(key) |
+            Envar::IpAddr(key) |
+            Envar::SocketAddr(key) |
+            Envar::Duration(key) |
+            Envar::ByteSize(key) |
+            Envar::Percent(key) |
+            Envar::Hex(key) |
+            Envar::Path(key) |
+            Envar::Secret(key) |
+            Envar::OneOf(key, _) |
+            Envar::List(key) |
+            Envar::ListOf(key, _) |
+            Envar::Custom(key, _) => key,
+            #[cfg(feature = "base64")]
+            Envar::Base64(key) => key,
+            #[cfg(feature = "semver")]
+            Envar::Version(key) => key,
+            #[cfg(feature = "semver")]
+            Envar::VersionReq(key) => key,
+            #[cfg(feature = "chrono")]
+            Envar::DateTime(key) => key,
+            #[cfg(feature = "chrono")]
+            Envar::Date(key) => key,
+            #[cfg(feature = "log")]
+            Envar::LogLevel(key) => key,
+            #[cfg(feature = "serde_json")]
+            Envar::Json(key) => key,
+            #[cfg(feature = "toml")]
+            Envar::Toml(key) => key,
+            #[cfg(feature = "yaml")]
+            Envar::Yaml(key) => key,
+            #[cfg(feature = "url")]
+            Envar::Url(key) => key,
+        }
+    };
+}
+
+
+/// Defines a constructor function for each `Envar` variant holding only a name,
+/// so the name can be given as a `&str` or a `String` without `.into()`.
+macro_rules! constructors {
+    ($($(#[$attr:meta])* $name:ident => $variant:ident),* $(,)?) => {
+        impl<'a> Envar<'a> {
+            $(
+                #[doc = concat!("Defines an `Envar::", stringify!($variant), "` environment variable named `key`.")]
+                $(#[$attr])*
+                pub fn $name(key: impl Into<std::borrow::Cow<'a, str>>) -> Self {
+                    Envar::$variant(key.into())
+                }
+            )*
+        }
+    };
+}
+
+constructors! {
+    bool => Bool,
+    string => String,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    usize => Usize,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    isize => Isize,
+    f32 => F32,
+    f64 => F64,
+    char => Char,
+    non_zero_u16 => NonZeroU16,
+    non_zero_u32 => NonZeroU32,
+    non_zero_u64 => NonZeroU64,
+    ip_addr => IpAddr,
+    socket_addr => SocketAddr,
+    duration => Duration,
+    byte_size => ByteSize,
+    percent => Percent,
+    hex => Hex,
+    #[cfg(feature = "base64")]
+    base64 => Base64,
+    path => Path,
+    #[cfg(feature = "semver")]
+    version => Version,
+    #[cfg(feature = "semver")]
+    version_req => VersionReq,
+    #[cfg(feature = "chrono")]
+    date_time => DateTime,
+    #[cfg(feature = "chrono")]
+    date => Date,
+    #[cfg(feature = "log")]
+    log_level => LogLevel,
+    #[cfg(feature = "serde_json")]
+    json => Json,
+    #[cfg(feature = "toml")]
+    toml => Toml,
+    #[cfg(feature = "yaml")]
+    yaml => Yaml,
+    #[cfg(feature = "url")]
+    url => Url,
+    ///
+    /// Named `secret_string`, since `Envar::secret` marks a variable of any type as secret.
+    secret_string => Secret,
+    list => List,
+}
+
+
+impl<'a> Envar<'a> {
+    /// Defines an `Envar::OneOf` environment variable named `key`, restricted to `values`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("ONE_OF_DOC_LEVEL", "info");
+    ///
+    /// let loaded = Envar::one_of("ONE_OF_DOC_LEVEL", ["debug", "info"]).load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::String(_, level) if level == "info"));
+    /// ```
+    pub fn one_of<I, S>(key: impl Into<std::borrow::Cow<'a, str>>, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<std::borrow::Cow<'a, str>>,
+    {
+        Envar::OneOf(key.into(), values.into_iter().map(Into::into).collect())
+    }
+
+
+    /// Defines an environment variable of any type implementing `FromStr`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use std::net::Ipv6Addr;
+    ///
+    /// std::env::set_var("PARSE_DOC_ADDR", "::1");
+    ///
+    /// let loaded = Envar::parse::<Ipv6Addr>("PARSE_DOC_ADDR").load().unwrap();
+    ///
+    /// assert!(loaded.get::<Ipv6Addr>().unwrap().is_loopback());
+    ///
+    /// std::env::set_var("PARSE_DOC_ADDR", "localhost");
+    ///
+    /// assert!(Envar::parse::<Ipv6Addr>("PARSE_DOC_ADDR").load().is_err());
+    /// ```
+    pub fn parse<T: std::str::FromStr + Send + Sync + 'static>(key: impl Into<std::borrow::Cow<'a, str>>) -> Self {
+        Envar::Custom(key.into(), CustomType::of::<T>())
+    }
+
+    /// Defines an environment variable parsed by `parser`, for types that don't implement `FromStr`
+    /// or need a parser of their own.
+    ///
+    /// The value is loaded as `LoadedEnvar::Custom`, which holds both the raw value and the value `parser` returned,
+    /// available through `LoadedEnvar::get`. An error from `parser` is reported like one from `EnvarSpec::validate`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Rgb(u8, u8, u8);
+    ///
+    /// fn parse_rgb(raw: &str) -> Result<Rgb, String> {
+    ///     let hex = raw.strip_prefix('#').filter(|hex| hex.len() == 6).ok_or("must be a color such as #ff8800")?;
+    ///     let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string());
+    ///     Ok(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    /// }
+    ///
+    /// let spec = Envar::custom("ACCENT", parse_rgb);
+    ///
+    /// let loaded = spec.load_from(&MockEnv::new().var("ACCENT", "#ff8800")).unwrap();
+    ///
+    /// assert_eq!(loaded.get::<Rgb>(), Some(&Rgb(255, 136, 0)));
+    /// assert_eq!(loaded.value_string(), "#ff8800");
+    /// assert!(spec.load_from(&MockEnv::new().var("ACCENT", "orange")).is_err());
+    /// ```
+    pub fn custom<T, E>(key: impl Into<std::borrow::Cow<'a, str>>, parser: impl Fn(&str) -> Result<T, E> + Send + Sync + 'static) -> Self
+    where
+        T: Send + Sync + 'static,
+        E: std::fmt::Display,
+    {
+        let ty = CustomType {
+            name: std::any::type_name::<T>(),
+            parse: std::sync::Arc::new(move |raw| match parser(raw) {
+                Ok(val) => Ok(std::sync::Arc::new(val)),
+                Err(e) => Err(Some(e.to_string())),
+            }),
+        };
+        Envar::Custom(key.into(), ty)
+    }
+
+    /// Defines a JSON environment variable that must deserialize into `T`.
+    ///
+    /// The value is validated with `serde_json`, and loaded as `LoadedEnvar::Custom`,
+    /// which holds the raw JSON. Requires the `serde_json` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use std::collections::HashMap;
+    ///
+    /// std::env::set_var("JSON_AS_DOC_LIMITS", r#"{"upload": 10, "download": 50}"#);
+    ///
+    /// let loaded = Envar::json_as::<HashMap<String, u32>>("JSON_AS_DOC_LIMITS").load().unwrap();
+    ///
+    /// assert_eq!(loaded.get::<HashMap<String, u32>>().unwrap()["upload"], 10);
+    ///
+    /// std::env::set_var("JSON_AS_DOC_LIMITS", r#"{"upload": "ten"}"#);
+    ///
+    /// assert!(Envar::json_as::<HashMap<String, u32>>("JSON_AS_DOC_LIMITS").load().is_err());
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn json_as<T: serde::de::DeserializeOwned + Send + Sync + 'static>(key: impl Into<std::borrow::Cow<'a, str>>) -> Self {
+        Envar::Custom(key.into(), CustomType::json::<T>())
+    }
+
+    /// Defines a comma-separated list environment variable whose items are of any type implementing `FromStr`.
+    ///
+    /// Every item is validated, but kept as a string.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError, LoadedEnvar};
+    ///
+    /// std::env::set_var("LIST_OF_DOC_PORTS", "80, 443,8080");
+    ///
+    /// let loaded = Envar::list_of::<u16>("LIST_OF_DOC_PORTS").load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::List(_, ports) if ports == ["80", "443", "8080"]));
+    ///
+    /// std::env::set_var("LIST_OF_DOC_PORTS", "80,http");
+    ///
+    /// let result = Envar::list_of::<u16>("LIST_OF_DOC_PORTS").load();
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ParseError { raw, .. }) if raw == "http"));
+    /// ```
+    pub fn list_of<T: std::str::FromStr>(key: impl Into<std::borrow::Cow<'a, str>>) -> Self {
+        Envar::ListOf(key.into(), ListType::of::<T>(','))
+    }
+
+    /// Splits a list environment variable on `delimiter` instead of a comma.
+    ///
+    /// Has no effect on variables that are not `Envar::List` or `Envar::ListOf`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("DELIMITER_DOC_ORIGINS", "a.com; b.com");
+    ///
+    /// let loaded = Envar::list("DELIMITER_DOC_ORIGINS").delimiter(';').load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::List(_, origins) if origins == ["a.com", "b.com"]));
+    /// ```
+    pub fn delimiter(self, delimiter: char) -> Self {
+        match self {
+            Envar::List(key) => Envar::ListOf(key, ListType::of::<String>(delimiter)),
+            Envar::ListOf(key, ty) => Envar::ListOf(key, ListType { delimiter, ..ty }),
+            other => other,
+        }
+    }
+
+    /// Loads the environment variable's value from the system environment,
+    /// then attempts to parse it into the corresponding data type.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(LoadedEnvar)`: if the value is successfully retrieved and parsed.
+    /// - `Err(EnvarError::Missing)`: if the environment variable is not set.
+    /// - `Err(EnvarError::NotUnicode)`: if the value is not valid unicode.
+    /// - `Err(EnvarError::ParseError)`: if the value is invalid for the expected type.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // build.rs
+    /// use easy_envar::Envar;
+    ///
+    /// fn main() {
+    ///     easy_envar::init().unwrap();
+    ///
+    ///     let env_var = Envar::string("VAR_NAME");
+    ///
+    ///     env_var.load().unwrap();
+    /// }
+    /// ```
+    pub fn load(&self) -> Result<LoadedEnvar<'a>, EnvarError> {
+        self.load_from(&ProcessEnv)
+    }
+
+    /// Loads like `load`, but reads the value from `source` instead of the process environment.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use std::collections::HashMap;
+    ///
+    /// let source = HashMap::from([("PORT", "8080")]);
+    ///
+    /// assert!(matches!(Envar::u16("PORT").load_from(&source).unwrap(), LoadedEnvar::U16(_, 8080)));
+    /// ```
+    pub fn load_from(&self, source: &dyn Source) -> Result<LoadedEnvar<'a>, EnvarError> {
+        let raw = source.var(self.key()).map_err(|e| e.with_suggestion(source))?;
+
+        let loaded = match self.trims_by_default() {
+            true => self.parse_raw(self.key(), trim_value(&raw).to_string()),
+            false => self.parse_raw(self.key(), raw),
+        };
+
+        let loaded = loaded.map_err(|e| e.with_location(source.location(self.key())))?;
+        let mut loaded = loaded;
+        loaded.set_origin(source.origin(self.key()));
+
+        #[cfg(feature = "tracing")]
+        trace::loaded(&loaded);
+
+        Ok(loaded)
+    }
+
+    /// Returns the environment variable name.
+    ///
+    /// Names are either borrowed, such as a string literal, or owned, such as a name built at runtime.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::Envar;
+    ///
+    /// assert_eq!(Envar::u16("PORT").key(), "PORT");
+    ///
+    /// let service = "billing";
+    /// assert_eq!(Envar::string(format!("{}_URL", service.to_uppercase())).key(), "BILLING_URL");
+    /// ```
+    pub fn key(&self) -> &str {
+        envar_key!(self)
+    }
+
+    /// Renames this environment variable, keeping its type.
+    pub(crate) fn set_key(&mut self, new: impl Into<std::borrow::Cow<'a, str>>) {
+        *envar_key!(self) = new.into();
+    }
+
+    /// Returns a human-readable description of the expected type, such as `u16` or `list of u16`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::Envar;
+    ///
+    /// assert_eq!(Envar::u16("PORT").type_name(), "u16");
+    /// assert_eq!(Envar::one_of("LOG_LEVEL", ["debug", "info"]).type_name(), "one of: debug, info");
+    /// ```
+    pub fn type_name(&self) -> String {
+        match self {
+            Envar::String(_)     => "string".to_string(),
+            Envar::Bool(_)       => "bool".to_string(),
+            Envar::U8(_)         => "u8".to_string(),
+            Envar::U16(_)        => "u16".to_string(),
+            Envar::U32(_)        => "u32".to_string(),
+            Envar::U64(_)        => "u64".to_string(),
+            Envar::U128(_)       => "u128".to_string(),
+            Envar::Usize(_)      => "usize".to_string(),
+            Envar::I8(_)         => "i8".to_string(),
+            Envar::I16(_)        => "i16".to_string(),
+            Envar::I32(_)        => "i32".to_string(),
+            Envar::I64(_)        => "i64".to_string(),
+            Envar::I128(_)       => "i128".to_string(),
+            Envar::Isize(_)      => "isize".to_string(),
+            Envar::F32(_)        => "f32".to_string(),
+            Envar::F64(_)        => "f64".to_string(),
+            Envar::Char(_)       => "char".to_string(),
+            Envar::NonZeroU16(_) => "non-zero u16".to_string(),
+            Envar::NonZeroU32(_) => "non-zero u32".to_string(),
+            Envar::NonZeroU64(_) => "non-zero u64".to_string(),
+            Envar::IpAddr(_)     => "IP address".to_string(),
+            Envar::SocketAddr(_) => "socket address".to_string(),
+            Envar::Duration(_)   => "duration".to_string(),
+            Envar::ByteSize(_)   => "byte size".to_string(),
+            Envar::Percent(_)    => "percentage".to_string(),
+            Envar::Hex(_)        => "hex string".to_string(),
+            Envar::Path(_)       => "path".to_string(),
+            Envar::Secret(_)     => "secret string".to_string(),
+            Envar::OneOf(_, allowed) => format!("one of: {}", allowed.join(", ")),
+            Envar::List(_)       => "list".to_string(),
+            Envar::ListOf(_, ty) => match ty.delimiter {
+                ',' => format!("list of {}", ty.item),
+                delimiter => format!("list of {} separated by {:?}", ty.item, delimiter),
+            },
+            Envar::Custom(_, ty) => ty.name.to_string(),
+            #[cfg(feature = "base64")]
+            Envar::Base64(_)     => "base64 string".to_string(),
+            #[cfg(feature = "semver")]
+            Envar::Version(_)    => "semantic version".to_string(),
+            #[cfg(feature = "semver")]
+            Envar::VersionReq(_) => "version requirement".to_string(),
+            #[cfg(feature = "chrono")]
+            Envar::DateTime(_)   => "RFC 3339 timestamp".to_string(),
+            #[cfg(feature = "chrono")]
+            Envar::Date(_)       => "date".to_string(),
+            #[cfg(feature = "log")]
+            Envar::LogLevel(_)   => "log level".to_string(),
+            #[cfg(feature = "serde_json")]
+            Envar::Json(_)       => "JSON".to_string(),
+            #[cfg(feature = "toml")]
+            Envar::Toml(_)       => "TOML".to_string(),
+            #[cfg(feature = "yaml")]
+            Envar::Yaml(_)       => "YAML".to_string(),
+            #[cfg(feature = "url")]
+            Envar::Url(_)        => "URL".to_string(),
+        }
+    }
+
+    /// Creates the environment variable whose `type_name` is `type_name`,
+    /// such as `Envar::U16(key)` for `u16`. Also accepts the variant names, such as `U16` or `NonZeroU16`.
+    ///
+    /// Returns `None` for unknown names, and for types that can't be described by name alone,
+    /// such as `ListOf` and `Custom`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::Envar;
+    ///
+    /// assert!(matches!(Envar::from_type_name("PORT", "u16"), Some(Envar::U16(key)) if key == "PORT"));
+    /// assert!(matches!(Envar::from_type_name("TIMEOUT", "Duration"), Some(Envar::Duration(key)) if key == "TIMEOUT"));
+    /// assert!(matches!(Envar::from_type_name("LEVEL", "one of: debug, info"), Some(Envar::OneOf(key, allowed)) if key == "LEVEL" && allowed == ["debug", "info"]));
+    /// assert!(Envar::from_type_name("PORT", "port number").is_none());
+    /// ```
+    pub fn from_type_name(key: impl Into<std::borrow::Cow<'a, str>>, type_name: &str) -> Option<Self> {
+        let key = key.into();
+
+        if let Some(allowed) = type_name.strip_prefix("one of: ") {
+            let allowed = allowed.split(", ").map(|value| value.to_string().into()).collect();
+            return Some(Envar::OneOf(key, allowed));
+        }
+
+        let envar = match type_name {
+            "string" | "String"                  => Envar::String(key),
+            "bool" | "Bool"                      => Envar::Bool(key),
+            "u8" | "U8"                          => Envar::U8(key),
+            "u16" | "U16"                        => Envar::U16(key),
+            "u32" | "U32"                        => Envar::U32(key),
+            "u64" | "U64"                        => Envar::U64(key),
+            "u128" | "U128"                      => Envar::U128(key),
+            "usize" | "Usize"                    => Envar::Usize(key),
+            "i8" | "I8"                          => Envar::I8(key),
+            "i16" | "I16"                        => Envar::I16(key),
+            "i32" | "I32"                        => Envar::I32(key),
+            "i64" | "I64"                        => Envar::I64(key),
+            "i128" | "I128"                      => Envar::I128(key),
+            "isize" | "Isize"                    => Envar::Isize(key),
+            "f32" | "F32"                        => Envar::F32(key),
+            "f64" | "F64"                        => Envar::F64(key),
+            "char" | "Char"                      => Envar::Char(key),
+            "non-zero u16" | "NonZeroU16"        => Envar::NonZeroU16(key),
+            "non-zero u32" | "NonZeroU32"        => Envar::NonZeroU32(key),
+            "non-zero u64" | "NonZeroU64"        => Envar::NonZeroU64(key),
+            "IP address" | "IpAddr"              => Envar::IpAddr(key),
+            "socket address" | "SocketAddr"      => Envar::SocketAddr(key),
+            "duration" | "Duration"              => Envar::Duration(key),
+            "byte size" | "ByteSize"             => Envar::ByteSize(key),
+            "percentage" | "Percent"             => Envar::Percent(key),
+            "hex string" | "Hex"                 => Envar::Hex(key),
+            "path" | "Path"                      => Envar::Path(key),
+            "secret string" | "Secret"           => Envar::Secret(key),
+            "list" | "List"                      => Envar::List(key),
+            #[cfg(feature = "base64")]
+            "base64 string" | "Base64"           => Envar::Base64(key),
+            #[cfg(feature = "semver")]
+            "semantic version" | "Version"       => Envar::Version(key),
+            #[cfg(feature = "semver")]
+            "version requirement" | "VersionReq" => Envar::VersionReq(key),
+            #[cfg(feature = "chrono")]
+            "RFC 3339 timestamp" | "DateTime"    => Envar::DateTime(key),
+            #[cfg(feature = "chrono")]
+            "date" | "Date"                      => Envar::Date(key),
+            #[cfg(feature = "log")]
+            "log level" | "LogLevel"             => Envar::LogLevel(key),
+            #[cfg(feature = "serde_json")]
+            "JSON" | "Json"                      => Envar::Json(key),
+            #[cfg(feature = "toml")]
+            "TOML" | "Toml"                      => Envar::Toml(key),
+            #[cfg(feature = "yaml")]
+            "YAML" | "Yaml"                      => Envar::Yaml(key),
+            #[cfg(feature = "url")]
+            "URL" | "Url"                        => Envar::Url(key),
+            _ => return None,
+        };
+
+        Some(envar)
+    }
+
+    /// Makes this environment variable optional, falling back to `default` when it is not set.
+    ///
+    /// The default is parsed exactly like a value read from the environment,
+    /// so an invalid default is reported as `EnvarError::ParseError`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// let loaded = Envar::u16("OR_DEFAULT_DOC_PORT").or_default(8080).load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::U16(_, 8080)));
+    /// ```
+    pub fn or_default(self, default: impl ToString) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).or_default(default)
+    }
+
+    /// Falls back to `default` when the variable is not set and the crate is built with `profile`.
+    ///
+    /// See `EnvarSpec::or_default_in`.
+    pub fn or_default_in(self, profile: Profile, default: impl ToString) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).or_default_in(profile, default)
+    }
+
+    /// Requires the parsed value to lie within `range`.
+    ///
+    /// See `EnvarSpec::range`.
+    pub fn range<T, R>(self, range: R) -> EnvarSpec<'a>
+    where
+        T: RangeValue,
+        R: std::ops::RangeBounds<T> + Send + Sync + 'static,
+    {
+        EnvarSpec::from(self).range(range)
+    }
+
+    /// Requires the value to contain at least one non-whitespace character.
+    ///
+    /// See `EnvarSpec::non_empty`.
+    pub fn non_empty(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).non_empty()
+    }
+
+    /// Sets how a missing value is handled.
+    ///
+    /// See `EnvarSpec::level`.
+    pub fn level(self, level: LoadLevel) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).level(level)
+    }
+
+    /// Normalizes the value with `transform` after parsing it.
+    ///
+    /// See `EnvarSpec::map`.
+    pub fn map(self, transform: impl Fn(&str) -> String + Send + Sync + 'static) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).map(transform)
+    }
+
+    /// Requires `check` to accept the value.
+    ///
+    /// See `EnvarSpec::validate`.
+    pub fn validate<T, E>(self, check: impl Fn(&str) -> Result<T, E> + Send + Sync + 'static) -> EnvarSpec<'a>
+    where
+        E: std::fmt::Display,
+    {
+        EnvarSpec::from(self).validate(check)
+    }
+
+    /// Prints a `cargo:warning` instead of failing when the value is missing.
+    ///
+    /// See `EnvarSpec::warn_if_missing`.
+    pub fn warn_if_missing(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).warn_if_missing()
+    }
+
+    /// Requires this variable only when a cargo feature is enabled.
+    ///
+    /// See `EnvarSpec::required_if_feature`.
+    pub fn required_if_feature(self, feature: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).required_if_feature(feature)
+    }
+
+    /// Requires this variable only when building for a target with a `cfg` option set to `value`.
+    ///
+    /// See `EnvarSpec::required_on`.
+    pub fn required_on(self, name: impl Into<String>, value: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).required_on(name, value)
+    }
+
+    /// Requires this variable only when the crate is built with `profile`.
+    ///
+    /// See `EnvarSpec::required_in`.
+    pub fn required_in(self, profile: Profile) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).required_in(profile)
+    }
+
+    /// Attaches a human-readable description, used in errors, audit reports, generated templates, and schemas.
+    ///
+    /// See `EnvarSpec::describe`.
+    pub fn describe(self, description: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).describe(description)
+    }
+
+    /// Marks this environment variable as secret, masking its value in diagnostic output.
+    ///
+    /// See `EnvarSpec::secret`.
+    pub fn secret(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).secret()
+    }
+
+    /// Expands `${NAME}` references in the value with the values of other variables.
+    ///
+    /// See `EnvarSpec::expand`.
+    pub fn expand(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).expand()
+    }
+
+    /// Accepts integers spelled in `format`, such as `1_000_000` or `0x1F`.
+    ///
+    /// See `EnvarSpec::number_format`.
+    pub fn number_format(self, format: NumberFormat) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).number_format(format)
+    }
+
+    /// Only accepts `true` and `false` for a boolean.
+    ///
+    /// See `EnvarSpec::strict_bool`.
+    pub fn strict_bool(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).strict_bool()
+    }
+
+    /// Controls whether surrounding whitespace and quotes are stripped before parsing.
+    ///
+    /// See `EnvarSpec::trim`.
+    pub fn trim(self, trim: bool) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).trim(trim)
+    }
+
+    /// Also matches names that differ only in ASCII case, with a warning when it does.
+    ///
+    /// See `EnvarSpec::case_insensitive`.
+    pub fn case_insensitive(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).case_insensitive()
+    }
+
+    /// Falls back to `key` when the variable is not set.
+    ///
+    /// See `EnvarSpec::or_env`.
+    pub fn or_env(self, key: impl Into<std::borrow::Cow<'a, str>>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).or_env(key)
+    }
+
+    /// Fetches the value from AWS Secrets Manager when the variable is not set.
+    ///
+    /// See `EnvarSpec::from_aws`.
+    #[cfg(feature = "aws")]
+    pub fn from_aws(self, secret_id: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).from_aws(secret_id)
+    }
+
+    /// Fetches the value from a field of a HashiCorp Vault KV v2 secret when the variable is not set.
+    ///
+    /// See `EnvarSpec::from_vault`.
+    #[cfg(feature = "vault")]
+    pub fn from_vault(self, path: impl std::fmt::Display, field: impl std::fmt::Display) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).from_vault(path, field)
+    }
+
+    /// Fetches the value from Google Cloud Secret Manager when the variable is not set.
+    ///
+    /// See `EnvarSpec::from_gcp`.
+    #[cfg(feature = "gcp")]
+    pub fn from_gcp(self, secret: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).from_gcp(secret)
+    }
+
+    /// Fetches the value from Azure Key Vault when the variable is not set.
+    ///
+    /// See `EnvarSpec::from_azure`.
+    #[cfg(feature = "azure")]
+    pub fn from_azure(self, secret: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).from_azure(secret)
+    }
+
+    /// Reads the value from the OS keyring when the variable is not set.
+    ///
+    /// See `EnvarSpec::from_keyring`.
+    #[cfg(feature = "keyring")]
+    pub fn from_keyring(self, service: impl std::fmt::Display) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).from_keyring(service)
+    }
+
+    /// Fetches the value from the secret store chosen per deployment when the variable is not set.
+    ///
+    /// See `EnvarSpec::from_secret_store`.
+    #[cfg(any(feature = "aws", feature = "azure", feature = "gcp"))]
+    pub fn from_secret_store(self, name: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).from_secret_store(name)
+    }
+
+    /// Also loads the value from a deprecated former name, with a warning when it is used.
+    ///
+    /// See `EnvarSpec::deprecated_alias`.
+    pub fn deprecated_alias(self, alias: impl Into<String>) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).deprecated_alias(alias)
+    }
+
+    /// Requires the value to be a path that exists.
+    ///
+    /// See `EnvarSpec::must_exist`.
+    pub fn must_exist(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).must_exist()
+    }
+
+    /// Requires the value to be a path to an existing file.
+    ///
+    /// See `EnvarSpec::must_be_file`.
+    pub fn must_be_file(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).must_be_file()
+    }
+
+    /// Requires the value to be a path to an existing directory.
+    ///
+    /// See `EnvarSpec::must_be_dir`.
+    pub fn must_be_dir(self) -> EnvarSpec<'a> {
+        EnvarSpec::from(self).must_be_dir()
+    }
+
+    /// Requires the value to be a URL with one of the given schemes.
+    ///
+    /// See `EnvarSpec::schemes`.
+    #[cfg(feature = "url")]
+    pub fn schemes<I, S>(self, schemes: I) -> EnvarSpec<'a>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        EnvarSpec::from(self).schemes(schemes)
+    }
+
+    /// Returns whether this is an integer variable, whose spelling is controlled by `NumberFormat`.
+    pub(crate) fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Envar::U8(_) | Envar::U16(_) | Envar::U32(_) | Envar::U64(_) | Envar::U128(_) | Envar::Usize(_) |
+            Envar::I8(_) | Envar::I16(_) | Envar::I32(_) | Envar::I64(_) | Envar::I128(_) | Envar::Isize(_) |
+            Envar::NonZeroU16(_) | Envar::NonZeroU32(_) | Envar::NonZeroU64(_)
+        )
+    }
+
+    /// Returns whether values are trimmed before parsing unless `EnvarSpec::trim` says otherwise,
+    /// which is the case for booleans and numbers.
+    pub(crate) fn trims_by_default(&self) -> bool {
+        matches!(
+            self,
+            Envar::Bool(_) |
+            Envar::U8(_) | Envar::U16(_) | Envar::U32(_) | Envar::U64(_) | Envar::U128(_) | Envar::Usize(_) |
+            Envar::I8(_) | Envar::I16(_) | Envar::I32(_) | Envar::I64(_) | Envar::I128(_) | Envar::Isize(_) |
+            Envar::F32(_) | Envar::F64(_) |
+            Envar::NonZeroU16(_) | Envar::NonZeroU32(_) | Envar::NonZeroU64(_) |
+            Envar::ByteSize(_) | Envar::Percent(_)
+        )
+    }
+
+    /// Parses a raw value into the corresponding data type.
+    ///
+    /// `lookup` is the name the value was read from, which is reported in errors.
+    pub(crate) fn parse_raw(&self, lookup: &str, raw: String) -> Result<LoadedEnvar<'a>, EnvarError> {
+        let key = LoadedKey::from(envar_key!(self).clone());
+
+        match self {
+            Envar::String(_) => {
+                let val = raw;
+                Ok(LoadedEnvar::String(key, val))
+            },
+            Envar::Bool(_) => {
+                let val = parse_bool(&raw).ok_or_else(|| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "bool",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Bool(key, val))
+            },
+            Envar::U8(_) => {
+                let val = parse::<u8>(lookup, raw, "u8")?;
+                Ok(LoadedEnvar::U8(key, val))
+            },
+            Envar::U16(_) => {
+                let val = parse::<u16>(lookup, raw, "u16")?;
+                Ok(LoadedEnvar::U16(key, val))
+            },
+            Envar::U32(_) => {
+                let val = parse::<u32>(lookup, raw, "u32")?;
+                Ok(LoadedEnvar::U32(key, val))
+            },
+            Envar::U64(_) => {
+                let val = parse::<u64>(lookup, raw, "u64")?;
+                Ok(LoadedEnvar::U64(key, val))
+            },
+            Envar::U128(_) => {
+                let val = parse::<u128>(lookup, raw, "u128")?;
+                Ok(LoadedEnvar::U128(key, val))
+            },
+            Envar::Usize(_) => {
+                let val = parse::<usize>(lookup, raw, "usize")?;
+                Ok(LoadedEnvar::Usize(key, val))
+            },
+            Envar::I8(_) => {
+                let val = parse::<i8>(lookup, raw, "i8")?;
+                Ok(LoadedEnvar::I8(key, val))
+            },
+            Envar::I16(_) => {
+                let val = parse::<i16>(lookup, raw, "i16")?;
+                Ok(LoadedEnvar::I16(key, val))
+            },
+            Envar::I32(_) => {
+                let val = parse::<i32>(lookup, raw, "i32")?;
+                Ok(LoadedEnvar::I32(key, val))
+            },
+            Envar::I64(_) => {
+                let val = parse::<i64>(lookup, raw, "i64")?;
+                Ok(LoadedEnvar::I64(key, val))
+            },
+            Envar::I128(_) => {
+                let val = parse::<i128>(lookup, raw, "i128")?;
+                Ok(LoadedEnvar::I128(key, val))
+            },
+            Envar::Isize(_) => {
+                let val = parse::<isize>(lookup, raw, "isize")?;
+                Ok(LoadedEnvar::Isize(key, val))
+            },
+            Envar::F32(_) => {
+                let val = parse::<f32>(lookup, raw, "f32")?;
+                Ok(LoadedEnvar::F32(key, val))
+            },
+            Envar::F64(_) => {
+                let val = parse::<f64>(lookup, raw, "f64")?;
+                Ok(LoadedEnvar::F64(key, val))
+            },
+            Envar::Char(_) => {
+                let val = parse::<char>(lookup, raw, "char")?;
+                Ok(LoadedEnvar::Char(key, val))
+            },
+            Envar::NonZeroU16(_) => {
+                let val = parse::<std::num::NonZeroU16>(lookup, raw, "non-zero u16")?;
+                Ok(LoadedEnvar::NonZeroU16(key, val))
+            },
+            Envar::NonZeroU32(_) => {
+                let val = parse::<std::num::NonZeroU32>(lookup, raw, "non-zero u32")?;
+                Ok(LoadedEnvar::NonZeroU32(key, val))
+            },
+            Envar::NonZeroU64(_) => {
+                let val = parse::<std::num::NonZeroU64>(lookup, raw, "non-zero u64")?;
+                Ok(LoadedEnvar::NonZeroU64(key, val))
+            },
+            Envar::IpAddr(_) => {
+                let val = parse::<std::net::IpAddr>(lookup, raw, "IP address")?;
+                Ok(LoadedEnvar::IpAddr(key, val))
+            },
+            Envar::SocketAddr(_) => {
+                let val = parse::<std::net::SocketAddr>(lookup, raw, "socket address")?;
+                Ok(LoadedEnvar::SocketAddr(key, val))
+            },
+            Envar::Duration(_) => {
+                let val = duration::parse(&raw).ok_or_else(|| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "duration",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Duration(key, val))
+            },
+            Envar::ByteSize(_) => {
+                let val = bytesize::parse(&raw).ok_or_else(|| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "byte size",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::ByteSize(key, val))
+            },
+            Envar::Percent(_) => {
+                let val = parse_percent(&raw).ok_or_else(|| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "percentage between 0% and 100%",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Percent(key, val))
+            },
+            Envar::Hex(_) => {
+                let val = decode_hex(&raw).ok_or_else(|| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "hex string",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Hex(key, val))
+            },
+            #[cfg(feature = "base64")]
+            Envar::Base64(_) => {
+                use base64::Engine;
+
+                let val = base64::engine::general_purpose::STANDARD.decode(&raw).map_err(|_| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "base64 string",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Base64(key, val))
+            },
+            Envar::Path(_) => {
+                let val = std::path::PathBuf::from(raw);
+                Ok(LoadedEnvar::Path(key, val))
+            },
+            #[cfg(feature = "semver")]
+            Envar::Version(_) => {
+                let val = parse::<semver::Version>(lookup, raw, "semantic version")?;
+                Ok(LoadedEnvar::Version(key, val))
+            },
+            #[cfg(feature = "semver")]
+            Envar::VersionReq(_) => {
+                let val = parse::<semver::VersionReq>(lookup, raw, "version requirement")?;
+                Ok(LoadedEnvar::VersionReq(key, val))
+            },
+            #[cfg(feature = "chrono")]
+            Envar::DateTime(_) => {
+                let val = chrono::DateTime::parse_from_rfc3339(&raw).map_err(|_| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "RFC 3339 timestamp",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::DateTime(key, val))
+            },
+            #[cfg(feature = "chrono")]
+            Envar::Date(_) => {
+                let val = parse::<chrono::NaiveDate>(lookup, raw, "date")?;
+                Ok(LoadedEnvar::Date(key, val))
+            },
+            #[cfg(feature = "log")]
+            Envar::LogLevel(_) => {
+                let val = parse::<log::LevelFilter>(lookup, raw, "log level")?;
+                Ok(LoadedEnvar::LogLevel(key, val))
+            },
+            #[cfg(feature = "serde_json")]
+            Envar::Json(_) => {
+                let val = serde_json::from_str(&raw).map_err(|_| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "JSON",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Json(key, val))
+            },
+            #[cfg(feature = "toml")]
+            Envar::Toml(_) => {
+                let val = inline::parse_toml(&raw).ok_or_else(|| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "TOML",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Toml(key, val))
+            },
+            #[cfg(feature = "yaml")]
+            Envar::Yaml(_) => {
+                let val = serde_yaml::from_str(&raw).map_err(|_| EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: "YAML",
+                    description: None,
+                    location: None,
+                })?;
+                Ok(LoadedEnvar::Yaml(key, val))
+            },
+            #[cfg(feature = "url")]
+            Envar::Url(_) => {
+                let val = parse::<url::Url>(lookup, raw, "URL")?;
+                Ok(LoadedEnvar::Url(key, val))
+            },
+            Envar::Secret(_) => {
+                let val = Secret::new(raw);
+                Ok(LoadedEnvar::Secret(key, val))
+            },
+            Envar::OneOf(_, allowed) => {
+                if !allowed.iter().any(|value| *value == raw) {
+                    return Err(EnvarError::ConstraintViolation {
+                        key: lookup.to_string(),
+                        raw,
+                        constraint: format!("must be one of: {}", allowed.join(", ")),
+                        description: None,
+                        location: None,
+                    });
+                }
+                Ok(LoadedEnvar::String(key, raw))
+            },
+            Envar::List(_) => {
+                let val = split(&raw, ',');
+                Ok(LoadedEnvar::List(key, val))
+            },
+            Envar::ListOf(_, ty) => {
+                let val = split(&raw, ty.delimiter);
+                if let Some(item) = val.iter().find(|item| !(ty.check)(item)) {
+                    return Err(EnvarError::ParseError {
+                        key: lookup.to_string(),
+                        raw: item.clone(),
+                        expected_type: ty.item,
+                        description: None,
+                        location: None,
+                    });
+                }
+                Ok(LoadedEnvar::List(key, val))
+            },
+            Envar::Custom(_, ty) => match (ty.parse)(&raw) {
+                Ok(parsed) => Ok(LoadedEnvar::Custom(key, CustomValue { raw, parsed })),
+                Err(None) => Err(EnvarError::ParseError {
+                    key: lookup.to_string(),
+                    raw,
+                    expected_type: ty.name,
+                    description: None,
+                    location: None,
+                }),
+                Err(Some(violation)) => Err(EnvarError::ConstraintViolation {
+                    key: lookup.to_string(),
+                    raw,
+                    constraint: violation,
+                    description: None,
+                    location: None,
+                }),
+            },
+        }
+    }
+}
+
+
+/// Binds the name of any `LoadedEnvar` variant, by value or by reference depending on `$env_var`.
+macro_rules! loaded_key {
+    ($env_var:expr) => {
+        match $env_var {
+            LoadedEnvar::String(key, _) |
+            LoadedEnvar::Bool(key, _) |
+            LoadedEnvar::U8(key, _) |
+            LoadedEnvar::U16(key, _) |
+            LoadedEnvar::U32(key, _) |
+            LoadedEnvar::U64(key, _) |
+            LoadedEnvar::U128(key, _) |
+            LoadedEnvar::Usize(key, _) |
+            LoadedEnvar::I8(key, _) |
+            LoadedEnvar::I16(key, _) |
+            LoadedEnvar::I32(key, _) |
+            LoadedEnvar::I64(key, _) |
+            LoadedEnvar::I128(key, _) |
+            LoadedEnvar::Isize(key, _) |
+            LoadedEnvar::F32(key, _) |
+            LoadedEnvar::F64(key, _) |
+            LoadedEnvar::Char(key, _) |
+            LoadedEnvar::NonZeroU16(key, _) |
+            LoadedEnvar::NonZeroU32(key, _) |
+            LoadedEnvar::NonZeroU64(key, _) |
+            LoadedEnvar::IpAddr(key, _) |
+            LoadedEnvar::SocketAddr(key, _) |
+            LoadedEnvar::Duration(key, _) |
+            LoadedEnvar::ByteSize(key, _) |
+            LoadedEnvar::Percent(key, _) |
+            LoadedEnvar::Hex(key, _) |
+            LoadedEnvar::Path(key, _) |
+            LoadedEnvar::Secret(key, _) |
+            LoadedEnvar::List(key, _) |
+            LoadedEnvar::Custom(key, _) => key,
+            #[cfg(feature = "base64")]
+            LoadedEnvar::Base64(key, _) => key,
+            #[cfg(feature = "semver")]
+            LoadedEnvar::Version(key, _) => key,
+            #[cfg(feature = "semver")]
+            LoadedEnvar::VersionReq(key, _) => key,
+            #[cfg(feature = "chrono")]
+            LoadedEnvar::DateTime(key, _) => key,
+            #[cfg(feature = "chrono")]
+            LoadedEnvar::Date(key, _) => key,
+            #[cfg(feature = "log")]
+            LoadedEnvar::LogLevel(key, _) => key,
+            #[cfg(feature = "serde_json")]
+            LoadedEnvar::Json(key, _) => key,
+            #[cfg(feature = "toml")]
+            LoadedEnvar::Toml(key, _) => key,
+            #[cfg(feature = "yaml")]
+            LoadedEnvar::Yaml(key, _) => key,
+            #[cfg(feature = "url")]
+            LoadedEnvar::Url(key, _) => key,
+        }
+    };
+}
+
+
+impl<'a> LoadedEnvar<'a> {
+    /// Exports this loaded environment variable as a Cargo build directive (`cargo::rustc-env`).
+    ///
+    /// When invoked in a build script, this method prints a line that instructs
+    /// Cargo to set the specified environment variable for subsequent compiler
+    /// invocations. This is useful for scenarios where you need to make certain
+    /// environment variables available at compile time (e.g., via `env!` in your code).
+    ///
+    /// Values may contain `=`, `#`, and any unicode. A directive ends at a line break, so line breaks
+    /// and backslashes are exported escaped; decode the value with `unescape_exported`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::LoadedEnvar;
+    /// 
+    /// fn main() {
+    ///     let loaded_var = LoadedEnvar::String("VAR_NAME".into(), "some_value".to_string());
+    ///     loaded_var.export();
+    ///
+    ///     // Printed as `cargo:rustc-env=MOTD=line one\nline two`.
+    ///     let multi_line = LoadedEnvar::String("MOTD".into(), "line one\nline two".to_string());
+    ///     multi_line.export();
+    /// }
+    /// ```
+    /// 
+    /// ***
+    /// 
+    /// ```rust,ignore
+    /// # use easy_envar::LoadedEnvar;
+    /// # let loaded_var = LoadedEnvar::String("VAR_NAME".into(), "some_value".to_string());
+    /// # loaded_var.export();
+    /// 
+    /// // main.rs
+    /// 
+    /// fn main() {
+    ///     let value = std::env!("VAR_NAME");
+    ///     assert_eq!(value, "some_value".to_string());
+    /// }
+    /// ```
+    pub fn export(&self) {
+        directive::print(|out| self.export_to(out));
+    }
+
+    /// Writes the directive printed by `export` to `out` instead of stdout.
+    ///
+    /// This lets tests assert the exact directives a build script emits,
+    /// and lets build systems other than Cargo collect them.
+    /// A `Secret` variable is handled as selected with `set_secret_export`.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if the directive was written.
+    /// - `Err(std::io::Error)`: if writing to `out` failed, or, with the `PermissionDenied` kind,
+    ///   if the variable is secret and `SecretExport::Deny` is in effect.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// let mut out = Vec::new();
+    /// LoadedEnvar::String("MOTD".into(), "line one\nline two".to_string()).export_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(out).unwrap(), "cargo:rustc-env=MOTD=line one\\nline two\n");
+    /// ```
+    pub fn export_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        if self.is_secret() {
+            let key = self.key();
+            match directive::secret_export() {
+                directive::SecretExport::Deny => {
+                    let message = EnvarError::SecretExported { key: key.to_string() }.to_string();
+                    return Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, message));
+                },
+                directive::SecretExport::Warn => {
+                    writeln!(out, "{}warning={} is secret and is embedded in the binary; load it at runtime instead", directive::prefix(), key)?;
+                },
+                directive::SecretExport::Allow => {},
+            }
+        }
+
+        writeln!(out, "{}rustc-env={}={}", directive::prefix(), self.key(), directive::rustc_env_value(&self.value_string()))
+    }
+
+    /// Exports this loaded environment variable like `export`,
+    /// and also prints `cargo:rerun-if-env-changed` for it.
+    ///
+    /// This makes Cargo re-run the build script whenever the variable changes,
+    /// so stale values don't persist between builds.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// // Prints `cargo:rerun-if-env-changed=VAR_NAME`
+    /// // and `cargo:rustc-env=VAR_NAME=some_value`.
+    /// LoadedEnvar::String("VAR_NAME".into(), "some_value".to_string()).export_with_rerun();
+    /// ```
+    pub fn export_with_rerun(&self) {
+        directive::print(|out| self.export_with_rerun_to(out));
+    }
+
+    /// Writes the directives printed by `export_with_rerun` to `out` instead of stdout.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// let mut out = Vec::new();
+    /// LoadedEnvar::U16("PORT".into(), 8080).export_with_rerun_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(out).unwrap(), "cargo:rerun-if-env-changed=PORT\ncargo:rustc-env=PORT=8080\n");
+    /// ```
+    pub fn export_with_rerun_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        writeln!(out, "{}rerun-if-env-changed={}", directive::prefix(), self.key())?;
+        self.export_to(out)
+    }
+
+    /// Exports this loaded environment variable as a Cargo cfg flag (`cargo:rustc-cfg`).
+    ///
+    /// The cfg name is the lowercased environment variable name.
+    /// A `bool` variable sets the flag `cfg(name)` only when its value is `true`,
+    /// while any other variable sets the key-value flag `cfg(name = "value")`.
+    /// A matching `cargo:rustc-check-cfg` directive is also printed,
+    /// so the flag doesn't trigger the `unexpected_cfgs` lint.
+    ///
+    /// cfg flags are passed to `rustc` on the command line, which shows up in verbose build logs,
+    /// so a `Secret` variable is never exported this way. A `cargo:warning` is printed instead.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// // Prints `cargo:rustc-cfg=secure`.
+    /// LoadedEnvar::Bool("SECURE".into(), true).export_cfg();
+    ///
+    /// // Prints `cargo:rustc-cfg=log_level="debug"`.
+    /// LoadedEnvar::String("LOG_LEVEL".into(), "debug".to_string()).export_cfg();
+    /// ```
+    ///
+    /// ***
+    ///
+    /// ```rust,ignore
+    /// // main.rs
+    ///
+    /// #[cfg(secure)]
+    /// fn connect() { /* TLS connection */ }
+    ///
+    /// #[cfg(not(secure))]
+    /// fn connect() { /* plain connection */ }
+    /// ```
+    pub fn export_cfg(&self) {
+        directive::print(|out| self.export_cfg_to(out));
+    }
+
+    /// Writes the directives printed by `export_cfg` to `out` instead of stdout.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// let mut out = Vec::new();
+    /// LoadedEnvar::Bool("SECURE".into(), true).export_cfg_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(out).unwrap(), "cargo:rustc-check-cfg=cfg(secure)\ncargo:rustc-cfg=secure\n");
+    /// ```
+    pub fn export_cfg_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        let name = self.key().to_lowercase();
+
+        match self {
+            _ if self.is_secret() => {
+                writeln!(out, "{}warning={} is secret and was not exported as a cfg flag", directive::prefix(), self.key())
+            },
+            LoadedEnvar::Bool(_, val) => {
+                writeln!(out, "{}rustc-check-cfg=cfg({})", directive::prefix(), name)?;
+                if *val {
+                    writeln!(out, "{}rustc-cfg={}", directive::prefix(), name)?;
+                }
+                Ok(())
+            },
+            _ => {
+                writeln!(out, "{}rustc-check-cfg=cfg({}, values(any()))", directive::prefix(), name)?;
+                writeln!(out, "{}rustc-cfg={}={:?}", directive::prefix(), name, self.value_string())
+            },
+        }
+    }
+
+    /// Returns the SHA-256 hash of the value, as 64 lowercase hex digits. Requires the `sha2` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{LoadedEnvar, Secret};
+    ///
+    /// let key = LoadedEnvar::Secret("API_KEY".into(), Secret::new("abc".to_string()));
+    ///
+    /// assert_eq!(key.sha256(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    /// ```
+    #[cfg(feature = "sha2")]
+    pub fn sha256(&self) -> String {
+        use sha2::Digest;
+
+        sha2::Sha256::digest(self.value_string().as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Exports the SHA-256 hash of this variable's value as `KEY_SHA256` (`cargo:rustc-env`), rather than the value itself.
+    ///
+    /// Code can then compare `env!("API_KEY_SHA256")` across builds to tell whether a secret was rotated,
+    /// without the secret being embedded in the binary. Call `export` as well to export both.
+    /// Requires the `sha2` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::{LoadedEnvar, Secret};
+    ///
+    /// // Prints `cargo:rustc-env=API_KEY_SHA256=ba7816bf...`.
+    /// LoadedEnvar::Secret("API_KEY".into(), Secret::new("abc".to_string())).export_sha256();
+    /// ```
+    #[cfg(feature = "sha2")]
+    pub fn export_sha256(&self) {
+        directive::print(|out| self.export_sha256_to(out));
+    }
+
+    /// Writes the directive printed by `export_sha256` to `out` instead of stdout. Requires the `sha2` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{LoadedEnvar, Secret};
+    ///
+    /// let mut out = Vec::new();
+    /// LoadedEnvar::Secret("API_KEY".into(), Secret::new("abc".to_string())).export_sha256_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     "cargo:rustc-env=API_KEY_SHA256=ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\n",
+    /// );
+    /// ```
+    #[cfg(feature = "sha2")]
+    pub fn export_sha256_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        writeln!(out, "{}rustc-env={}_SHA256={}", directive::prefix(), self.key(), self.sha256())
+    }
+
+    /// Exports this loaded environment variable as a library search path (`cargo:rustc-link-search`),
+    /// as sys crates do with a variable such as `OPENSSL_LIB_DIR`.
+    ///
+    /// A `List` variable exports one directive per item. The value may start with a kind, such as `native=/opt/lib`.
+    /// Like `export_cfg`, a `Secret` variable is never exported, and a `cargo:warning` is printed instead.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// // Prints `cargo:rustc-link-search=/opt/openssl/lib`.
+    /// LoadedEnvar::Path("OPENSSL_LIB_DIR".into(), "/opt/openssl/lib".into()).export_link_search();
+    /// ```
+    pub fn export_link_search(&self) {
+        directive::print(|out| self.export_link_search_to(out));
+    }
+
+    /// Writes the directives printed by `export_link_search` to `out` instead of stdout.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if the directives were written.
+    /// - `Err(std::io::Error)`: if writing to `out` failed, or a value contains a line break.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// let dirs = LoadedEnvar::List("LIB_DIRS".into(), vec!["/opt/a".to_string(), "native=/opt/b".to_string()]);
+    ///
+    /// let mut out = Vec::new();
+    /// dirs.export_link_search_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     "cargo:rustc-link-search=/opt/a\ncargo:rustc-link-search=native=/opt/b\n",
+    /// );
+    /// ```
+    pub fn export_link_search_to(&self, out: impl std::io::Write) -> std::io::Result<()> {
+        self.export_each_to(out, "rustc-link-search")
+    }
+
+    /// Exports this loaded environment variable as a library to link (`cargo:rustc-link-lib`),
+    /// as sys crates do with a variable such as `OPENSSL_LIBS`.
+    ///
+    /// A `List` variable exports one directive per item. The value may start with a kind, such as `static=ssl`.
+    /// Like `export_cfg`, a `Secret` variable is never exported, and a `cargo:warning` is printed instead.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// // Prints `cargo:rustc-link-lib=static=ssl` and `cargo:rustc-link-lib=static=crypto`.
+    /// LoadedEnvar::List("OPENSSL_LIBS".into(), vec!["static=ssl".to_string(), "static=crypto".to_string()]).export_link_lib();
+    /// ```
+    pub fn export_link_lib(&self) {
+        directive::print(|out| self.export_link_lib_to(out));
+    }
+
+    /// Writes the directives printed by `export_link_lib` to `out` instead of stdout.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// let mut out = Vec::new();
+    /// LoadedEnvar::String("ZLIB_LIB".into(), "z".to_string()).export_link_lib_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(out).unwrap(), "cargo:rustc-link-lib=z\n");
+    /// ```
+    pub fn export_link_lib_to(&self, out: impl std::io::Write) -> std::io::Result<()> {
+        self.export_each_to(out, "rustc-link-lib")
+    }
+
+    /// Writes a `kind` directive for each item of a `List` variable, or for the value of any other,
+    /// or a warning for a `Secret`.
+    fn export_each_to(&self, mut out: impl std::io::Write, kind: &str) -> std::io::Result<()> {
+        match self {
+            _ if self.is_secret() => {
+                writeln!(out, "{}warning={} is secret and was not exported as {}", directive::prefix(), self.key(), kind)
+            },
+            LoadedEnvar::List(_, items) => {
+                items.iter().try_for_each(|item| directive::emit_directive_to(&mut out, kind, item))
+            },
+            _ => directive::emit_directive_to(out, kind, self.value_string()),
+        }
+    }
+
+    /// Returns this variable as a `KEY=value` line of a Docker env file, including the trailing newline.
+    ///
+    /// Values are written as-is when possible, since `docker run --env-file` doesn't unquote them.
+    /// Values containing newlines, quotes, `#`, `$`, backslashes, or surrounding whitespace
+    /// are double-quoted and escaped the way Docker Compose reads them.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// assert_eq!(LoadedEnvar::U16("PORT".into(), 8080).env_file_line(), "PORT=8080\n");
+    /// assert_eq!(LoadedEnvar::String("MOTD".into(), "a\nb".to_string()).env_file_line(), "MOTD=\"a\\nb\"\n");
+    /// ```
+    pub fn env_file_line(&self) -> String {
+        manifest::env_file_line(self.key(), &self.value_string())
+    }
+
+    /// Returns a line exporting this variable in the given shell, including the trailing newline.
+    ///
+    /// The value is single-quoted, so spaces, `$`, and other special characters are kept literally.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{LoadedEnvar, Shell};
+    ///
+    /// let motd = LoadedEnvar::String("MOTD".into(), "it's here".to_string());
+    ///
+    /// assert_eq!(motd.shell_line(Shell::Posix), "export MOTD='it'\\''s here'\n");
+    /// assert_eq!(motd.shell_line(Shell::Fish), "set -gx MOTD 'it\\'s here'\n");
+    /// ```
+    pub fn shell_line(&self, shell: Shell) -> String {
+        manifest::shell_line(shell, self.key(), &self.value_string())
+    }
+
+    /// Returns where the value of this variable was loaded from:
+    /// the process environment, a `.env` file and line, the declared default, or another source.
+    ///
+    /// The origin is kept with the loaded value, so it is `None` for variables built by hand.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{DotenvFile, Envar, EnvarSet, LoadedEnvar, Origin, ProcessEnv, SourceStack};
+    ///
+    /// let path = std::env::temp_dir().join("loaded_origin_doc.env");
+    /// std::fs::write(&path, "LOADED_ORIGIN_DOC_HOST=localhost\nLOADED_ORIGIN_DOC_PORT=3000\n").unwrap();
+    ///
+    /// std::env::set_var("LOADED_ORIGIN_DOC_HOST", "example.com");
+    ///
+    /// let sources = SourceStack::new().with(ProcessEnv).with(DotenvFile::from_path(&path).unwrap());
+    ///
+    /// let loaded = EnvarSet::with_prefix("LOADED_ORIGIN_DOC_")
+    ///     .var(Envar::string("HOST"))
+    ///     .var(Envar::u16("PORT"))
+    ///     .load_from(&sources)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(loaded[0].origin(), Some(Origin::ProcessEnv));
+    /// assert_eq!(loaded[1].origin().unwrap().to_string(), format!("{}:2", path.display()));
+    ///
+    /// assert_eq!(LoadedEnvar::U16("LOADED_ORIGIN_DOC_PORT".into(), 3000).origin(), None);
+    /// ```
+    pub fn origin(&self) -> Option<Origin> {
+        let key: &LoadedKey<'a> = loaded_key!(self);
+        key.origin()
+    }
+
+    /// Returns the environment variable name.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// assert_eq!(LoadedEnvar::U16("PORT".into(), 8080).key(), "PORT");
+    /// ```
+    pub fn key(&self) -> &str {
+        loaded_key!(self)
+    }
+
+    /// Records where the value of this variable was loaded from.
+    pub(crate) fn set_origin(&mut self, origin: Origin) {
+        let key: &mut LoadedKey<'a> = loaded_key!(self);
+        key.set_origin(origin);
+    }
+
+    /// Returns whether the value is secret: either a `Secret` variable,
+    /// or a variable of another type declared with `EnvarSpec::secret`, which keeps its type.
+    ///
+    /// Secret values are masked as `***` in `Debug` and `Display` output, logs, and reports,
+    /// and follow `set_secret_export` when exported.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let env = MockEnv::new().var("ALLOWED_KEYS", "k1,k2");
+    /// let loaded = Envar::list("ALLOWED_KEYS").secret().load_from(&env).unwrap();
+    ///
+    /// assert!(loaded.is_secret());
+    /// assert!(matches!(&loaded, LoadedEnvar::List(_, keys) if keys == &["k1", "k2"]));
+    /// assert_eq!(loaded.to_string(), "ALLOWED_KEYS=***");
+    /// assert_eq!(format!("{:?}", loaded), r#"List("ALLOWED_KEYS", ***)"#);
+    /// ```
+    pub fn is_secret(&self) -> bool {
+        let key: &LoadedKey<'a> = loaded_key!(self);
+        key.is_secret() || matches!(self, LoadedEnvar::Secret(..))
+    }
+
+    /// Marks the value as secret, keeping its type.
+    pub(crate) fn set_secret(&mut self) {
+        let key: &mut LoadedKey<'a> = loaded_key!(self);
+        key.set_secret();
+    }
+
+    /// Returns the value formatted as a string, exactly as it is exported.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// assert_eq!(LoadedEnvar::U16("PORT".into(), 8080).value_string(), "8080");
+    /// ```
+    pub fn value_string(&self) -> String {
+        match self {
+            LoadedEnvar::String(_, val)     => val.clone(),
+            LoadedEnvar::Bool(_, val)       => val.to_string(),
+            LoadedEnvar::U8(_, val)         => val.to_string(),
+            LoadedEnvar::U16(_, val)        => val.to_string(),
+            LoadedEnvar::U32(_, val)        => val.to_string(),
+            LoadedEnvar::U64(_, val)        => val.to_string(),
+            LoadedEnvar::U128(_, val)       => val.to_string(),
+            LoadedEnvar::Usize(_, val)      => val.to_string(),
+            LoadedEnvar::I8(_, val)         => val.to_string(),
+            LoadedEnvar::I16(_, val)        => val.to_string(),
+            LoadedEnvar::I32(_, val)        => val.to_string(),
+            LoadedEnvar::I64(_, val)        => val.to_string(),
+            LoadedEnvar::I128(_, val)       => val.to_string(),
+            LoadedEnvar::Isize(_, val)      => val.to_string(),
+            LoadedEnvar::F32(_, val)        => val.to_string(),
+            LoadedEnvar::F64(_, val)        => val.to_string(),
+            LoadedEnvar::Char(_, val)       => val.to_string(),
+            LoadedEnvar::NonZeroU16(_, val) => val.to_string(),
+            LoadedEnvar::NonZeroU32(_, val) => val.to_string(),
+            LoadedEnvar::NonZeroU64(_, val) => val.to_string(),
+            LoadedEnvar::IpAddr(_, val)     => val.to_string(),
+            LoadedEnvar::SocketAddr(_, val) => val.to_string(),
+            LoadedEnvar::Duration(_, val)   => val.as_millis().to_string(),
+            LoadedEnvar::ByteSize(_, val)   => val.to_string(),
+            LoadedEnvar::Percent(_, val)    => val.to_string(),
+            LoadedEnvar::Hex(_, val)        => val.iter().map(|byte| format!("{:02x}", byte)).collect(),
+            LoadedEnvar::Path(_, val)       => val.display().to_string(),
+            LoadedEnvar::Secret(_, val)     => val.expose().to_string(),
+            LoadedEnvar::List(_, val)       => val.join(","),
+            LoadedEnvar::Custom(_, val)     => val.raw.clone(),
+            #[cfg(feature = "base64")]
+            LoadedEnvar::Base64(_, val)     => base64::Engine::encode(&base64::engine::general_purpose::STANDARD, val),
+            #[cfg(feature = "semver")]
+            LoadedEnvar::Version(_, val)    => val.to_string(),
+            #[cfg(feature = "semver")]
+            LoadedEnvar::VersionReq(_, val) => val.to_string(),
+            #[cfg(feature = "chrono")]
+            LoadedEnvar::DateTime(_, val)   => val.to_rfc3339(),
+            #[cfg(feature = "chrono")]
+            LoadedEnvar::Date(_, val)       => val.to_string(),
+            #[cfg(feature = "log")]
+            LoadedEnvar::LogLevel(_, val)   => val.as_str().to_ascii_lowercase(),
+            #[cfg(feature = "serde_json")]
+            LoadedEnvar::Json(_, val)       => val.to_string(),
+            #[cfg(feature = "toml")]
+            LoadedEnvar::Toml(_, val)       => val.to_string(),
+            #[cfg(feature = "yaml")]
+            LoadedEnvar::Yaml(_, val)       => inline::yaml_flow(val),
+            #[cfg(feature = "url")]
+            LoadedEnvar::Url(_, val)        => val.to_string(),
+        }
+    }
+
+    /// Returns the value if this is a `String` or `Custom` variable.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// assert_eq!(LoadedEnvar::String("HOST".into(), "localhost".to_string()).as_str(), Some("localhost"));
+    /// assert_eq!(LoadedEnvar::U16("PORT".into(), 8080).as_str(), None);
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            LoadedEnvar::String(_, val) => Some(val),
+            LoadedEnvar::Custom(_, val) => Some(val.raw()),
+            _ => None,
+        }
+    }
+
+    /// Returns the parsed value if this is a `Custom` variable whose target type is `T`,
+    /// such as one defined with `Envar::parse::<T>` or `Envar::custom`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let loaded = Envar::parse::<std::net::Ipv6Addr>("ADDR").load_from(&MockEnv::new().var("ADDR", "::1")).unwrap();
+    ///
+    /// assert_eq!(loaded.get::<std::net::Ipv6Addr>(), Some(&std::net::Ipv6Addr::LOCALHOST));
+    /// assert_eq!(loaded.get::<u16>(), None);
+    /// ```
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        match self {
+            LoadedEnvar::Custom(_, val) => val.get(),
+            _ => None,
+        }
+    }
+
+    /// Takes the value out as `T`, if `T` is the type of the value or the target type of a `Custom` variable.
+    pub(crate) fn take<T: std::str::FromStr + Send + Sync + 'static>(self) -> Option<T> {
+        let val: Box<dyn std::any::Any> = match self {
+            LoadedEnvar::Custom(_, val)     => return val.into_parsed(),
+            LoadedEnvar::Secret(_, val)     => Box::new(val),
+            LoadedEnvar::String(_, val)     => Box::new(val),
+            LoadedEnvar::Bool(_, val)       => Box::new(val),
+            LoadedEnvar::U8(_, val)         => Box::new(val),
+            LoadedEnvar::U16(_, val)        => Box::new(val),
+            LoadedEnvar::U32(_, val)        => Box::new(val),
+            LoadedEnvar::U64(_, val)        => Box::new(val),
+            LoadedEnvar::U128(_, val)       => Box::new(val),
+            LoadedEnvar::Usize(_, val)      => Box::new(val),
+            LoadedEnvar::I8(_, val)         => Box::new(val),
+            LoadedEnvar::I16(_, val)        => Box::new(val),
+            LoadedEnvar::I32(_, val)        => Box::new(val),
+            LoadedEnvar::I64(_, val)        => Box::new(val),
+            LoadedEnvar::I128(_, val)       => Box::new(val),
+            LoadedEnvar::Isize(_, val)      => Box::new(val),
+            LoadedEnvar::F32(_, val)        => Box::new(val),
+            LoadedEnvar::F64(_, val)        => Box::new(val),
+            LoadedEnvar::Char(_, val)       => Box::new(val),
+            LoadedEnvar::NonZeroU16(_, val) => Box::new(val),
+            LoadedEnvar::NonZeroU32(_, val) => Box::new(val),
+            LoadedEnvar::NonZeroU64(_, val) => Box::new(val),
+            LoadedEnvar::IpAddr(_, val)     => Box::new(val),
+            LoadedEnvar::SocketAddr(_, val) => Box::new(val),
+            LoadedEnvar::Path(_, val)       => Box::new(val),
+            _ => return None,
+        };
+
+        val.downcast().ok().map(|val| *val)
+    }
+
+    /// Returns the value if this is a `Bool` variable.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            LoadedEnvar::Bool(_, val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if this is a `U16` variable.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::LoadedEnvar;
+    ///
+    /// assert_eq!(LoadedEnvar::U16("PORT".into(), 8080).as_u16(), Some(8080));
+    /// assert_eq!(LoadedEnvar::Bool("SECURE".into(), true).as_u16(), None);
+    /// ```
+    pub fn as_u16(&self) -> Option<u16> {
+        match self {
+            LoadedEnvar::U16(_, val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if this is a `U32` variable.
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            LoadedEnvar::U32(_, val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if this is a `Json` variable.
+    #[cfg(feature = "serde_json")]
+    pub fn as_json(&self) -> Option<&serde_json::Value> {
+        match self {
+            LoadedEnvar::Json(_, val) => Some(val),
+            _ => None,
+        }
+    }
+}
+
+
+/// Formats the variable as `KEY=value`, masking `Secret` values as `***`,
+/// which makes it safe to print in build logs.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{LoadedEnvar, Secret};
+///
+/// assert_eq!(LoadedEnvar::U16("PORT".into(), 8080).to_string(), "PORT=8080");
+/// assert_eq!(LoadedEnvar::Secret("API_KEY".into(), Secret::new("hunter2")).to_string(), "API_KEY=***");
+/// ```
+impl std::fmt::Display for LoadedEnvar<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.is_secret() {
+            true => write!(f, "{}=***", self.key()),
+            false => write!(f, "{}={}", self.key(), self.value_string()),
+        }
+    }
+}
+
+
+/// Formats like a derived `Debug`, such as `U16("PORT", 8080)`, with the value masked as `***` if it is secret.
+impl std::fmt::Debug for LoadedEnvar<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        macro_rules! debug_variants {
+            ($($(#[$cfg:meta])* $variant:ident),* $(,)?) => {
+                match self {
+                    $(
+                        $(#[$cfg])*
+                        LoadedEnvar::$variant(key, val) => {
+                            let mut tuple = f.debug_tuple(stringify!($variant));
+                            tuple.field(key);
+                            match self.is_secret() {
+                                true => tuple.field(&format_args!("***")),
+                                false => tuple.field(val),
+                            };
+                            tuple.finish()
+                        },
+                    )*
+                }
+            };
+        }
+
+        debug_variants!(
+            String, Bool, U8, U16, U32, U64, U128, Usize, I8, I16, I32, I64, I128, Isize, F32, F64, Char,
+            NonZeroU16, NonZeroU32, NonZeroU64, IpAddr, SocketAddr, Duration, ByteSize, Percent, Hex,
+            #[cfg(feature = "base64")] Base64,
+            Path,
+            #[cfg(feature = "semver")] Version,
+            #[cfg(feature = "semver")] VersionReq,
+            #[cfg(feature = "chrono")] DateTime,
+            #[cfg(feature = "chrono")] Date,
+            #[cfg(feature = "log")] LogLevel,
+            #[cfg(feature = "serde_json")] Json,
+            #[cfg(feature = "toml")] Toml,
+            #[cfg(feature = "yaml")] Yaml,
+            #[cfg(feature = "url")] Url,
+            Secret, List, Custom,
+        )
+    }
+}
+
+/// Loads the `.env` file from the root directory of your project.
+/// 
+/// The file is looked for in the current directory and its ancestors, and every entry that isn't already set
+/// in the process environment is set. A file that can't be read is reported as `EnvarError::DotenvError`,
+/// and an invalid one as `EnvarError::Syntax`, with the line and column of the error.
+/// Warnings about the file, such as a key defined twice, are printed as `cargo:warning` directives in a build script,
+/// or to stderr otherwise. The file is remembered, so errors about its values can point at the line that defines them.
+///
+/// ***
+/// # Examples
+///
+/// ```rust,no_run
+/// // build.rs
+///
+/// easy_envar::init().unwrap();
+/// ```
+pub fn init() -> Result<std::path::PathBuf, EnvarError> {
+    let file = DotenvFile::find()?;
+    let path = file.path().map(std::path::Path::to_path_buf).unwrap_or_default();
+    file.apply();
+    source::remember(file);
+    Ok(path)
+}
+
+
+/// Loads the `.env` file at the given path.
+///
+/// Like `init`, entries already set in the process environment are not overridden,
+/// and the file is remembered.
+/// Useful when the `.env` file doesn't live in the crate root,
+/// such as in workspaces.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// let path = std::env::temp_dir().join("init_from_path_doc.env");
+/// std::fs::write(&path, "INIT_FROM_PATH_DOC=loaded").unwrap();
+///
+/// easy_envar::init_from_path(&path).unwrap();
+///
+/// assert_eq!(std::env::var("INIT_FROM_PATH_DOC").unwrap(), "loaded");
+/// ```
+pub fn init_from_path(path: impl AsRef<std::path::Path>) -> Result<std::path::PathBuf, EnvarError> {
+    let path = path.as_ref();
+    let file = DotenvFile::from_path(path)?;
+    file.apply();
+    source::remember(file);
+    Ok(path.to_path_buf())
+}
+
+
+/// Loads the `.env` file like `init`,
+/// and also prints `cargo:rerun-if-changed` for the file that was found.
+///
+/// This makes Cargo re-run the build script whenever the `.env` file is edited.
+///
+/// ***
+/// # Examples
+///
+/// ```rust,no_run
+/// // build.rs
+///
+/// easy_envar::init_tracked().unwrap();
+/// ```
+pub fn init_tracked() -> Result<std::path::PathBuf, EnvarError> {
+    let path = init()?;
+    println!("{}rerun-if-changed={}", directive::prefix(), path.display());
+    Ok(path)
+}
+
+
+/// Loads every given environment variable, collecting all failures instead of stopping at the first one.
+///
+/// Accepts both plain `Envar`s and `EnvarSpec`s. Warnings, such as for a missing `LoadLevel::Warn` variable,
+/// are printed as `cargo:warning` directives in a build script, and to stderr otherwise.
+///
+/// ***
+/// # Returns
+///
+/// - `Ok(Vec<LoadedEnvar>)`: if every variable is successfully loaded, in the given order.
+///   Missing `LoadLevel::Warn` variables without a default are skipped.
+/// - `Err(Vec<EnvarError>)`: every error encountered, in the given order.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarError};
+///
+/// std::env::set_var("LOAD_ALL_DOC_HOST", "localhost");
+/// std::env::set_var("LOAD_ALL_DOC_PORT", "not_a_number");
+///
+/// let env_vars = [
+///     Envar::string("LOAD_ALL_DOC_HOST"),
+///     Envar::u16("LOAD_ALL_DOC_PORT"),
+///     Envar::bool("LOAD_ALL_DOC_MISSING"),
+/// ];
+///
+/// let errors = easy_envar::load_all(&env_vars).unwrap_err();
+///
+/// assert_eq!(errors.len(), 2);
+/// assert!(matches!(errors[0], EnvarError::ParseError { .. }));
+/// assert!(matches!(errors[1], EnvarError::Missing { .. }));
+/// ```
+pub fn load_all<'a, E>(env_vars: &[E]) -> Result<Vec<LoadedEnvar<'a>>, Vec<EnvarError>>
+where
+    E: Clone + Into<EnvarSpec<'a>>,
+{
+    let mut loaded = Vec::with_capacity(env_vars.len());
+    let mut errors = Vec::new();
+
+    for env_var in env_vars {
+        match env_var.clone().into().load_lenient(&ProcessEnv, "", spec::Diagnostics::detect()) {
+            Ok(env_var) => loaded.extend(env_var),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(loaded)
+    } else {
+        Err(errors)
+    }
+}
+
+
+/// Reads a raw value from the system environment.
+fn var(key: &str) -> Result<String, EnvarError> {
+    match std::env::var(key) {
+        Ok(raw) => Ok(raw),
+        Err(std::env::VarError::NotPresent) => Err(EnvarError::Missing { key: key.to_string(), description: None, suggestion: None }),
+        Err(std::env::VarError::NotUnicode(_)) => Err(EnvarError::NotUnicode { key: key.to_string(), description: None }),
+    }
+}
+
+
+/// Parses a percentage such as `85%` or `0.85` into a fraction, returning `None` if it is not in `[0, 1]`.
+fn parse_percent(raw: &str) -> Option<f64> {
+    let val = match raw.strip_suffix('%') {
+        Some(percent) => percent.trim_end().parse::<f64>().ok()? / 100.0,
+        None => raw.parse::<f64>().ok()?,
+    };
+
+    (0.0..=1.0).contains(&val).then_some(val)
+}
+
+
+/// Parses a boolean, accepting the common truthy and falsy spellings in any case.
+fn parse_bool(raw: &str) -> Option<bool> {
+    match raw.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "y" | "on" | "1" => Some(true),
+        "false" | "no" | "n" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+
+/// Trims surrounding whitespace, then strips one pair of matching single or double quotes.
+fn trim_value(raw: &str) -> &str {
+    let raw = raw.trim();
+
+    for quote in ['"', '\''] {
+        if let Some(inner) = raw.strip_prefix(quote).and_then(|raw| raw.strip_suffix(quote)) {
+            return inner.trim();
+        }
+    }
+
+    raw
+}
+
+
+/// Splits a raw list value on `delimiter`, trimming items and skipping empty ones.
+fn split(raw: &str, delimiter: char) -> Vec<String> {
+    raw.split(delimiter)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+
+/// Decodes a hex string into bytes, returning `None` if it is malformed.
+fn decode_hex(raw: &str) -> Option<Vec<u8>> {
+    if !raw.len().is_multiple_of(2) || !raw.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..raw.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&raw[i..i + 2], 16).ok())
+        .collect()
+}
+
+
+/// Parses a raw value, reporting failures as `EnvarError::ParseError`.
+fn parse<T: std::str::FromStr>(key: &str, raw: String, expected_type: &'static str) -> Result<T, EnvarError> {
+    raw.parse::<T>().map_err(|_| EnvarError::ParseError {
+        key: key.to_string(),
+        raw,
+        expected_type,
+        description: None,
+        location: None,
+    })
+}
+
+
+#[doc(hidden)]
+pub mod __private {
+    //! Support code for `#[derive(Envar)]`. Not part of the public API.
+
+    use std::any::TypeId;
+    use std::borrow::Cow;
+
+    use crate::spec::Diagnostics;
+    use crate::{Envar, EnvarError, EnvarSpec};
+
+    /// Loads the field named `key` under `prefix` like any other variable, declared with the options
+    /// applied by `declare`, such as `or_default` and `range`, and returns its parsed value.
+    pub fn load<T: std::str::FromStr + Send + Sync + 'static>(
+        prefix: &str,
+        key: &'static str,
+        declare: impl FnOnce(EnvarSpec<'static>) -> EnvarSpec<'static>,
+    ) -> Result<T, EnvarError> {
+        let spec = declare(EnvarSpec::from(envar::<T>(key)));
+        let loaded = spec.load_prefixed(&crate::ProcessEnv, prefix, Diagnostics::detect())?;
+
+        Ok(loaded.take().expect("a loaded field has the type it was declared with"))
+    }
+
+    /// Loads like `load`, but returns `Ok(None)` if neither the variable nor a default is set.
+    pub fn load_optional<T: std::str::FromStr + Send + Sync + 'static>(
+        prefix: &str,
+        key: &'static str,
+        declare: impl FnOnce(EnvarSpec<'static>) -> EnvarSpec<'static>,
+    ) -> Result<Option<T>, EnvarError> {
+        match load(prefix, key, declare) {
+            Err(EnvarError::Missing { .. }) => Ok(None),
+            loaded => loaded.map(Some),
+        }
+    }
+
+    /// Creates an `Envar` of a given variant, such as `Envar::Bool`.
+    type Variant = fn(Cow<'static, str>) -> Envar<'static>;
+
+    /// Returns the variable a field of type `T` is loaded as: the variant that parses `T`, such as `Envar::Bool`
+    /// for `bool`, so the field accepts the same values as that variant, or else `Envar::parse::<T>`.
+    fn envar<T: std::str::FromStr + Send + Sync + 'static>(key: &'static str) -> Envar<'static> {
+        let variants: [(TypeId, Variant); 24] = [
+            (TypeId::of::<String>(), Envar::String),
+            (TypeId::of::<bool>(), Envar::Bool),
+            (TypeId::of::<u8>(), Envar::U8),
+            (TypeId::of::<u16>(), Envar::U16),
+            (TypeId::of::<u32>(), Envar::U32),
+            (TypeId::of::<u64>(), Envar::U64),
+            (TypeId::of::<u128>(), Envar::U128),
+            (TypeId::of::<usize>(), Envar::Usize),
+            (TypeId::of::<i8>(), Envar::I8),
+            (TypeId::of::<i16>(), Envar::I16),
+            (TypeId::of::<i32>(), Envar::I32),
+            (TypeId::of::<i64>(), Envar::I64),
+            (TypeId::of::<i128>(), Envar::I128),
+            (TypeId::of::<isize>(), Envar::Isize),
+            (TypeId::of::<f32>(), Envar::F32),
+            (TypeId::of::<f64>(), Envar::F64),
+            (TypeId::of::<char>(), Envar::Char),
+            (TypeId::of::<std::num::NonZeroU16>(), Envar::NonZeroU16),
+            (TypeId::of::<std::num::NonZeroU32>(), Envar::NonZeroU32),
+            (TypeId::of::<std::num::NonZeroU64>(), Envar::NonZeroU64),
+            (TypeId::of::<std::net::IpAddr>(), Envar::IpAddr),
+            (TypeId::of::<std::net::SocketAddr>(), Envar::SocketAddr),
+            (TypeId::of::<std::path::PathBuf>(), Envar::Path),
+            (TypeId::of::<crate::Secret>(), Envar::Secret),
+        ];
+
+        match variants.iter().find(|(id, _)| *id == TypeId::of::<T>()) {
+            Some((_, variant)) => variant(key.into()),
+            None => Envar::parse::<T>(key),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+/// Loads the `.env` file, then loads and exports every listed environment variable.
+///
+/// Each entry is a variable name followed by any type implementing `FromStr`.
+/// Every variable is loaded before anything is exported, so all failures are reported together.
+/// On failure, every error is printed to stderr and the build script exits with status `1`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust,no_run
+/// // build.rs
+///
+/// easy_envar::export_env! {
+///     HOST: String,
+///     PORT: u16,
+///     SECURE: bool,
+/// }
+/// ```
+#[macro_export]
+macro_rules! export_env {
+    ($($key:ident : $ty:ty),* $(,)?) => {{
+        if let ::std::result::Result::Err(e) = $crate::init() {
+            ::std::eprintln!("{}", e);
+            ::std::process::exit(1);
+        }
+
+        let env_vars = $crate::EnvarSet::new()
+            $(.var($crate::Envar::parse::<$ty>(::std::stringify!($key))))*;
+
+        if let ::std::result::Result::Err(errors) = env_vars.export_all() {
+            for e in errors {
+                ::std::eprintln!("{}", e);
+            }
+            ::std::process::exit(1);
+        }
+    }};
+}
@@ -0,0 +1,114 @@
+use crate::LoadedEnvar;
+
+
+/// Renders a Kubernetes `ConfigMap` holding every non-secret variable.
+pub(crate) fn config_map(name: &str, env_vars: &[(String, LoadedEnvar)]) -> String {
+    let entries = env_vars.iter().filter(|(_, env_var)| !env_var.is_secret());
+    manifest("ConfigMap", name, "data", entries)
+}
+
+
+/// Renders a Kubernetes `Secret` holding every secret variable.
+pub(crate) fn secret(name: &str, env_vars: &[(String, LoadedEnvar)]) -> String {
+    let entries = env_vars.iter().filter(|(_, env_var)| env_var.is_secret());
+    manifest("Secret", name, "stringData", entries)
+}
+
+
+/// Renders a manifest of the given kind, listing `entries` under `field`.
+fn manifest<'e, 'a: 'e, I>(kind: &str, name: &str, field: &str, entries: I) -> String
+where
+    I: Iterator<Item = &'e (String, LoadedEnvar<'a>)>,
+{
+    let mut out = format!("apiVersion: v1\nkind: {}\nmetadata:\n  name: {}\n", kind, quote(name));
+
+    if kind == "Secret" {
+        out.push_str("type: Opaque\n");
+    }
+
+    let lines: Vec<String> = entries
+        .map(|(key, env_var)| format!("  {}: {}\n", quote(key), quote(&env_var.value_string())))
+        .collect();
+
+    if lines.is_empty() {
+        out.push_str(&format!("{}: {{}}\n", field));
+    } else {
+        out.push_str(&format!("{}:\n", field));
+        lines.iter().for_each(|line| out.push_str(line));
+    }
+
+    out
+}
+
+
+/// A shell to generate export scripts for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    /// A POSIX shell such as `sh`, `bash`, or `zsh`, using `export KEY='value'`.
+    Posix,
+
+    /// The fish shell, using `set -gx KEY 'value'`.
+    Fish,
+}
+
+
+/// Renders a line of a shell script exporting `key`.
+pub(crate) fn shell_line(shell: Shell, key: &str, val: &str) -> String {
+    match shell {
+        // Single quotes can't be escaped inside single quotes, so close the quotes, add an escaped one, and reopen.
+        Shell::Posix => format!("export {}='{}'\n", key, val.replace('\'', "'\\''")),
+        Shell::Fish => format!("set -gx {} '{}'\n", key, val.replace('\\', "\\\\").replace('\'', "\\'")),
+    }
+}
+
+
+/// Renders a `KEY=value` line of a Docker env file.
+///
+/// Values are written as-is when possible, since `docker run --env-file` doesn't unquote them.
+/// Values that would otherwise be misread, such as multi-line values, are double-quoted and escaped
+/// the way Docker Compose reads them.
+pub(crate) fn env_file_line(key: &str, val: &str) -> String {
+    let plain = val.chars().all(|c| !matches!(c, '\n' | '\r' | '"' | '\'' | '\\' | '#' | '$'))
+        && val.trim() == val;
+
+    if plain {
+        return format!("{}={}\n", key, val);
+    }
+
+    let mut out = format!("{}=\"", key);
+
+    for c in val.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '$' => out.push_str("$$"),
+            c => out.push(c),
+        }
+    }
+
+    out.push_str("\"\n");
+    out
+}
+
+
+/// Renders a YAML double-quoted scalar.
+fn quote(val: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in val.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
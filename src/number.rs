@@ -0,0 +1,87 @@
+/// Controls which human-friendly spellings are accepted for integer values.
+///
+/// The default is strict: only plain decimal integers such as `1000000` are accepted,
+/// as with `str::parse`. Opt in to `1_000_000` with `separators`,
+/// and to `0x1F`, `0o755`, and `0b1010` with `radix_prefixes`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, LoadedEnvar, NumberFormat};
+/// use easy_envar::testing::MockEnv;
+///
+/// let env = MockEnv::new().var("MAX_ROWS", "1_000_000").var("MODE", "0o755");
+///
+/// let rows = Envar::u32("MAX_ROWS").number_format(NumberFormat::human()).load_from(&env).unwrap();
+/// let mode = Envar::u32("MODE").number_format(NumberFormat::human()).load_from(&env).unwrap();
+///
+/// assert!(matches!(rows, LoadedEnvar::U32(_, 1_000_000)));
+/// assert!(matches!(mode, LoadedEnvar::U32(_, 0o755)));
+/// assert!(Envar::u32("MAX_ROWS").load_from(&env).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NumberFormat {
+    separators: bool,
+    radix_prefixes: bool,
+}
+
+
+impl NumberFormat {
+    /// Creates the strict format, which only accepts plain decimal integers.
+    pub fn new() -> Self {
+        NumberFormat::default()
+    }
+
+    /// Creates a format that accepts both `_` separators and radix prefixes.
+    pub fn human() -> Self {
+        NumberFormat::new().separators(true).radix_prefixes(true)
+    }
+
+    /// Sets whether `_` is accepted between digits, as in `1_000_000`.
+    pub fn separators(mut self, separators: bool) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Sets whether the `0x`, `0o`, and `0b` prefixes are accepted for hexadecimal, octal, and binary.
+    pub fn radix_prefixes(mut self, radix_prefixes: bool) -> Self {
+        self.radix_prefixes = radix_prefixes;
+        self
+    }
+
+    /// Rewrites `raw` as a plain decimal integer, so it can be parsed with `str::parse`.
+    ///
+    /// Returns `None` if `raw` is not an integer in this format,
+    /// in which case it should be parsed as-is to report the original value.
+    pub(crate) fn normalize(self, raw: &str) -> Option<String> {
+        if self == NumberFormat::new() {
+            return None;
+        }
+
+        let (sign, digits) = match raw.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", raw.strip_prefix('+').unwrap_or(raw)),
+        };
+
+        let digits = match self.separators {
+            true if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") => return None,
+            true => digits.replace('_', ""),
+            false => digits.to_string(),
+        };
+
+        let (radix, digits) = match digits.get(..2) {
+            Some("0x" | "0X") if self.radix_prefixes => (16, &digits[2..]),
+            Some("0o" | "0O") if self.radix_prefixes => (8, &digits[2..]),
+            Some("0b" | "0B") if self.radix_prefixes => (2, &digits[2..]),
+            _ => (10, digits.as_str()),
+        };
+
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return None;
+        }
+
+        let val = u128::from_str_radix(digits, radix).ok()?;
+        Some(format!("{}{}", sign, val))
+    }
+}
@@ -0,0 +1,57 @@
+use crate::Location;
+
+
+/// Where a loaded value came from.
+///
+/// Returned by `LoadedEnvar::origin`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, Origin};
+///
+/// std::env::set_var("ORIGIN_DOC_PORT", "8080");
+///
+/// let port = Envar::u16("ORIGIN_DOC_PORT").load().unwrap();
+/// let host = Envar::string("ORIGIN_DOC_HOST").or_default("localhost").load().unwrap();
+///
+/// assert_eq!(port.origin(), Some(Origin::ProcessEnv));
+/// assert_eq!(host.origin(), Some(Origin::Default));
+/// assert_eq!(host.origin().unwrap().to_string(), "the declared default");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// The process environment, set outside of any `.env` file loaded by `init`.
+    ProcessEnv,
+
+    /// A line in a `.env` file, either read as a `DotenvFile`
+    /// or loaded into the process environment by `init` or `init_from_path`.
+    Dotenv(Location),
+
+    /// The default declared with `or_default`, since the variable was not set.
+    Default,
+
+    /// A remote secrets backend or configuration server, described by the backend and the secret's id,
+    /// such as AWS Secrets Manager `prod/db/password`, or by the URL of an `HttpSource`.
+    #[cfg(feature = "remote")]
+    Remote(String),
+
+    /// Another source, such as a `HashMap` of values or a command-line flag.
+    Other,
+}
+
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::ProcessEnv => write!(f, "the process environment"),
+            Origin::Dotenv(location) => write!(f, "{}", location),
+            Origin::Default => write!(f, "the declared default"),
+            #[cfg(feature = "remote")]
+            Origin::Remote(remote) => write!(f, "{}", remote),
+            Origin::Other => write!(f, "another source"),
+        }
+    }
+}
+
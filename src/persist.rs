@@ -0,0 +1,187 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use crate::EnvarError;
+
+
+/// Sets `key` to `val` in the `.env` file, found the same way as `init`, or creates `.env` in the current directory.
+///
+/// An existing entry is updated in place, keeping its `export` prefix if it has one,
+/// and a new one is added at the end, so comments, blank lines, and the order of entries are preserved.
+/// The process environment is not modified.
+///
+/// ***
+/// # Returns
+///
+/// - `Ok(PathBuf)`: the path of the updated file.
+/// - `Err(EnvarError::Io)`: if the file could not be read or written.
+///
+/// ***
+/// # Examples
+///
+/// ```rust,no_run
+/// // An onboarding tool filling in a developer's `.env`.
+/// easy_envar::set("DATABASE_URL", "postgres://localhost/dev").unwrap();
+/// easy_envar::set("PORT", 8080).unwrap();
+/// ```
+pub fn set(key: &str, val: impl Display) -> Result<PathBuf, EnvarError> {
+    let dir = std::env::current_dir().map_err(|source| EnvarError::Io { path: PathBuf::from(".env"), source })?;
+    let path = dir.ancestors()
+        .map(|dir| dir.join(".env"))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dir.join(".env"));
+
+    set_in_path(&path, key, val)?;
+    Ok(path)
+}
+
+
+/// Sets `key` to `val` in the `.env` file at `path`, like `set`, creating the file if it doesn't exist.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// let path = std::env::temp_dir().join("set_in_path_doc.env");
+/// std::fs::write(&path, "# Local settings\nexport PORT=80\nHOST=localhost\n").unwrap();
+///
+/// easy_envar::set_in_path(&path, "PORT", 8080).unwrap();
+/// easy_envar::set_in_path(&path, "GREETING", "hello world").unwrap();
+///
+/// assert_eq!(
+///     std::fs::read_to_string(&path).unwrap(),
+///     "# Local settings\nexport PORT=8080\nHOST=localhost\nGREETING='hello world'\n",
+/// );
+/// ```
+pub fn set_in_path(path: impl AsRef<Path>, key: &str, val: impl Display) -> Result<(), EnvarError> {
+    update(path.as_ref(), &[(key.to_string(), val.to_string())])
+}
+
+
+/// Sets every entry in the `.env` file at `path`, creating it if it doesn't exist.
+pub(crate) fn update(path: &Path, entries: &[(String, String)]) -> Result<(), EnvarError> {
+    let io_error = |source| EnvarError::Io { path: path.to_path_buf(), source };
+
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(io_error(e)),
+    };
+
+    std::fs::write(path, updated(&contents, entries)).map_err(io_error)
+}
+
+
+/// Returns `contents` with each entry's first definition replaced, and the others appended.
+fn updated(contents: &str, entries: &[(String, String)]) -> String {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut out = String::with_capacity(contents.len());
+    let mut done = vec![false; entries.len()];
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let span = span(&lines[i..]);
+
+        let assigned = assignment(line)
+            .and_then(|(export, key)| Some((export, entries.iter().position(|(k, _)| k == key)?)));
+
+        match assigned {
+            Some((export, index)) if !done[index] => {
+                let (key, val) = &entries[index];
+                out.push_str(if export { "export " } else { "" });
+                out.push_str(&line_for(key, val));
+                done[index] = true;
+            },
+            _ => lines[i..i + span].iter().for_each(|line| {
+                out.push_str(line);
+                out.push('\n');
+            }),
+        }
+
+        i += span;
+    }
+
+    for ((key, val), done) in entries.iter().zip(done) {
+        if !done {
+            out.push_str(&line_for(key, val));
+        }
+    }
+
+    out
+}
+
+
+/// Returns whether `line` assigns a variable with `export`, and the variable's name.
+fn assignment(line: &str) -> Option<(bool, &str)> {
+    let line = line.trim_start();
+    if line.starts_with('#') {
+        return None;
+    }
+
+    let (export, line) = match line.strip_prefix("export ") {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, line),
+    };
+
+    let (key, _) = line.split_once('=')?;
+    Some((export, key.trim()))
+}
+
+
+/// Returns how many of `lines` the entry on the first one spans, as a quoted value may continue over several lines.
+fn span(lines: &[&str]) -> usize {
+    let Some((_, val)) = lines[0].split_once('=').filter(|_| assignment(lines[0]).is_some()) else {
+        return 1;
+    };
+
+    let val = val.trim_start();
+    let Some(quote) = val.chars().next().filter(|c| matches!(c, '"' | '\'')) else {
+        return 1;
+    };
+
+    let mut escaped = false;
+    for (n, line) in std::iter::once(&val[1..]).chain(lines[1..].iter().copied()).enumerate() {
+        for c in line.chars() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if quote == '"' => escaped = true,
+                c if c == quote => return n + 1,
+                _ => {},
+            }
+        }
+    }
+
+    // An unterminated quote is left as it is, one line at a time.
+    1
+}
+
+
+/// Returns a `.env` line assigning `val` to `key`, quoted so it reads back unchanged.
+///
+/// Values of plain characters are written bare. Others are single-quoted, which reads them literally,
+/// unless they contain a single quote or a line break, in which case they are double-quoted and escaped.
+fn line_for(key: &str, val: &str) -> String {
+    let plain = val.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ',' | '/' | ':' | '@' | '+' | '%'));
+
+    if plain {
+        format!("{}={}\n", key, val)
+    } else if !val.contains(['\'', '\n', '\r']) {
+        format!("{}='{}'\n", key, val)
+    } else {
+        let mut out = format!("{}=\"", key);
+
+        for c in val.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '$' => out.push_str("\\$"),
+                '\n' => out.push_str("\\n"),
+                c => out.push(c),
+            }
+        }
+
+        out.push_str("\"\n");
+        out
+    }
+}
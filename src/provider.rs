@@ -0,0 +1,89 @@
+//! Integration with `figment`, so an `EnvarSet` can be merged into a `Figment` like any other provider.
+
+use figment::value::{Dict, Map, Value};
+use figment::{Error, Metadata, Profile, Provider};
+
+use crate::spec::Diagnostics;
+use crate::{EnvarSet, LoadedEnvar, ProcessEnv};
+
+
+/// Provides the loaded variables to `figment`, keyed by their lowercased name.
+///
+/// Every variable is loaded and validated as with `EnvarSet::load_runtime`,
+/// and all failures are reported together in a single `figment::Error`.
+/// Booleans and numbers become typed values, lists become arrays, and everything else,
+/// including secrets, becomes a string. Values are provided for the default profile.
+/// Requires the `figment` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarSet};
+/// use figment::Figment;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     port: u16,
+///     secure: bool,
+/// }
+///
+/// std::env::set_var("FIGMENT_DOC_PORT", "8080");
+///
+/// let set = EnvarSet::with_prefix("FIGMENT_DOC_")
+///     .var(Envar::u16("PORT"))
+///     .var(Envar::bool("SECURE").or_default(false));
+///
+/// let config: Config = Figment::new().merge(set).extract().unwrap();
+///
+/// assert_eq!(config.port, 8080);
+/// assert!(!config.secure);
+/// ```
+impl Provider for EnvarSet<'_> {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("easy-envar environment variables")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let loaded = self.load_with(&ProcessEnv, Diagnostics::Stderr).map_err(|errors| {
+            let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            Error::from(messages.join("; "))
+        })?;
+
+        let dict = loaded.iter()
+            .map(|env_var| (env_var.key().to_lowercase(), value(env_var)))
+            .collect();
+
+        Ok(Profile::Default.collect(dict))
+    }
+}
+
+
+/// Converts a loaded variable into a `figment` value, keeping booleans and numbers typed.
+fn value(env_var: &LoadedEnvar) -> Value {
+    match env_var {
+        LoadedEnvar::Bool(_, val)       => Value::from(*val),
+        LoadedEnvar::U8(_, val)         => Value::from(*val),
+        LoadedEnvar::U16(_, val)        => Value::from(*val),
+        LoadedEnvar::U32(_, val)        => Value::from(*val),
+        LoadedEnvar::U64(_, val)        => Value::from(*val),
+        LoadedEnvar::U128(_, val)       => Value::from(*val),
+        LoadedEnvar::Usize(_, val)      => Value::from(*val),
+        LoadedEnvar::I8(_, val)         => Value::from(*val),
+        LoadedEnvar::I16(_, val)        => Value::from(*val),
+        LoadedEnvar::I32(_, val)        => Value::from(*val),
+        LoadedEnvar::I64(_, val)        => Value::from(*val),
+        LoadedEnvar::I128(_, val)       => Value::from(*val),
+        LoadedEnvar::Isize(_, val)      => Value::from(*val),
+        LoadedEnvar::F32(_, val)        => Value::from(*val),
+        LoadedEnvar::F64(_, val)        => Value::from(*val),
+        LoadedEnvar::Percent(_, val)    => Value::from(*val),
+        LoadedEnvar::ByteSize(_, val)   => Value::from(*val),
+        LoadedEnvar::NonZeroU16(_, val) => Value::from(val.get()),
+        LoadedEnvar::NonZeroU32(_, val) => Value::from(val.get()),
+        LoadedEnvar::NonZeroU64(_, val) => Value::from(val.get()),
+        LoadedEnvar::List(_, val)       => Value::from(val.clone()),
+        _ => Value::from(env_var.value_string()),
+    }
+}
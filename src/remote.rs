@@ -0,0 +1,511 @@
+// Without a backend feature, `Backend` has no variants and everything below is unreachable.
+#![cfg_attr(not(any(feature = "aws", feature = "azure", feature = "gcp", feature = "keyring", feature = "vault")), allow(unused, unreachable_code))]
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(any(feature = "aws", feature = "azure", feature = "gcp", feature = "vault"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::spec::Diagnostics;
+use crate::{EnvarError, EnvarSpec, Location, Origin, Source};
+
+
+/// The variable naming the backend of secrets declared with `EnvarSpec::from_secret_store`.
+const SECRET_STORE_VAR: &str = "EASY_ENVAR_SECRET_STORE";
+
+/// How many requests to remote backends may run at once, unless set with `EnvarSet::remote_concurrency`.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Values already fetched from remote backends in this process, by remote and backend context,
+/// so loading again doesn't fetch them again.
+static CACHE: Mutex<Option<HashMap<(Remote, String), String>>> = Mutex::new(None);
+
+
+/// Where a variable's value is stored outside the environment, declared with a method such as `EnvarSpec::from_aws`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Remote {
+    /// The backend, or `None` to read it from `EASY_ENVAR_SECRET_STORE` at load time.
+    backend: Option<Backend>,
+    id: String,
+}
+
+
+/// A secrets backend values can be fetched from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Backend {
+    /// AWS Secrets Manager, through the `aws` command.
+    #[cfg(feature = "aws")]
+    Aws,
+
+    /// HashiCorp Vault's KV v2 engine, through the `vault` command.
+    #[cfg(feature = "vault")]
+    Vault,
+
+    /// Google Cloud Secret Manager, through the `gcloud` command.
+    #[cfg(feature = "gcp")]
+    Gcp,
+
+    /// Azure Key Vault, through the `az` command.
+    #[cfg(feature = "azure")]
+    Azure,
+
+    /// The OS keyring, through the `keyring` crate.
+    #[cfg(feature = "keyring")]
+    Keyring,
+}
+
+
+/// Why a value could not be fetched from a remote backend.
+///
+/// Carried by `EnvarError::Remote`, so callers can tell a misconfigured deployment
+/// (missing credentials or permissions) from a secret that doesn't exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteErrorKind {
+    /// The backend rejected the credentials, or they are missing or expired.
+    Unauthorized,
+
+    /// The backend has no secret at the declared path.
+    NotFound,
+
+    /// The backend could not be reached, or failed in some other way.
+    Unavailable,
+}
+
+
+/// How remote values are fetched, set with methods such as `EnvarSet::remote_concurrency`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FetchOptions {
+    /// How many requests may run at once.
+    pub(crate) concurrency: usize,
+    /// How long values saved to the on-disk cache are used for, or `None` to not save them.
+    pub(crate) cache_ttl: Option<Duration>,
+    /// Whether the values of secret variables are saved to the on-disk cache too.
+    pub(crate) cache_secrets: bool,
+    /// Whether to fetch every value again, rather than using one fetched before.
+    pub(crate) refresh: bool,
+}
+
+
+/// A value that could not be fetched, reported as `EnvarError::Remote` when the variable is loaded.
+#[derive(Debug, Clone)]
+pub(crate) struct Failure {
+    pub(crate) kind: RemoteErrorKind,
+    pub(crate) message: String,
+}
+
+
+impl Remote {
+    /// Declares that the value is stored in `backend` under `id`.
+    pub(crate) fn new(backend: Backend, id: impl Into<String>) -> Self {
+        Remote { backend: Some(backend), id: id.into() }
+    }
+
+    /// Declares that the value is stored under `id` in the backend named by `EASY_ENVAR_SECRET_STORE`.
+    #[cfg(any(feature = "aws", feature = "azure", feature = "gcp"))]
+    pub(crate) fn selected(id: impl Into<String>) -> Self {
+        Remote { backend: None, id: id.into() }
+    }
+
+    /// Returns the remote with its backend chosen, reading `EASY_ENVAR_SECRET_STORE` if it wasn't declared.
+    fn resolve(&self) -> Result<Remote, Failure> {
+        if self.backend.is_some() {
+            return Ok(self.clone());
+        }
+
+        let failure = |message: String| Failure { kind: RemoteErrorKind::Unavailable, message };
+        let name = std::env::var(SECRET_STORE_VAR)
+            .map_err(|_| failure(format!("`{}` is not set to a secret store", SECRET_STORE_VAR)))?;
+
+        match Backend::selectable(&name) {
+            Some(backend) => Ok(Remote::new(backend, self.id.clone())),
+            None => Err(failure(format!("`{}` is set to {:?}, which is not an enabled secret store", SECRET_STORE_VAR, name))),
+        }
+    }
+
+    /// Returns the remote stored in the on-disk cache under `backend` and `id`.
+    pub(crate) fn from_tag(backend: &str, id: &str) -> Option<Self> {
+        Some(Remote::new(Backend::from_tag(backend)?, id))
+    }
+
+    /// Returns the backend and id to store the remote under in the on-disk cache.
+    pub(crate) fn tag(&self) -> (&'static str, &str) {
+        (self.backend.map_or("", Backend::tag), &self.id)
+    }
+
+    /// Returns the name of the backend used in errors.
+    fn backend_name(&self) -> &'static str {
+        self.backend.map_or("a secret store", Backend::name)
+    }
+}
+
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        FetchOptions { concurrency: DEFAULT_CONCURRENCY, cache_ttl: None, cache_secrets: false, refresh: false }
+    }
+}
+
+
+impl std::fmt::Display for Remote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} `{}`", self.backend_name(), self.id)
+    }
+}
+
+
+impl Backend {
+    /// Returns the name used in errors and origins.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "aws")]
+            Backend::Aws => "AWS Secrets Manager",
+            #[cfg(feature = "vault")]
+            Backend::Vault => "Vault",
+            #[cfg(feature = "gcp")]
+            Backend::Gcp => "Google Cloud Secret Manager",
+            #[cfg(feature = "azure")]
+            Backend::Azure => "Azure Key Vault",
+            #[cfg(feature = "keyring")]
+            Backend::Keyring => "the OS keyring",
+        }
+    }
+
+    /// Returns the name of the backend in the on-disk cache.
+    fn tag(self) -> &'static str {
+        match self {
+            #[cfg(feature = "aws")]
+            Backend::Aws => "aws",
+            #[cfg(feature = "vault")]
+            Backend::Vault => "vault",
+            #[cfg(feature = "gcp")]
+            Backend::Gcp => "gcp",
+            #[cfg(feature = "azure")]
+            Backend::Azure => "azure",
+            #[cfg(feature = "keyring")]
+            Backend::Keyring => "keyring",
+        }
+    }
+
+    /// Returns the enabled backend named `tag` in the on-disk cache.
+    fn from_tag(tag: &str) -> Option<Backend> {
+        match tag {
+            #[cfg(feature = "aws")]
+            "aws" => Some(Backend::Aws),
+            #[cfg(feature = "vault")]
+            "vault" => Some(Backend::Vault),
+            #[cfg(feature = "gcp")]
+            "gcp" => Some(Backend::Gcp),
+            #[cfg(feature = "azure")]
+            "azure" => Some(Backend::Azure),
+            _ => None,
+        }
+    }
+
+    /// Returns what, besides an id, decides which secret the backend reads, such as the AWS profile and region,
+    /// so values fetched with one configuration are never reused with another.
+    fn context(self) -> String {
+        match self {
+            #[cfg(feature = "aws")]
+            Backend::Aws => crate::aws::context(),
+            #[cfg(feature = "vault")]
+            Backend::Vault => crate::vault::context(),
+            #[cfg(feature = "gcp")]
+            Backend::Gcp => crate::gcp::context(),
+            #[cfg(feature = "azure")]
+            Backend::Azure => crate::azure::context(),
+            #[cfg(feature = "keyring")]
+            Backend::Keyring => String::new(),
+        }
+    }
+
+    /// Returns whether values from the backend may be saved to the on-disk cache.
+    /// The OS keyring is already local, and is meant to keep secrets out of plaintext files.
+    fn cacheable(self) -> bool {
+        #[cfg(feature = "keyring")]
+        if self == Backend::Keyring {
+            return false;
+        }
+        true
+    }
+
+    /// Fetches the values of every id, in as few requests as the backend allows, running up to `concurrency` at once.
+    #[cfg_attr(not(any(feature = "aws", feature = "azure", feature = "gcp", feature = "vault")), allow(unused_variables))]
+    fn fetch(self, ids: &[&str], concurrency: usize) -> HashMap<String, Result<String, Failure>> {
+        match self {
+            #[cfg(feature = "aws")]
+            Backend::Aws => crate::aws::fetch(ids, concurrency),
+            #[cfg(feature = "vault")]
+            Backend::Vault => crate::vault::fetch(ids, concurrency),
+            #[cfg(feature = "gcp")]
+            Backend::Gcp => crate::gcp::fetch(ids, concurrency),
+            #[cfg(feature = "azure")]
+            Backend::Azure => crate::azure::fetch(ids, concurrency),
+            // Reading the keyring may prompt the user, so entries are read one at a time.
+            #[cfg(feature = "keyring")]
+            Backend::Keyring => crate::keyring_source::fetch(ids),
+        }
+    }
+
+    /// Returns the enabled backend that `EASY_ENVAR_SECRET_STORE` can name: `aws`, `gcp`, or `azure`.
+    fn selectable(name: &str) -> Option<Backend> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            #[cfg(feature = "aws")]
+            "aws" => Some(Backend::Aws),
+            #[cfg(feature = "gcp")]
+            "gcp" => Some(Backend::Gcp),
+            #[cfg(feature = "azure")]
+            "azure" => Some(Backend::Azure),
+            _ => None,
+        }
+    }
+}
+
+
+/// Describes the values of the environment variables `names`, as returned by `Backend::context`.
+#[cfg(any(feature = "aws", feature = "azure", feature = "gcp", feature = "vault"))]
+pub(crate) fn env_context(names: &[&str]) -> String {
+    names.iter()
+        .map(|name| format!("{}={}", name, std::env::var(name).unwrap_or_default()))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+
+/// Reports that a backend's command could not be run, with a hint to install `cli` if it wasn't found.
+#[cfg(any(feature = "aws", feature = "azure", feature = "gcp", feature = "vault"))]
+pub(crate) fn not_installed(program: &str, cli: &str, e: std::io::Error) -> Failure {
+    let message = match e.kind() {
+        std::io::ErrorKind::NotFound => format!("the `{}` command was not found; install the {} and make sure it is on the PATH", program, cli),
+        _ => format!("could not run `{}`: {}", program, e),
+    };
+
+    Failure { kind: RemoteErrorKind::Unavailable, message }
+}
+
+
+/// Calls `f` on every item, on up to `concurrency` threads at once, returning the results in order.
+#[cfg(any(feature = "aws", feature = "azure", feature = "gcp", feature = "vault"))]
+pub(crate) fn parallel<T: Sync, R: Send>(items: &[T], concurrency: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = concurrency.clamp(1, items.len().max(1));
+    if threads == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let (next, f) = (&AtomicUsize::new(0), &f);
+    let mut results: Vec<(usize, R)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| scope.spawn(move || {
+                let mut done = Vec::new();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else { break done };
+                    done.push((i, f(item)));
+                }
+            }))
+            .collect();
+
+        workers.into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+
+/// A source that falls back to values fetched from remote backends for the variables declared with one.
+pub(crate) struct Layered<'s> {
+    local: &'s dyn Source,
+    remote: HashMap<String, (Remote, Result<String, Failure>)>,
+}
+
+
+/// A variable to fetch, because it is declared with a remote and not set locally.
+struct Pending {
+    /// The name the variable is looked up under.
+    key: String,
+    /// The remote as declared.
+    declared: Remote,
+    /// The remote with its backend chosen, or why none could be.
+    resolved: Result<Remote, Failure>,
+    /// Whether the variable is secret, so its value is only saved to disk if `EnvarSet::remote_cache_secrets` is set.
+    secret: bool,
+}
+
+
+impl<'s> Layered<'s> {
+    /// Fetches the remote values of the variables in `env_vars` that `local` doesn't set, looked up under `prefix`.
+    ///
+    /// Values set locally are never fetched, so the environment or a `.env` file can override a remote secret.
+    pub(crate) fn resolve(env_vars: &[EnvarSpec], prefix: &str, local: &'s dyn Source, options: FetchOptions) -> Self {
+        let pending = pending(env_vars, prefix, local);
+        let values = fetch_all(&remotes(&pending), options);
+        Layered::new(local, pending, values)
+    }
+
+    /// Fetches remote values like `resolve`, on a thread of the Tokio runtime's blocking pool,
+    /// so the runtime's workers keep running other tasks meanwhile.
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn resolve_async(env_vars: &[EnvarSpec<'_>], prefix: &str, local: &'s (dyn Source + Sync), options: FetchOptions) -> Self {
+        let pending = pending(env_vars, prefix, local);
+        if pending.is_empty() {
+            return Layered::new(local, pending, HashMap::new());
+        }
+
+        let remotes = remotes(&pending);
+        let values = match tokio::task::spawn_blocking(move || fetch_all(&remotes, options)).await {
+            Ok(values) => values,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => pending.iter()
+                .filter_map(|pending| pending.resolved.clone().ok())
+                .map(|remote| (remote, Err(Failure { kind: RemoteErrorKind::Unavailable, message: "the runtime shut down".to_string() })))
+                .collect(),
+        };
+
+        Layered::new(local, pending, values)
+    }
+
+    /// Pairs each pending variable with its fetched value.
+    fn new(local: &'s dyn Source, pending: Vec<Pending>, values: HashMap<Remote, Result<String, Failure>>) -> Self {
+        let remote = pending.into_iter()
+            .filter_map(|pending| match pending.resolved {
+                Ok(remote) => {
+                    let value = values.get(&remote)?.clone();
+                    Some((pending.key, (remote, value)))
+                },
+                Err(failure) => Some((pending.key, (pending.declared, Err(failure)))),
+            })
+            .collect();
+
+        Layered { local, remote }
+    }
+}
+
+
+/// Finds the variables in `env_vars` declared with a remote that `local` doesn't set.
+fn pending(env_vars: &[EnvarSpec], prefix: &str, local: &dyn Source) -> Vec<Pending> {
+    env_vars.iter()
+        .filter_map(|env_var| {
+            let remote = env_var.remote()?;
+            match env_var.raw(local, prefix, Diagnostics::Silent) {
+                Err(EnvarError::Missing { key, .. }) => Some(Pending { key, declared: remote.clone(), resolved: remote.resolve(), secret: env_var.is_secret() }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+
+/// Returns the remotes to fetch for the pending variables whose backend could be chosen,
+/// each with whether it belongs to a secret variable.
+fn remotes(pending: &[Pending]) -> Vec<(Remote, bool)> {
+    pending.iter().filter_map(|pending| Some((pending.resolved.clone().ok()?, pending.secret))).collect()
+}
+
+
+/// Fetches every remote value that isn't cached yet, batched by backend.
+///
+/// Values are looked up in the process's cache, then in the on-disk cache if `options` enables it,
+/// unless `options` asks to refresh them. Values of secret variables are kept off disk unless `options` allows it.
+/// Both caches are keyed by the backend's context too, so switching profiles or projects fetches again.
+fn fetch_all(remotes: &[(Remote, bool)], options: FetchOptions) -> HashMap<Remote, Result<String, Failure>> {
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    let mut disk = options.cache_ttl.map(|ttl| (crate::remote_cache::DiskCache::open(), ttl));
+
+    let mut values = HashMap::new();
+    let mut contexts: HashMap<Backend, String> = HashMap::new();
+    let mut uncached: HashMap<Backend, Vec<&str>> = HashMap::new();
+    let mut on_disk: HashSet<&Remote> = HashSet::new();
+
+    for (remote, secret) in remotes {
+        let backend = remote.backend.expect("resolved");
+        let cacheable = backend.cacheable() && (!secret || options.cache_secrets);
+        if cacheable {
+            on_disk.insert(remote);
+        }
+
+        let context = contexts.entry(backend).or_insert_with(|| backend.context());
+        let key = (remote.clone(), context.clone());
+
+        let cached = match &disk {
+            _ if options.refresh => None,
+            Some((disk, ttl)) if cacheable => cache.get(&key).map(String::as_str).or_else(|| disk.get(&key, *ttl)),
+            _ => cache.get(&key).map(String::as_str),
+        };
+
+        match cached {
+            Some(val) => { values.insert(remote.clone(), Ok(val.to_string())); },
+            None => uncached.entry(backend).or_default().push(&remote.id),
+        }
+    }
+
+    let mut fetched = false;
+
+    for (backend, mut ids) in uncached {
+        ids.sort_unstable();
+        ids.dedup();
+
+        let context = &contexts[&backend];
+
+        for (id, value) in backend.fetch(&ids, options.concurrency) {
+            let remote = Remote::new(backend, id);
+            if let Ok(val) = &value {
+                let key = (remote.clone(), context.clone());
+                if let Some((disk, _)) = disk.as_mut().filter(|_| on_disk.contains(&remote)) {
+                    disk.insert(key.clone(), val.clone());
+                    fetched = true;
+                }
+                cache.insert(key, val.clone());
+            }
+            values.insert(remote, value);
+        }
+    }
+
+    if let Some((disk, _)) = disk.filter(|_| fetched) {
+        // A cache that can't be written only means the next build fetches again.
+        let _ = disk.save();
+    }
+
+    values
+}
+
+
+impl Source for Layered<'_> {
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        match self.local.var(key) {
+            Err(EnvarError::Missing { .. }) => {},
+            raw => return raw,
+        }
+
+        match self.remote.get(key) {
+            Some((_, Ok(val))) => Ok(val.clone()),
+            Some((remote, Err(failure))) => Err(EnvarError::Remote {
+                key: key.to_string(),
+                backend: remote.backend_name(),
+                kind: failure.kind,
+                message: failure.message.clone(),
+            }),
+            None => Err(EnvarError::Missing { key: key.to_string(), description: None, suggestion: None }),
+        }
+    }
+
+    fn names(&self) -> Vec<String> {
+        let mut names = self.local.names();
+        names.extend(self.remote.keys().cloned());
+        names
+    }
+
+    fn location(&self, key: &str) -> Option<Location> {
+        self.local.location(key)
+    }
+
+    fn origin(&self, key: &str) -> Origin {
+        match (self.local.var(key), self.remote.get(key)) {
+            (Err(EnvarError::Missing { .. }), Some((remote, _))) => Origin::Remote(remote.to_string()),
+            _ => self.local.origin(key),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::remote::Remote;
+
+
+/// The name of the cache file, in the user's cache directory.
+const FILE_NAME: &str = "easy-envar-remote-cache";
+
+
+/// Remote values saved to disk, with the time each was fetched, so repeated builds don't fetch them again.
+///
+/// Each line holds the fetch time in seconds since the epoch, the backend, the backend's context, the id, and the value,
+/// separated by tabs, with backslashes, tabs, and line breaks escaped. The context, such as the AWS profile and region,
+/// is part of the key, so one file serves every project and shell without mixing up their secrets.
+pub(crate) struct DiskCache {
+    path: PathBuf,
+    entries: HashMap<(Remote, String), (u64, String)>,
+}
+
+
+impl DiskCache {
+    /// Reads the cache file, treating a missing or unreadable file as empty.
+    pub(crate) fn open() -> Self {
+        let path = path();
+        let entries = read(&path);
+        DiskCache { path, entries }
+    }
+
+    /// Returns the value saved under `key`, a remote and the backend's context, if it was fetched less than `ttl` ago.
+    pub(crate) fn get(&self, key: &(Remote, String), ttl: Duration) -> Option<&str> {
+        let (fetched, val) = self.entries.get(key)?;
+        (now().saturating_sub(*fetched) < ttl.as_secs()).then_some(val.as_str())
+    }
+
+    /// Records that `val` was just fetched under `key`, a remote and the backend's context.
+    pub(crate) fn insert(&mut self, key: (Remote, String), val: String) {
+        self.entries.insert(key, (now(), val));
+    }
+
+    /// Writes the cache file, readable only by the current user where the platform allows.
+    ///
+    /// On Unix the file is created with mode `0600`. Elsewhere, such as on Windows,
+    /// it inherits the permissions of its directory, which under `%LOCALAPPDATA%` are the user's own.
+    ///
+    /// The file is read again just before writing, and the newer of each entry kept,
+    /// so values saved by a concurrent build since `open` aren't lost.
+    /// It is written next to its final path and renamed over it, so concurrent builds never read half of it.
+    pub(crate) fn save(&self) -> std::io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut entries = read(&self.path);
+        for (key, (fetched, val)) in &self.entries {
+            match entries.get(key) {
+                Some((saved, _)) if saved > fetched => {},
+                _ => { entries.insert(key.clone(), (*fetched, val.clone())); },
+            }
+        }
+
+        let mut contents = String::new();
+        for ((remote, context), (fetched, val)) in &entries {
+            let (backend, id) = remote.tag();
+            contents.push_str(&format!("{}\t{}\t{}\t{}\t{}\n", fetched, backend, escape(context), escape(id), escape(val)));
+        }
+
+        let temp = self.path.with_extension(format!("{}.tmp", std::process::id()));
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        options.open(&temp)?.write_all(contents.as_bytes())?;
+        std::fs::rename(&temp, &self.path)
+    }
+}
+
+
+/// Reads the entries of the cache file at `path`, skipping lines it can't parse,
+/// such as ones for a backend that isn't enabled.
+fn read(path: &Path) -> HashMap<(Remote, String), (u64, String)> {
+    let mut entries = HashMap::new();
+
+    for line in std::fs::read_to_string(path).unwrap_or_default().lines() {
+        let mut fields = line.splitn(5, '\t');
+        let (Some(fetched), Some(backend), Some(context), Some(id), Some(val)) = (fields.next(), fields.next(), fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let (Ok(fetched), Some(remote)) = (fetched.parse(), Remote::from_tag(backend, &unescape(id))) else {
+            continue;
+        };
+        entries.insert((remote, unescape(context)), (fetched, unescape(val)));
+    }
+
+    entries
+}
+
+
+/// Returns the path of the cache file in the user's cache directory, such as `~/.cache/easy-envar`.
+///
+/// `OUT_DIR` is never used, even from a build script, since `target` directories are often shared or archived.
+fn path() -> PathBuf {
+    let dir = std::env::var_os("XDG_CACHE_HOME").map(PathBuf::from)
+        .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+
+    dir.join("easy-envar").join(FILE_NAME)
+}
+
+
+/// Returns the current time in seconds since the epoch.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+
+/// Escapes backslashes, tabs, and line breaks, so a field fits on one line.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+
+/// Reverses `escape`.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
@@ -0,0 +1,98 @@
+use crate::EnvarError;
+
+
+/// Every failure from loading a set, formatted as a numbered multi-line report.
+///
+/// Each error is listed on its own, followed by the `.env` file and line that define the bad value
+/// when it is known, so every problem in a large configuration can be fixed in one pass.
+/// `Debug` prints the same report, so `main` can return `Result<(), Report>` to print it on failure.
+/// Requires the `pretty-errors` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{DotenvFile, Envar, EnvarSet, Report};
+///
+/// let path = std::env::temp_dir().join("report_doc.env");
+/// std::fs::write(&path, "HOST=localhost\nPORT=http\n").unwrap();
+///
+/// let errors = EnvarSet::new()
+///     .var(Envar::u16("PORT"))
+///     .var(Envar::string("DATABASE_URL"))
+///     .load_from(&DotenvFile::from_path(&path).unwrap())
+///     .unwrap_err();
+///
+/// let report = Report::from(errors);
+///
+/// assert_eq!(report.to_string(), format!(
+///     "2 errors while loading environment variables:\n\
+///      \n\
+///      \x20 1. environment variable `PORT` has value \"http\", which is not a valid u16\n\
+///      \x20    --> {}:2\n\
+///      \n\
+///      \x20 2. environment variable `DATABASE_URL` is not set\n",
+///     path.display(),
+/// ));
+/// ```
+pub struct Report {
+    errors: Vec<EnvarError>,
+}
+
+
+impl Report {
+    /// Creates a report of the given errors.
+    pub fn new(errors: Vec<EnvarError>) -> Self {
+        Report { errors }
+    }
+
+    /// Returns the reported errors.
+    pub fn errors(&self) -> &[EnvarError] {
+        &self.errors
+    }
+}
+
+
+impl From<Vec<EnvarError>> for Report {
+    fn from(errors: Vec<EnvarError>) -> Self {
+        Report::new(errors)
+    }
+}
+
+
+impl From<EnvarError> for Report {
+    fn from(error: EnvarError) -> Self {
+        Report::new(vec![error])
+    }
+}
+
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.errors.len() {
+            1 => writeln!(f, "1 error while loading environment variables:")?,
+            n => writeln!(f, "{} errors while loading environment variables:", n)?,
+        }
+
+        for (i, error) in self.errors.iter().enumerate() {
+            let number = format!("{}.", i + 1);
+            writeln!(f, "\n  {} {}", number, error)?;
+
+            if let Some(location) = error.location() {
+                writeln!(f, "  {:width$} --> {}", "", location, width = number.len())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+impl std::fmt::Debug for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+
+impl std::error::Error for Report {}
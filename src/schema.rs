@@ -0,0 +1,104 @@
+use serde_json::{json, Map, Value};
+
+use crate::{Envar, EnvarSet, EnvarSpec, LoadLevel};
+
+
+/// Builds a JSON Schema document describing the variables declared in `set`.
+pub(crate) fn json_schema(set: &EnvarSet) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for env_var in set.env_vars() {
+        let key = format!("{}{}", set.prefix(), env_var.key());
+
+        if env_var.default().is_none() && env_var.load_level() == LoadLevel::Required && env_var.conditions().is_empty() {
+            required.push(Value::String(key.clone()));
+        }
+
+        properties.insert(key, property(env_var));
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+
+/// Builds the schema of a single variable.
+///
+/// Environment variables are always strings, so each property is a string schema,
+/// narrowed by `enum` or `pattern` where the expected type allows it.
+fn property(env_var: &EnvarSpec) -> Value {
+    let mut property = Map::new();
+    property.insert("type".to_string(), json!("string"));
+
+    if let Some(description) = env_var.description() {
+        property.insert("description".to_string(), json!(description));
+    }
+
+    match env_var.envar() {
+        Envar::Bool(_) => {
+            property.insert("enum".to_string(), json!(["true", "false", "yes", "no", "y", "n", "on", "off", "1", "0"]));
+        },
+        Envar::OneOf(_, allowed) => {
+            property.insert("enum".to_string(), json!(allowed));
+        },
+        Envar::U8(_) | Envar::U16(_) | Envar::U32(_) | Envar::U64(_) | Envar::U128(_) | Envar::Usize(_) => {
+            property.insert("pattern".to_string(), json!("^\\+?[0-9]+$"));
+        },
+        Envar::I8(_) | Envar::I16(_) | Envar::I32(_) | Envar::I64(_) | Envar::I128(_) | Envar::Isize(_) => {
+            property.insert("pattern".to_string(), json!("^[+-]?[0-9]+$"));
+        },
+        Envar::NonZeroU16(_) | Envar::NonZeroU32(_) | Envar::NonZeroU64(_) => {
+            property.insert("pattern".to_string(), json!("^\\+?0*[1-9][0-9]*$"));
+        },
+        Envar::ByteSize(_) => {
+            property.insert("pattern".to_string(), json!("^[0-9]+\\s*([bB]|[kKmMgGtT]([iI]?[bB])?)?$"));
+        },
+        Envar::Percent(_) => {
+            property.insert("pattern".to_string(), json!("^([0-9]+(\\.[0-9]*)?|\\.[0-9]+)\\s*%?$"));
+        },
+        Envar::Hex(_) => {
+            property.insert("pattern".to_string(), json!("^([0-9a-fA-F]{2})*$"));
+        },
+        #[cfg(feature = "chrono")]
+        Envar::DateTime(_) => {
+            property.insert("format".to_string(), json!("date-time"));
+        },
+        #[cfg(feature = "chrono")]
+        Envar::Date(_) => {
+            property.insert("format".to_string(), json!("date"));
+        },
+        #[cfg(feature = "log")]
+        Envar::LogLevel(_) => {
+            property.insert("enum".to_string(), json!(["off", "error", "warn", "info", "debug", "trace"]));
+        },
+        #[cfg(feature = "serde_json")]
+        Envar::Json(_) => {
+            property.insert("contentMediaType".to_string(), json!("application/json"));
+        },
+        #[cfg(feature = "url")]
+        Envar::Url(_) => {
+            property.insert("format".to_string(), json!("uri"));
+        },
+        _ => {},
+    }
+
+    if env_var.is_secret() {
+        property.insert("writeOnly".to_string(), json!(true));
+    } else if let Some(default) = env_var.default() {
+        property.insert("default".to_string(), json!(default));
+    }
+
+    property.insert("x-type".to_string(), json!(env_var.envar().type_name()));
+
+    let constraints: Vec<&str> = env_var.constraints().iter().map(|constraint| constraint.description()).collect();
+    if !constraints.is_empty() {
+        property.insert("x-constraints".to_string(), json!(constraints));
+    }
+
+    Value::Object(property)
+}
@@ -0,0 +1,70 @@
+/// A loaded secret value whose `Debug` and `Display` output is masked as `***`.
+///
+/// The value is still exported as-is, so it remains available through `env!`.
+/// Use `expose` to read it explicitly.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, LoadedEnvar};
+///
+/// std::env::set_var("SECRET_DOC_API_KEY", "hunter2");
+///
+/// let loaded = Envar::secret_string("SECRET_DOC_API_KEY").load().unwrap();
+///
+/// assert_eq!(format!("{:?}", loaded), r#"Secret("SECRET_DOC_API_KEY", ***)"#);
+///
+/// if let LoadedEnvar::Secret(_, secret) = loaded {
+///     assert_eq!(secret.to_string(), "***");
+///     assert_eq!(secret.expose(), "hunter2");
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+
+impl Secret {
+    /// Wraps a secret value.
+    pub fn new(val: impl Into<String>) -> Self {
+        Secret(val.into())
+    }
+
+    /// Returns the unmasked value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+
+/// Wraps the string as is, so a `Secret` can be loaded like any type implementing `FromStr`,
+/// such as a `#[envar(secret)]` field.
+impl std::str::FromStr for Secret {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Secret::new(s))
+    }
+}
+
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***")
+    }
+}
+
+
+impl std::fmt::Display for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***")
+    }
+}
+
+
+#[cfg(feature = "secrecy")]
+impl From<Secret> for secrecy::SecretString {
+    fn from(secret: Secret) -> Self {
+        secrecy::SecretString::from(secret.0)
+    }
+}
@@ -0,0 +1,136 @@
+//! `serde` serialization of loaded values, to dump the resolved configuration as JSON or TOML.
+
+use std::collections::BTreeMap;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::{EnvarConfig, LoadedEnvar, LoadedValue};
+
+
+/// Serializes the wrapped value with secret values in plain text, rather than redacted as `***`.
+///
+/// `LoadedValue`, `LoadedEnvar`, `EnvarConfig`, and the map returned by `EnvarSet::load_map`
+/// redact secrets when serialized on their own, so a debugging endpoint or a support bundle
+/// doesn't leak them by accident. Requires the `serde` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{EnvarConfig, LoadedEnvar, Secret, Unredacted};
+///
+/// let config: EnvarConfig = [
+///     LoadedEnvar::U16("PORT".into(), 8080),
+///     LoadedEnvar::Secret("API_KEY".into(), Secret::new("hunter2".to_string())),
+/// ].into_iter().collect();
+///
+/// assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"API_KEY":"***","PORT":8080}"#);
+/// assert_eq!(serde_json::to_string(&Unredacted(&config)).unwrap(), r#"{"API_KEY":"hunter2","PORT":8080}"#);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Unredacted<'a, T: ?Sized>(pub &'a T);
+
+
+/// A value that serializes with its secrets either redacted or in plain text, as selected by `Unredacted`.
+/// Requires the `serde` feature.
+pub trait Redact {
+    /// Serializes the value, replacing secret values with `***` if `redact` is `true`.
+    fn serialize_redacted<S: Serializer>(&self, redact: bool, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+
+impl<T: Redact + ?Sized> Serialize for Unredacted<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_redacted(false, serializer)
+    }
+}
+
+
+impl Redact for LoadedValue {
+    fn serialize_redacted<S: Serializer>(&self, redact: bool, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            LoadedValue::Bool(val) => serializer.serialize_bool(*val),
+            // Formats such as TOML have no 128-bit integers, so these are narrowed when they fit.
+            LoadedValue::UInt(val) => match u64::try_from(*val) {
+                Ok(val) => serializer.serialize_u64(val),
+                Err(_) => serializer.serialize_u128(*val),
+            },
+            LoadedValue::Int(val) => match i64::try_from(*val) {
+                Ok(val) => serializer.serialize_i64(val),
+                Err(_) => serializer.serialize_i128(*val),
+            },
+            LoadedValue::Float(val) => serializer.serialize_f64(*val),
+            LoadedValue::Char(val) => serializer.serialize_char(*val),
+            LoadedValue::Duration(val) => LoadedValue::UInt(val.as_millis()).serialize_redacted(redact, serializer),
+            LoadedValue::List(val) => val.serialize(serializer),
+            LoadedValue::Secret(val) if !redact => serializer.serialize_str(val.expose()),
+            val => serializer.collect_str(val),
+        }
+    }
+}
+
+
+impl Serialize for LoadedValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_redacted(true, serializer)
+    }
+}
+
+
+/// Serializes the value alone, as a `LoadedValue`.
+impl Redact for LoadedEnvar<'_> {
+    fn serialize_redacted<S: Serializer>(&self, redact: bool, serializer: S) -> Result<S::Ok, S::Error> {
+        LoadedValue::from(self.clone()).serialize_redacted(redact, serializer)
+    }
+}
+
+
+impl Serialize for LoadedEnvar<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_redacted(true, serializer)
+    }
+}
+
+
+/// Serializes a map from variable name to value, sorted by name.
+impl Redact for EnvarConfig<'_> {
+    fn serialize_redacted<S: Serializer>(&self, redact: bool, serializer: S) -> Result<S::Ok, S::Error> {
+        let sorted: BTreeMap<&str, &LoadedEnvar> = self.iter().map(|env_var| (env_var.key(), env_var)).collect();
+
+        let mut map = serializer.serialize_map(Some(sorted.len()))?;
+        for (key, env_var) in sorted {
+            map.serialize_entry(key, &Redacted(env_var, redact))?;
+        }
+        map.end()
+    }
+}
+
+
+impl Serialize for EnvarConfig<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_redacted(true, serializer)
+    }
+}
+
+
+/// Serializes a map returned by `EnvarSet::load_map`, which redacts secrets on its own through `LoadedValue`.
+impl Redact for BTreeMap<String, LoadedValue> {
+    fn serialize_redacted<S: Serializer>(&self, redact: bool, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self {
+            map.serialize_entry(key, &Redacted(value, redact))?;
+        }
+        map.end()
+    }
+}
+
+
+/// A value to serialize as an entry, passing the choice to redact secrets down.
+struct Redacted<'a, T: ?Sized>(&'a T, bool);
+
+
+impl<T: Redact + ?Sized> Serialize for Redacted<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_redacted(self.1, serializer)
+    }
+}
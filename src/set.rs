@@ -0,0 +1,1356 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::spec::Diagnostics;
+use crate::audit::{AuditEntry, AuditReport};
+use crate::{DotenvFile, EnvarConfig, EnvarError, EnvarSpec, LoadLevel, LoadedEnvar, LoadedValue, ProcessEnv, Shell, Source, SourceDiff, SourceStack};
+
+
+/// A collection of declared environment variables that are loaded together.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarSet, LoadedEnvar};
+///
+/// std::env::set_var("SET_DOC_PORT", "8080");
+///
+/// let set = EnvarSet::with_prefix("SET_DOC_")
+///     .var(Envar::u16("PORT"))
+///     .var(Envar::string("HOST").or_default("localhost"));
+///
+/// let loaded = set.load().unwrap();
+///
+/// assert!(matches!(&loaded[0], LoadedEnvar::U16(key, 8080) if key == "PORT"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EnvarSet<'a> {
+    prefix: String,
+    env_vars: Vec<EnvarSpec<'a>>,
+    rules: Vec<Rule>,
+    /// How values declared with a remote backend are fetched.
+    #[cfg(feature = "remote")]
+    remote: crate::remote::FetchOptions,
+}
+
+
+/// A function checking the loaded variables, returning why they are invalid if they are.
+type Check = dyn Fn(&EnvarConfig) -> Result<(), String> + Send + Sync;
+
+
+/// A check spanning several variables, added with `EnvarSet::rule`.
+#[derive(Clone)]
+struct Rule(Arc<Check>);
+
+
+impl std::fmt::Debug for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Rule")
+    }
+}
+
+
+impl<'a> EnvarSet<'a> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        EnvarSet::default()
+    }
+
+    /// Creates an empty set whose variables are looked up under the given prefix.
+    ///
+    /// Declaring `Envar::u16("PORT")` in a set prefixed with `MYAPP_` reads `MYAPP_PORT`.
+    /// Loaded variables keep their declared names, so `PORT` is what gets exported.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet, LoadedEnvar};
+    ///
+    /// std::env::set_var("MYAPP_PORT", "8080");
+    ///
+    /// let set = EnvarSet::with_prefix("MYAPP_").var(Envar::u16("PORT"));
+    ///
+    /// assert!(matches!(&set.load().unwrap()[0], LoadedEnvar::U16(key, 8080) if key == "PORT"));
+    /// ```
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        EnvarSet {
+            prefix: prefix.into(),
+            env_vars: Vec::new(),
+            rules: Vec::new(),
+            #[cfg(feature = "remote")]
+            remote: Default::default(),
+        }
+    }
+
+    /// Adds an environment variable to the set.
+    pub fn var(mut self, env_var: impl Into<EnvarSpec<'a>>) -> Self {
+        self.env_vars.push(env_var.into());
+        self
+    }
+
+    /// Adds every given environment variable to the set.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let set = EnvarSet::new().vars([
+    ///     Envar::string("HOST"),
+    ///     Envar::u16("PORT"),
+    /// ]);
+    ///
+    /// assert_eq!(set.env_vars().len(), 2);
+    /// ```
+    pub fn vars<I>(mut self, env_vars: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<EnvarSpec<'a>>,
+    {
+        self.env_vars.extend(env_vars.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a rule spanning several variables, such as requiring `TLS_CERT` when `SECURE` is `true`,
+    /// or `POOL_MIN` to be at most `POOL_MAX`.
+    ///
+    /// After loading, each rule is called with the variables that loaded successfully, in the order the rules were added.
+    /// A rule returning `Err(message)` is reported as `EnvarError::RuleViolation`, after any errors of individual variables.
+    /// Rules see only the variables that loaded, so a rule should treat a missing variable as unset rather than as a violation.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let set = EnvarSet::new()
+    ///     .var(Envar::bool("SECURE").or_default(false))
+    ///     .var(Envar::path("TLS_CERT").warn_if_missing())
+    ///     .var(Envar::u32("POOL_MIN").or_default(1))
+    ///     .var(Envar::u32("POOL_MAX").or_default(10))
+    ///     .rule(|vars| match (vars.get_as::<bool>("SECURE"), vars.get("TLS_CERT")) {
+    ///         (Some(true), None) => Err("`TLS_CERT` must be set when `SECURE` is true".to_string()),
+    ///         _ => Ok(()),
+    ///     })
+    ///     .rule(|vars| match (vars.get_as::<u32>("POOL_MIN"), vars.get_as::<u32>("POOL_MAX")) {
+    ///         (Some(min), Some(max)) if min > max => Err(format!("`POOL_MIN` ({}) exceeds `POOL_MAX` ({})", min, max)),
+    ///         _ => Ok(()),
+    ///     });
+    ///
+    /// let errors = set.load_from(&MockEnv::new().var("SECURE", "true").var("POOL_MIN", "20")).unwrap_err();
+    ///
+    /// assert_eq!(errors[0].to_string(), "configuration rule violated: `TLS_CERT` must be set when `SECURE` is true");
+    /// assert_eq!(errors[1].to_string(), "configuration rule violated: `POOL_MIN` (20) exceeds `POOL_MAX` (10)");
+    /// ```
+    pub fn rule(mut self, check: impl Fn(&EnvarConfig) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        self.rules.push(Rule(Arc::new(check)));
+        self
+    }
+
+    /// Sets how many requests to remote backends, such as Vault or AWS Secrets Manager, may run at once while loading.
+    ///
+    /// Secrets declared with methods such as `from_vault` are fetched in parallel, up to eight at a time by default.
+    /// Lower it to go easy on a rate-limited backend, or set it to `1` to fetch one at a time.
+    /// Entries in the OS keyring are always read one at a time, since reading one may prompt the user.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// // Fetch at most two secrets at a time from a rate-limited backend.
+    /// let set = EnvarSet::new()
+    ///     .var(Envar::string("DB_PASSWORD"))
+    ///     .remote_concurrency(2);
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn remote_concurrency(mut self, concurrency: usize) -> Self {
+        self.remote.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Saves values fetched from remote backends to a cache file, and reuses them for up to `ttl`,
+    /// so repeated local builds don't fetch every secret again.
+    ///
+    /// The file is `easy-envar-remote-cache` in the user's cache directory, such as `~/.cache/easy-envar`,
+    /// even in a build script, so secrets never land in a shared `target` directory.
+    /// It holds the values in plain text: on Unix it is readable only by the current user,
+    /// while on Windows it inherits the permissions of `%LOCALAPPDATA%`.
+    /// Secret variables and values from the OS keyring are not saved, unless `remote_cache_secrets` allows the former.
+    /// Values are saved along with the backend's configuration, such as `AWS_PROFILE` and `AWS_REGION`, `VAULT_ADDR`,
+    /// the `gcloud` project, or `EASY_ENVAR_AZURE_VAULT`, and are only reused under the same one.
+    /// Use `force_refresh` to fetch every value again regardless.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// // Fetch each secret at most once an hour across builds.
+    /// let set = EnvarSet::new()
+    ///     .var(Envar::string("DB_PASSWORD"))
+    ///     .remote_cache(Duration::from_secs(60 * 60));
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn remote_cache(mut self, ttl: std::time::Duration) -> Self {
+        self.remote.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Saves the values of secret variables to the cache file as well, which `remote_cache` otherwise skips.
+    ///
+    /// The values are stored in plain text, so only opt in on machines where the user's cache directory is private.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let set = EnvarSet::new()
+    ///     .var(Envar::secret_string("DB_PASSWORD"))
+    ///     .remote_cache(Duration::from_secs(60 * 60))
+    ///     .remote_cache_secrets();
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn remote_cache_secrets(mut self) -> Self {
+        self.remote.cache_secrets = true;
+        self
+    }
+
+    /// Fetches every value from its remote backend again, ignoring values cached in this process or on disk,
+    /// and saves the fresh values to the cache file if `remote_cache` is set.
+    ///
+    /// Useful after rotating a secret, for example behind a flag such as `REFRESH_SECRETS=1`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let mut set = EnvarSet::new()
+    ///     .var(Envar::string("DB_PASSWORD"))
+    ///     .remote_cache(Duration::from_secs(60 * 60));
+    ///
+    /// if std::env::var_os("REFRESH_SECRETS").is_some() {
+    ///     set = set.force_refresh();
+    /// }
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn force_refresh(mut self) -> Self {
+        self.remote.refresh = true;
+        self
+    }
+
+    /// Returns the prefix variables are looked up under.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the declared environment variables.
+    pub fn env_vars(&self) -> &[EnvarSpec<'a>] {
+        &self.env_vars
+    }
+
+    /// Loads every environment variable in the set,
+    /// collecting all failures instead of stopping at the first one.
+    ///
+    /// Warnings, such as for a deprecated name, are printed as `cargo:warning` directives in a build script,
+    /// and to stderr otherwise, so they don't end up in a program's output.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(Vec<LoadedEnvar>)`: if every variable is successfully loaded, in declaration order.
+    ///   Missing `LoadLevel::Warn` variables without a default are skipped.
+    /// - `Err(Vec<EnvarError>)`: every error encountered, in declaration order.
+    pub fn load(&self) -> Result<Vec<LoadedEnvar<'a>>, Vec<EnvarError>> {
+        self.load_with(&ProcessEnv, Diagnostics::detect())
+    }
+
+    /// Loads like `load`, but reads values from `source` instead of the process environment.
+    pub fn load_from(&self, source: &dyn Source) -> Result<Vec<LoadedEnvar<'a>>, Vec<EnvarError>> {
+        self.load_with(source, Diagnostics::detect())
+    }
+
+    /// Loads every environment variable in the set at runtime, such as in `main.rs` at startup,
+    /// rather than in a build script.
+    ///
+    /// Behaves like `load`, but prints warnings to stderr instead of as `cargo:warning` directives,
+    /// and collects the loaded variables into an `EnvarConfig` keyed by variable name.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(EnvarConfig)`: if every variable is successfully loaded.
+    /// - `Err(Vec<EnvarError>)`: every error encountered, in declaration order.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// std::env::set_var("LOAD_RUNTIME_DOC_PORT", "8080");
+    ///
+    /// let config = EnvarSet::new()
+    ///     .var(Envar::u16("LOAD_RUNTIME_DOC_PORT"))
+    ///     .var(Envar::string("LOAD_RUNTIME_DOC_HOST").or_default("localhost"))
+    ///     .load_runtime()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.get_as::<u16>("LOAD_RUNTIME_DOC_PORT"), Some(8080));
+    /// assert_eq!(config.get("LOAD_RUNTIME_DOC_HOST").unwrap().as_str(), Some("localhost"));
+    /// ```
+    pub fn load_runtime(&self) -> Result<EnvarConfig<'a>, Vec<EnvarError>> {
+        self.load_with(&ProcessEnv, Diagnostics::Stderr).map(EnvarConfig::from_iter)
+    }
+
+    /// Loads every environment variable in the set like `load_runtime`, into a map from variable name to typed value,
+    /// for code that iterates over or serializes the whole configuration.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(BTreeMap<String, LoadedValue>)`: if every variable is successfully loaded, sorted by name.
+    /// - `Err(Vec<EnvarError>)`: every error encountered, in declaration order.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet, LoadedValue};
+    ///
+    /// std::env::set_var("LOAD_MAP_DOC_PORT", "8080");
+    /// std::env::set_var("LOAD_MAP_DOC_HOSTS", "a, b");
+    ///
+    /// let map = EnvarSet::new()
+    ///     .var(Envar::u16("LOAD_MAP_DOC_PORT"))
+    ///     .var(Envar::list("LOAD_MAP_DOC_HOSTS"))
+    ///     .var(Envar::string("LOAD_MAP_DOC_HOST").or_default("localhost"))
+    ///     .load_map()
+    ///     .unwrap();
+    ///
+    /// assert!(matches!(map["LOAD_MAP_DOC_PORT"], LoadedValue::UInt(8080)));
+    /// assert!(matches!(&map["LOAD_MAP_DOC_HOSTS"], LoadedValue::List(hosts) if hosts == &["a", "b"]));
+    ///
+    /// for (key, value) in &map {
+    ///     println!("{}={}", key, value);
+    /// }
+    /// ```
+    pub fn load_map(&self) -> Result<BTreeMap<String, LoadedValue>, Vec<EnvarError>> {
+        let loaded = self.load_with(&ProcessEnv, Diagnostics::Stderr)?;
+        Ok(loaded.into_iter().map(|env_var| (env_var.key().to_string(), LoadedValue::from(env_var))).collect())
+    }
+
+    /// Loads every environment variable in the set from `source`, printing warnings to `diagnostics`.
+    pub(crate) fn load_with(&self, source: &dyn Source, diagnostics: Diagnostics) -> Result<Vec<LoadedEnvar<'a>>, Vec<EnvarError>> {
+        #[cfg(feature = "tracing")]
+        let _span = crate::trace::set_span(&self.prefix, self.env_vars.len()).entered();
+
+        #[cfg(feature = "remote")]
+        let source = &crate::remote::Layered::resolve(&self.env_vars, &self.prefix, source, self.remote);
+
+        self.load_each(source, diagnostics)
+    }
+
+    /// Loads every environment variable in the set at runtime like `load_runtime`, as a future.
+    ///
+    /// Values declared with a remote backend, such as `from_vault` or `from_aws`, are fetched on
+    /// Tokio's blocking thread pool, so a slow secret store doesn't stall the runtime's other tasks.
+    /// Must be awaited within a Tokio runtime. Build scripts have no runtime, so keep using `load` there.
+    /// Requires the `tokio` feature.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(EnvarConfig)`: if every variable is successfully loaded.
+    /// - `Err(Vec<EnvarError>)`: every error encountered, in declaration order.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// std::env::set_var("LOAD_ASYNC_DOC_PORT", "8080");
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    ///
+    /// let config = runtime.block_on(async {
+    ///     EnvarSet::new()
+    ///         .var(Envar::u16("LOAD_ASYNC_DOC_PORT"))
+    ///         .var(Envar::string("LOAD_ASYNC_DOC_HOST").or_default("localhost"))
+    ///         .load_async()
+    ///         .await
+    /// }).unwrap();
+    ///
+    /// assert_eq!(config.get_as::<u16>("LOAD_ASYNC_DOC_PORT"), Some(8080));
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn load_async(&self) -> Result<EnvarConfig<'a>, Vec<EnvarError>> {
+        self.load_from_async(&ProcessEnv).await
+    }
+
+    /// Loads like `load_async`, but reads values from `source` instead of the process environment.
+    ///
+    /// Requires the `tokio` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    ///
+    /// // The future can be spawned as a task of its own.
+    /// let task = runtime.spawn(async {
+    ///     let source = HashMap::from([("WORKERS", "4")]);
+    ///     let config = EnvarSet::new().var(Envar::u8("WORKERS")).load_from_async(&source).await.unwrap();
+    ///     config.get_as::<u8>("WORKERS")
+    /// });
+    ///
+    /// assert_eq!(runtime.block_on(task).unwrap(), Some(4));
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn load_from_async(&self, source: &(dyn Source + Sync)) -> Result<EnvarConfig<'a>, Vec<EnvarError>> {
+        let source = &crate::remote::Layered::resolve_async(&self.env_vars, &self.prefix, source, self.remote).await;
+
+        #[cfg(feature = "tracing")]
+        let _span = crate::trace::set_span(&self.prefix, self.env_vars.len()).entered();
+
+        self.load_each(source, Diagnostics::Stderr).map(EnvarConfig::from_iter)
+    }
+
+    /// Loads every environment variable in the set from `source`, which already holds any remote values.
+    fn load_each(&self, source: &dyn Source, diagnostics: Diagnostics) -> Result<Vec<LoadedEnvar<'a>>, Vec<EnvarError>> {
+        let mut loaded = Vec::with_capacity(self.env_vars.len());
+        let mut errors = Vec::new();
+
+        for env_var in &self.env_vars {
+            match env_var.load_lenient(source, &self.prefix, diagnostics) {
+                Ok(env_var) => loaded.extend(env_var),
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    crate::trace::failed(&e);
+                    errors.push(e);
+                },
+            }
+        }
+
+        if !self.rules.is_empty() {
+            let config = EnvarConfig::from_iter(loaded.iter().cloned());
+            errors.extend(self.rules.iter().filter_map(|rule| (rule.0)(&config).err()).map(|message| EnvarError::RuleViolation { message }));
+        }
+
+        if errors.is_empty() {
+            Ok(loaded)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Checks that every environment variable in the set loads successfully, without exporting anything.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if every variable is successfully loaded.
+    /// - `Err(Vec<EnvarError>)`: every error encountered, in declaration order.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError, EnvarSet};
+    ///
+    /// let set = EnvarSet::new().var(Envar::u16("VERIFY_DOC_MISSING"));
+    ///
+    /// assert!(matches!(set.verify().unwrap_err()[0], EnvarError::Missing { .. }));
+    /// ```
+    pub fn verify(&self) -> Result<(), Vec<EnvarError>> {
+        self.load().map(|_| ())
+    }
+
+    /// Checks the set like `verify`, and also rejects entries of the `.env` file that are never declared.
+    ///
+    /// This catches dead configuration and typos such as `PROT=8080`.
+    /// The `.env` file is found the same way as `init`.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if every variable is successfully loaded, and every `.env` entry is declared.
+    /// - `Err(Vec<EnvarError>)`: every load error in declaration order,
+    ///   followed by an `EnvarError::Undeclared` for each undeclared entry in file order.
+    ///   A single `EnvarError::DotenvError` if the `.env` file could not be read.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // build.rs
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// fn main() {
+    ///     easy_envar::init().unwrap();
+    ///
+    ///     let env_vars = EnvarSet::new()
+    ///         .var(Envar::string("HOST"))
+    ///         .var(Envar::u16("PORT"));
+    ///
+    ///     env_vars.verify_strict().unwrap();
+    /// }
+    /// ```
+    pub fn verify_strict(&self) -> Result<(), Vec<EnvarError>> {
+        let file = DotenvFile::find().map_err(|e| vec![e])?;
+        self.verify_entries(&file)
+    }
+
+    /// Checks the set like `verify_strict`, using the `.env` file at the given path.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError, EnvarSet};
+    ///
+    /// let path = std::env::temp_dir().join("verify_strict_doc.env");
+    /// std::fs::write(&path, "VERIFY_STRICT_DOC_PORT=8080\nVERIFY_STRICT_DOC_PROT=8080\n").unwrap();
+    ///
+    /// easy_envar::init_from_path(&path).unwrap();
+    ///
+    /// let set = EnvarSet::new().var(Envar::u16("VERIFY_STRICT_DOC_PORT"));
+    /// let errors = set.verify_strict_from_path(&path).unwrap_err();
+    ///
+    /// assert_eq!(errors.len(), 1);
+    /// assert!(matches!(&errors[0], EnvarError::Undeclared { key } if key == "VERIFY_STRICT_DOC_PROT"));
+    /// ```
+    pub fn verify_strict_from_path(&self, path: impl AsRef<Path>) -> Result<(), Vec<EnvarError>> {
+        let file = DotenvFile::from_path(path).map_err(|e| vec![e])?;
+        self.verify_entries(&file)
+    }
+
+    /// Audits the set against the process environment and the `.env` file, found the same way as `init`.
+    ///
+    /// Unlike loading, auditing never fails, and prints no warnings. Every declared variable
+    /// is reported as present, empty, defaulted, skipped, missing, or invalid,
+    /// and every `.env` entry no declared variable reads is reported as unused.
+    /// If there is no `.env` file, no entries are reported as unused.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // build.rs
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// fn main() {
+    ///     easy_envar::init().unwrap();
+    ///
+    ///     let report = EnvarSet::new()
+    ///         .var(Envar::string("HOST"))
+    ///         .var(Envar::u16("PORT"))
+    ///         .audit();
+    ///
+    ///     if !report.is_ok() {
+    ///         eprint!("{}", report);
+    ///         std::process::exit(1);
+    ///     }
+    /// }
+    /// ```
+    pub fn audit(&self) -> AuditReport {
+        self.audit_from(&ProcessEnv, DotenvFile::find().ok().as_ref())
+    }
+
+    /// Checks the set as a dry run of `init` followed by `load`, without touching the process environment.
+    ///
+    /// Every variable is looked up, parsed, and validated against the process environment,
+    /// then the `.env` file (found the same way as `init`), which is read but never loaded,
+    /// then its remote backend, if declared with one such as `from_aws`, exactly as `load` would.
+    /// Nothing is exported, no warnings are printed, and the remote cache file is neither read nor written,
+    /// so this suits a standalone preflight binary in a deploy pipeline. The result is the same report as `audit`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // src/bin/preflight.rs
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// fn main() {
+    ///     let report = EnvarSet::new()
+    ///         .var(Envar::string("HOST"))
+    ///         .var(Envar::u16("PORT"))
+    ///         .check();
+    ///
+    ///     print!("{}", report);
+    ///
+    ///     if !report.is_ok() {
+    ///         std::process::exit(1);
+    ///     }
+    /// }
+    /// ```
+    pub fn check(&self) -> AuditReport {
+        match DotenvFile::find() {
+            Ok(dotenv) => {
+                let sources = SourceStack::new().with(ProcessEnv).with(dotenv.clone());
+                self.audit_from(&sources, Some(&dotenv))
+            },
+            Err(_) => self.audit_from(&ProcessEnv, None),
+        }
+    }
+
+    /// Compares the declared variables between two sources, such as the process environment and `.env.production`.
+    ///
+    /// Unlike `diff`, only the declared variables are compared, looked up under the set's prefix,
+    /// and exactly the secret ones (see `EnvarSpec::secret`) have their values masked as `***`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::testing::MockEnv;
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let local = MockEnv::new().var("APP_PORT", "3000").var("APP_DB_PASS", "local").var("HOME", "/root");
+    /// let production = MockEnv::new().var("APP_PORT", "443").var("APP_DB_PASS", "hunter2").var("APP_REGION", "eu");
+    ///
+    /// let diff = EnvarSet::with_prefix("APP_")
+    ///     .var(Envar::u16("PORT"))
+    ///     .var(Envar::string("DB_PASS").secret())
+    ///     .var(Envar::string("REGION").or_default("us"))
+    ///     .diff(&local, &production);
+    ///
+    /// assert_eq!(diff.to_string(), "~ APP_DB_PASS: *** -> ***\n~ APP_PORT: 3000 -> 443\n+ APP_REGION=eu\n");
+    /// ```
+    pub fn diff(&self, left: &dyn Source, right: &dyn Source) -> SourceDiff {
+        let keys: BTreeSet<String> = self.env_vars.iter()
+            .map(|env_var| format!("{}{}", self.prefix, env_var.key()))
+            .collect();
+
+        let secrets: Vec<String> = self.env_vars.iter()
+            .filter(|env_var| env_var.is_secret())
+            .map(|env_var| format!("{}{}", self.prefix, env_var.key()))
+            .collect();
+
+        SourceDiff::new(keys, left, right, |key| secrets.iter().any(|secret| secret == key))
+    }
+
+    /// Audits the set like `audit`, reading values from `source`,
+    /// and reporting the unused entries of `dotenv`, if given.
+    ///
+    /// Variables that `source` doesn't set are fetched from their remote backend, if declared with one,
+    /// fresh rather than from the cache file set up by `remote_cache`, which is left untouched.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::testing::MockEnv;
+    /// use easy_envar::{AuditStatus, Envar, EnvarSet};
+    ///
+    /// let env = MockEnv::new().var("PORT", "http").var("HOST", "");
+    ///
+    /// let report = EnvarSet::new()
+    ///     .var(Envar::string("HOST"))
+    ///     .var(Envar::u16("PORT"))
+    ///     .var(Envar::bool("SECURE").or_default(false))
+    ///     .var(Envar::string("API_KEY"))
+    ///     .audit_from(&env, None);
+    ///
+    /// let statuses: Vec<_> = report.entries().iter().map(|entry| entry.status()).collect();
+    ///
+    /// assert!(matches!(statuses[0], AuditStatus::Empty));
+    /// assert!(matches!(statuses[1], AuditStatus::Invalid(_)));
+    /// assert!(matches!(statuses[2], AuditStatus::Defaulted));
+    /// assert!(matches!(statuses[3], AuditStatus::Missing));
+    /// assert!(!report.is_ok());
+    /// ```
+    pub fn audit_from(&self, source: &dyn Source, dotenv: Option<&DotenvFile>) -> AuditReport {
+        #[cfg(feature = "remote")]
+        let options = crate::remote::FetchOptions { cache_ttl: None, ..self.remote };
+        #[cfg(feature = "remote")]
+        let source = &crate::remote::Layered::resolve(&self.env_vars, &self.prefix, source, options);
+
+        let entries = self.env_vars.iter()
+            .map(|env_var| {
+                let lookup = format!("{}{}", self.prefix, env_var.key());
+                let raw = env_var.raw(source, &self.prefix, Diagnostics::Silent);
+                let loaded = env_var.load_lenient(source, &self.prefix, Diagnostics::Silent);
+
+                AuditEntry::new(lookup, env_var.description(), raw, loaded)
+            })
+            .collect();
+
+        let unused = dotenv.map(|dotenv| {
+            dotenv.keys().iter()
+                .filter(|key| !self.declares(key))
+                .cloned()
+                .collect()
+        });
+
+        AuditReport::new(entries, unused.unwrap_or_default())
+    }
+
+    /// Verifies the set, then reports every entry not declared in it.
+    fn verify_entries(&self, file: &DotenvFile) -> Result<(), Vec<EnvarError>> {
+        let mut errors = self.verify().err().unwrap_or_default();
+
+        for key in file.keys() {
+            if !self.declares(key) {
+                errors.push(EnvarError::Undeclared { key: key.clone() });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Loads every environment variable in the set and writes them to `dir` as Kubernetes manifests:
+    /// a `ConfigMap` in `configmap.yaml`, and a `Secret` in `secret.yaml`, both named `name`.
+    ///
+    /// Secret variables (see `EnvarSpec::secret`) go into the `Secret`, and every other variable
+    /// into the `ConfigMap`. Variables are keyed by the name they were looked up under, including any prefix,
+    /// so a deployment using the manifests provides the same environment the set was loaded from.
+    /// Nothing is written unless every variable loads successfully.
+    /// Warnings, such as for a deprecated name, are printed as `cargo:warning` directives in a build script,
+    /// and to stderr otherwise, so they don't end up in piped output.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if both manifests were written.
+    /// - `Err(Vec<EnvarError>)`: every load error in declaration order, or a single `EnvarError::Io`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// std::env::set_var("K8S_DOC_PORT", "8080");
+    /// std::env::set_var("K8S_DOC_API_KEY", "hunter2");
+    ///
+    /// let dir = std::env::temp_dir().join("write_k8s_manifests_doc");
+    /// std::fs::create_dir_all(&dir).unwrap();
+    ///
+    /// EnvarSet::new()
+    ///     .var(Envar::u16("K8S_DOC_PORT"))
+    ///     .var(Envar::secret_string("K8S_DOC_API_KEY"))
+    ///     .write_k8s_manifests(&dir, "my-app")
+    ///     .unwrap();
+    ///
+    /// let config_map = std::fs::read_to_string(dir.join("configmap.yaml")).unwrap();
+    /// let secret = std::fs::read_to_string(dir.join("secret.yaml")).unwrap();
+    ///
+    /// assert!(config_map.contains("\"K8S_DOC_PORT\": \"8080\""));
+    /// assert!(secret.contains("\"K8S_DOC_API_KEY\": \"hunter2\""));
+    /// ```
+    pub fn write_k8s_manifests(&self, dir: impl AsRef<Path>, name: &str) -> Result<(), Vec<EnvarError>> {
+        let loaded: Vec<(String, LoadedEnvar)> = self.load_with(&ProcessEnv, Diagnostics::detect())?
+            .into_iter()
+            .map(|env_var| (format!("{}{}", self.prefix, env_var.key()), env_var))
+            .collect();
+
+        let dir = dir.as_ref();
+        let files = [
+            (dir.join("configmap.yaml"), crate::manifest::config_map(name, &loaded)),
+            (dir.join("secret.yaml"), crate::manifest::secret(name, &loaded)),
+        ];
+
+        for (path, contents) in files {
+            std::fs::write(&path, contents).map_err(|source| vec![EnvarError::Io { path, source }])?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads every environment variable in the set and writes them to `path` as a Docker env file,
+    /// for `docker run --env-file` or the `env_file` option of Docker Compose.
+    ///
+    /// Variables are keyed by the name they were looked up under, including any prefix,
+    /// and quoted as described in `LoadedEnvar::env_file_line`.
+    /// Nothing is written unless every variable loads successfully.
+    /// Warnings, such as for a deprecated name, are printed as `cargo:warning` directives in a build script,
+    /// and to stderr otherwise, so they don't end up in piped output.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if the file was written.
+    /// - `Err(Vec<EnvarError>)`: every load error in declaration order, or a single `EnvarError::Io`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// std::env::set_var("ENV_FILE_DOC_PORT", "8080");
+    ///
+    /// let path = std::env::temp_dir().join("write_env_file_doc.env");
+    ///
+    /// EnvarSet::new()
+    ///     .var(Envar::u16("ENV_FILE_DOC_PORT"))
+    ///     .write_env_file(&path)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(std::fs::read_to_string(&path).unwrap(), "ENV_FILE_DOC_PORT=8080\n");
+    /// ```
+    pub fn write_env_file(&self, path: impl AsRef<Path>) -> Result<(), Vec<EnvarError>> {
+        let path = path.as_ref();
+
+        let contents: String = self.load_with(&ProcessEnv, Diagnostics::detect())?
+            .iter()
+            .map(|env_var| crate::manifest::env_file_line(&format!("{}{}", self.prefix, env_var.key()), &env_var.value_string()))
+            .collect();
+
+        std::fs::write(path, contents).map_err(|source| vec![EnvarError::Io { path: path.to_path_buf(), source }])
+    }
+
+    /// Loads every environment variable in the set and saves them to the `.env` file at `path`,
+    /// such as to generate a developer's `.env` from the defaults and the values an onboarding tool asked for.
+    ///
+    /// Variables are keyed by the name they were looked up under, including any prefix.
+    /// Entries already in the file are updated in place and new ones are added at the end,
+    /// so comments and the order of entries are preserved; see `set_in_path`.
+    /// Secret values are saved as they are. Nothing is written unless every variable loads successfully.
+    /// Warnings, such as for a deprecated name, are printed as `cargo:warning` directives in a build script,
+    /// and to stderr otherwise, so they don't end up in piped output.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if the file was written.
+    /// - `Err(Vec<EnvarError>)`: every load error in declaration order, or a single `EnvarError::Io`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let path = std::env::temp_dir().join("persist_doc.env");
+    /// std::fs::write(&path, "# Written by the onboarding tool\nAPP_PORT=80\n").unwrap();
+    ///
+    /// std::env::set_var("APP_PORT", "8080");
+    ///
+    /// EnvarSet::with_prefix("APP_")
+    ///     .var(Envar::u16("PORT"))
+    ///     .var(Envar::string("GREETING").or_default("hello world"))
+    ///     .persist(&path)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     std::fs::read_to_string(&path).unwrap(),
+    ///     "# Written by the onboarding tool\nAPP_PORT=8080\nAPP_GREETING='hello world'\n",
+    /// );
+    /// ```
+    pub fn persist(&self, path: impl AsRef<Path>) -> Result<(), Vec<EnvarError>> {
+        let entries: Vec<(String, String)> = self.load_with(&ProcessEnv, Diagnostics::detect())?
+            .iter()
+            .map(|env_var| (format!("{}{}", self.prefix, env_var.key()), env_var.value_string()))
+            .collect();
+
+        crate::persist::update(path.as_ref(), &entries).map_err(|e| vec![e])
+    }
+
+    /// Loads every environment variable in the set and writes them to `path`
+    /// as a script that exports them when sourced in the given shell.
+    ///
+    /// Variables are keyed by the name they were looked up under, including any prefix,
+    /// and quoted as described in `LoadedEnvar::shell_line`.
+    /// Nothing is written unless every variable loads successfully.
+    /// Warnings, such as for a deprecated name, are printed as `cargo:warning` directives in a build script,
+    /// and to stderr otherwise, so they don't end up in piped output.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(())`: if the script was written.
+    /// - `Err(Vec<EnvarError>)`: every load error in declaration order, or a single `EnvarError::Io`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet, Shell};
+    ///
+    /// std::env::set_var("SHELL_DOC_HOST", "localhost");
+    ///
+    /// let path = std::env::temp_dir().join("write_shell_script_doc.sh");
+    ///
+    /// EnvarSet::new()
+    ///     .var(Envar::string("SHELL_DOC_HOST"))
+    ///     .write_shell_script(&path, Shell::Posix)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(std::fs::read_to_string(&path).unwrap(), "export SHELL_DOC_HOST='localhost'\n");
+    /// ```
+    pub fn write_shell_script(&self, path: impl AsRef<Path>, shell: Shell) -> Result<(), Vec<EnvarError>> {
+        let path = path.as_ref();
+
+        let contents: String = self.load_with(&ProcessEnv, Diagnostics::detect())?
+            .iter()
+            .map(|env_var| crate::manifest::shell_line(shell, &format!("{}{}", self.prefix, env_var.key()), &env_var.value_string()))
+            .collect();
+
+        std::fs::write(path, contents).map_err(|source| vec![EnvarError::Io { path: path.to_path_buf(), source }])
+    }
+
+    /// Writes a `.env` template listing every declared variable to `path`.
+    ///
+    /// Each variable is preceded by a comment with its description, expected type, whether it is optional,
+    /// its default, and its constraints. Defaults of secret variables are masked as `***`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let path = std::env::temp_dir().join("write_example_doc.env.example");
+    ///
+    /// EnvarSet::new()
+    ///     .var(Envar::string("HOST"))
+    ///     .var(Envar::u16("PORT").or_default(8080))
+    ///     .write_example(&path)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     std::fs::read_to_string(&path).unwrap(),
+    ///     "# string, required\nHOST=\n\n# u16, optional, default: 8080\nPORT=\n",
+    /// );
+    /// ```
+    pub fn write_example(&self, path: impl AsRef<Path>) -> Result<(), EnvarError> {
+        let path = path.as_ref();
+
+        let contents = self.env_vars.iter()
+            .map(|env_var| {
+                let mut comment = match env_var.description() {
+                    Some(description) => format!("# {}\n# {}", description, env_var.envar().type_name()),
+                    None => format!("# {}", env_var.envar().type_name()),
+                };
+
+                match (env_var.default(), env_var.load_level()) {
+                    (Some(_), _) if env_var.is_secret() => comment.push_str(", optional, default: ***"),
+                    (Some(default), _) => comment.push_str(&format!(", optional, default: {}", default)),
+                    (None, LoadLevel::Warn) => comment.push_str(", optional"),
+                    (None, LoadLevel::Required) => comment.push_str(", required"),
+                }
+
+                for constraint in env_var.constraints() {
+                    comment.push_str(&format!("\n# {}", constraint.description()));
+                }
+
+                for alias in env_var.aliases() {
+                    comment.push_str(&format!("\n# formerly {}{}", self.prefix, alias));
+                }
+
+                if !env_var.fallbacks().is_empty() {
+                    comment.push_str(&format!("\n# falls back to {}", env_var.fallbacks().join(", ")));
+                }
+
+                if env_var.default().is_none() && env_var.load_level() == LoadLevel::Required {
+                    for condition in env_var.conditions() {
+                        comment.push_str(&format!("\n# required only with {}", condition));
+                    }
+                }
+
+                format!("{}\n{}{}=\n", comment, self.prefix, env_var.key())
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        std::fs::write(path, contents).map_err(|source| EnvarError::Io { path: path.to_path_buf(), source })
+    }
+
+    /// Writes a Markdown table documenting every declared variable to `path`,
+    /// suitable for committing alongside a README.
+    ///
+    /// Each row lists the variable's name, expected type, whether it is required, its default,
+    /// its constraints, and its description. Defaults of secret variables are masked as `***`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let path = std::env::temp_dir().join("write_markdown_docs_doc.md");
+    ///
+    /// EnvarSet::with_prefix("APP_")
+    ///     .var(Envar::string("HOST").describe("the public host name"))
+    ///     .var(Envar::u16("PORT").or_default(8080).range(1024..=65535))
+    ///     .write_markdown_docs(&path)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     std::fs::read_to_string(&path).unwrap(),
+    ///     "| Variable | Type | Required | Default | Constraints | Description |\n\
+    ///      | --- | --- | --- | --- | --- | --- |\n\
+    ///      | `APP_HOST` | string | yes |  |  | the public host name |\n\
+    ///      | `APP_PORT` | u16 | no | `8080` | must be in range 1024..=65535 |  |\n",
+    /// );
+    /// ```
+    pub fn write_markdown_docs(&self, path: impl AsRef<Path>) -> Result<(), EnvarError> {
+        let path = path.as_ref();
+
+        let mut contents = String::from("| Variable | Type | Required | Default | Constraints | Description |\n");
+        contents.push_str("| --- | --- | --- | --- | --- | --- |\n");
+
+        for env_var in &self.env_vars {
+            let mut name = format!("`{}{}`", self.prefix, env_var.key());
+
+            for alias in env_var.aliases() {
+                name.push_str(&format!(" (formerly `{}{}`)", self.prefix, alias));
+            }
+
+            let required = match (env_var.default(), env_var.load_level()) {
+                (None, LoadLevel::Required) if !env_var.conditions().is_empty() => {
+                    let conditions: Vec<String> = env_var.conditions().iter().map(ToString::to_string).collect();
+                    format!("with {}", conditions.join(" and "))
+                },
+                (None, LoadLevel::Required) => "yes".to_string(),
+                _ => "no".to_string(),
+            };
+
+            let default = match env_var.default() {
+                Some(_) if env_var.is_secret() => "`***`".to_string(),
+                Some(default) => format!("`{}`", default),
+                None => String::new(),
+            };
+
+            let mut constraints: Vec<String> = env_var.constraints().iter().map(|constraint| constraint.description().to_string()).collect();
+
+            if !env_var.fallbacks().is_empty() {
+                constraints.push(format!("falls back to {}", env_var.fallbacks().join(", ")));
+            }
+
+            let cells = [
+                name,
+                env_var.envar().type_name(),
+                required,
+                default,
+                constraints.join("; "),
+                env_var.description().unwrap_or_default().to_string(),
+            ];
+
+            let cells: Vec<String> = cells.iter().map(|cell| cell.replace('|', "\\|")).collect();
+            contents.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+
+        std::fs::write(path, contents).map_err(|source| EnvarError::Io { path: path.to_path_buf(), source })
+    }
+
+    /// Returns a command-line argument for every declared variable, to add to a `clap::Command`.
+    ///
+    /// Each argument is named after its variable, so `DATABASE_URL` becomes `--database-url`,
+    /// and its help text holds the description and the variable it overrides.
+    /// A boolean can also be passed as a bare flag, such as `--secure`, meaning `true`.
+    /// Pass the resulting matches to `load_with_matches`.
+    ///
+    /// Requires the `clap` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// std::env::set_var("CLAP_DOC_PORT", "8080");
+    /// std::env::set_var("CLAP_DOC_HOST", "localhost");
+    ///
+    /// let set = EnvarSet::with_prefix("CLAP_DOC_")
+    ///     .var(Envar::string("HOST"))
+    ///     .var(Envar::u16("PORT"))
+    ///     .var(Envar::bool("SECURE").or_default(false));
+    ///
+    /// let matches = clap::Command::new("app")
+    ///     .args(set.clap_args())
+    ///     .try_get_matches_from(["app", "--port", "9000", "--secure"])
+    ///     .unwrap();
+    ///
+    /// let config = set.load_with_matches(&matches).unwrap();
+    ///
+    /// assert_eq!(config.get_as::<u16>("PORT"), Some(9000));
+    /// assert_eq!(config.get_as::<String>("HOST").as_deref(), Some("localhost"));
+    /// assert_eq!(config.get_as::<bool>("SECURE"), Some(true));
+    /// ```
+    #[cfg(feature = "clap")]
+    pub fn clap_args(&self) -> Vec<clap::Arg> {
+        self.env_vars.iter().map(|env_var| crate::cli::arg(env_var, &self.prefix)).collect()
+    }
+
+    /// Loads like `load_runtime`, but values passed on the command line take precedence
+    /// over the environment.
+    ///
+    /// `matches` must come from a command built with `clap_args`. See `clap_args` for an example.
+    /// Requires the `clap` feature.
+    #[cfg(feature = "clap")]
+    pub fn load_with_matches(&self, matches: &clap::ArgMatches) -> Result<EnvarConfig<'a>, Vec<EnvarError>> {
+        let sources = crate::SourceStack::new()
+            .with(crate::cli::overrides(self, matches))
+            .with(ProcessEnv);
+
+        self.load_with(&sources, Diagnostics::Stderr).map(EnvarConfig::from_iter)
+    }
+
+    /// Returns a JSON Schema document describing every declared variable.
+    ///
+    /// The schema describes an object whose properties are the variable names, including any prefix.
+    /// As environment variables are strings, every property is a string schema, narrowed with `enum`
+    /// or `pattern` where possible. Each property also carries its description, its default
+    /// (unless secret), the expected type as `x-type`, and its constraints as `x-constraints`.
+    /// Variables without a default are listed as `required`, unless marked with `LoadLevel::Warn`.
+    ///
+    /// Requires the `schema` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let schema = EnvarSet::new()
+    ///     .var(Envar::string("HOST").describe("The host to bind to"))
+    ///     .var(Envar::u16("PORT").or_default(8080))
+    ///     .to_json_schema();
+    ///
+    /// assert_eq!(schema["required"], serde_json::json!(["HOST"]));
+    /// assert_eq!(schema["properties"]["HOST"]["description"], "The host to bind to");
+    /// assert_eq!(schema["properties"]["PORT"]["default"], "8080");
+    /// assert_eq!(schema["properties"]["PORT"]["x-type"], "u16");
+    /// ```
+    #[cfg(feature = "schema")]
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        crate::schema::json_schema(self)
+    }
+
+    /// Returns whether some declared variable is looked up under `key`.
+    fn declares(&self, key: &str) -> bool {
+        if self.env_vars.iter().any(|env_var| env_var.fallbacks().iter().any(|fallback| fallback == key)) {
+            return true;
+        }
+
+        match key.strip_prefix(self.prefix.as_str()) {
+            Some(key) => self.env_vars.iter().any(|env_var| {
+                env_var.key() == key || env_var.aliases().iter().any(|alias| alias == key)
+            }),
+            None => false,
+        }
+    }
+
+    /// Loads every environment variable in the set and exports each of them with `LoadedEnvar::export`.
+    ///
+    /// Nothing is exported unless every variable loads successfully, and, with `SecretExport::Deny`
+    /// selected by `set_secret_export`, unless no variable is secret.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(Vec<LoadedEnvar>)`: the exported variables, in declaration order.
+    /// - `Err(Vec<EnvarError>)`: every error encountered, in declaration order,
+    ///   or an `EnvarError::SecretExported` for each secret variable.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// std::env::set_var("EXPORT_ALL_DOC_PORT", "8080");
+    ///
+    /// // Prints `cargo:rustc-env=EXPORT_ALL_DOC_PORT=8080`
+    /// EnvarSet::new()
+    ///     .var(Envar::u16("EXPORT_ALL_DOC_PORT"))
+    ///     .export_all()
+    ///     .unwrap();
+    /// ```
+    pub fn export_all(&self) -> Result<Vec<LoadedEnvar<'a>>, Vec<EnvarError>> {
+        let loaded = self.load()?;
+
+        if crate::directive::secret_export() == crate::SecretExport::Deny {
+            let errors: Vec<_> = loaded.iter()
+                .filter(|env_var| env_var.is_secret())
+                .map(|env_var| EnvarError::SecretExported { key: env_var.key().to_string() })
+                .collect();
+
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+
+        loaded.iter().for_each(LoadedEnvar::export);
+        Ok(loaded)
+    }
+
+    /// Loads every environment variable in the set and writes them to `out_dir/file_name`
+    /// as typed `pub const` items, ready to be pulled in with `include!`.
+    ///
+    /// Each constant is named after its variable. Numbers, `bool`, `char`, addresses,
+    /// and durations keep their types, lists become `&[&str]`, bytes become `&[u8]`,
+    /// and every other variable becomes a `&str`.
+    /// Nothing is written unless every variable loads successfully.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(PathBuf)`: the path of the written file.
+    /// - `Err(Vec<EnvarError>)`: every load error in declaration order, or a single `EnvarError::Io`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // build.rs
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// fn main() {
+    ///     easy_envar::init().unwrap();
+    ///
+    ///     // Writes `pub const HOST: &str = "localhost";` and `pub const PORT: u16 = 8080;`.
+    ///     EnvarSet::new()
+    ///         .var(Envar::string("HOST"))
+    ///         .var(Envar::u16("PORT"))
+    ///         .write_const_module(std::env::var("OUT_DIR").unwrap(), "env_consts.rs")
+    ///         .unwrap();
+    /// }
+    /// ```
+    ///
+    /// ***
+    ///
+    /// ```rust,ignore
+    /// // main.rs
+    ///
+    /// include!(concat!(env!("OUT_DIR"), "/env_consts.rs"));
+    ///
+    /// fn main() {
+    ///     let port: u16 = PORT;
+    /// }
+    /// ```
+    pub fn write_const_module(&self, out_dir: impl AsRef<Path>, file_name: &str) -> Result<PathBuf, Vec<EnvarError>> {
+        let loaded = self.load()?;
+        let path = out_dir.as_ref().join(file_name);
+
+        let contents: String = loaded.iter().map(crate::codegen::const_item).collect();
+
+        std::fs::write(&path, contents).map_err(|source| vec![EnvarError::Io { path: path.clone(), source }])?;
+
+        Ok(path)
+    }
+
+    /// Loads every environment variable in the set and writes `embedded-env.txt` to `OUT_DIR`,
+    /// listing the variables and values that `export_all` compiles into the binary, where `env!` reads them.
+    ///
+    /// Each variable is listed as `KEY=value`, with the value as it is passed to `cargo:rustc-env`,
+    /// so security review can audit what ships in release artifacts.
+    /// Secret values are masked as `***`, and marked as embedded or not, as selected by `set_secret_export`.
+    /// With `SecretExport::Deny`, `export_all` exports nothing if any variable is secret,
+    /// so every variable is then marked as not embedded.
+    /// Nothing is written unless every variable loads successfully.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(PathBuf)`: the path of the written report.
+    /// - `Err(Vec<EnvarError>)`: every load error in declaration order, an `EnvarError::Missing` for `OUT_DIR`
+    ///   outside of a build script, or a single `EnvarError::Io`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// std::env::set_var("OUT_DIR", std::env::temp_dir());
+    /// std::env::set_var("REPORT_DOC_PORT", "8080");
+    /// std::env::set_var("REPORT_DOC_TOKEN", "hunter2");
+    ///
+    /// let set = EnvarSet::new()
+    ///     .var(Envar::u16("REPORT_DOC_PORT"))
+    ///     .var(Envar::secret_string("REPORT_DOC_TOKEN"));
+    ///
+    /// let path = set.report_embedded().unwrap();
+    /// set.export_all().unwrap();
+    ///
+    /// let report = std::fs::read_to_string(path).unwrap();
+    ///
+    /// assert!(report.contains("REPORT_DOC_PORT=8080\n"));
+    /// assert!(report.contains("REPORT_DOC_TOKEN=***  # secret, embedded\n"));
+    /// assert!(!report.contains("hunter2"));
+    /// ```
+    ///
+    /// With `SecretExport::Deny`, a secret variable keeps the whole set from being exported:
+    ///
+    /// ```rust
+    /// // build.rs
+    /// use easy_envar::{Envar, EnvarSet, SecretExport};
+    ///
+    /// std::env::set_var("OUT_DIR", std::env::temp_dir().join("report_deny_doc"));
+    /// std::fs::create_dir_all(std::env::var("OUT_DIR").unwrap()).unwrap();
+    /// std::env::set_var("REPORT_DENY_DOC_PORT", "8080");
+    /// std::env::set_var("REPORT_DENY_DOC_TOKEN", "hunter2");
+    ///
+    /// easy_envar::set_secret_export(SecretExport::Deny);
+    ///
+    /// let set = EnvarSet::new()
+    ///     .var(Envar::u16("REPORT_DENY_DOC_PORT"))
+    ///     .var(Envar::secret_string("REPORT_DENY_DOC_TOKEN"));
+    ///
+    /// let path = set.report_embedded().unwrap();
+    /// assert!(set.export_all().is_err());
+    ///
+    /// let report = std::fs::read_to_string(path).unwrap();
+    ///
+    /// assert!(report.contains("REPORT_DENY_DOC_PORT=8080  # not embedded\n"));
+    /// assert!(report.contains("REPORT_DENY_DOC_TOKEN=***  # secret, not embedded\n"));
+    /// ```
+    pub fn report_embedded(&self) -> Result<PathBuf, Vec<EnvarError>> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+            let description = Some("set by Cargo for build scripts".to_string());
+            vec![EnvarError::Missing { key: "OUT_DIR".to_string(), description, suggestion: None }]
+        })?;
+
+        let loaded = self.load()?;
+        let path = Path::new(&out_dir).join("embedded-env.txt");
+
+        // Matches `export_all`, which exports nothing if any variable is secret under `SecretExport::Deny`.
+        let exported = crate::directive::secret_export() != crate::SecretExport::Deny || !loaded.iter().any(LoadedEnvar::is_secret);
+
+        let mut contents = String::from("# Environment variables compiled into the binary by `cargo:rustc-env`, and read with `env!`.\n");
+        for env_var in &loaded {
+            let line = match (env_var.is_secret(), exported) {
+                (true, true) => format!("{}=***  # secret, embedded\n", env_var.key()),
+                (true, false) => format!("{}=***  # secret, not embedded\n", env_var.key()),
+                (false, true) => format!("{}={}\n", env_var.key(), crate::directive::rustc_env_value(&env_var.value_string())),
+                (false, false) => format!("{}={}  # not embedded\n", env_var.key(), crate::directive::rustc_env_value(&env_var.value_string())),
+            };
+            contents.push_str(&line);
+        }
+
+        std::fs::write(&path, contents).map_err(|source| vec![EnvarError::Io { path: path.clone(), source }])?;
+
+        Ok(path)
+    }
+}
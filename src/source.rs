@@ -0,0 +1,441 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::dotenv::{DotenvWarning, Parsed, SyntaxError};
+use crate::spec::Diagnostics;
+use crate::{EnvarError, Origin};
+
+
+/// The `.env` files loaded into the process environment by `init` and `init_from_path`.
+static LOADED_DOTENV_FILES: Mutex<Vec<DotenvFile>> = Mutex::new(Vec::new());
+
+
+/// A place raw environment variable values are read from.
+///
+/// Implemented by `ProcessEnv`, `DotenvFile`, `SourceStack`,
+/// and `HashMap`s of strings, which are handy for defaults.
+pub trait Source {
+    /// Returns the raw value of `key`.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(String)`: if this source defines `key`.
+    /// - `Err(EnvarError::Missing)`: if this source doesn't define `key`.
+    /// - `Err(EnvarError)`: any other failure, such as `EnvarError::NotUnicode`.
+    fn var(&self, key: &str) -> Result<String, EnvarError>;
+
+    /// Returns the name of every variable this source defines, in no particular order.
+    ///
+    /// Only used to find differently-cased matches for `EnvarSpec::case_insensitive`.
+    /// The default implementation returns no names, which disables such matches.
+    fn names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns the file and line where `key` is defined, if this source knows it.
+    ///
+    /// Used to point errors about bad values at the line to fix.
+    /// The default implementation returns `None`.
+    fn location(&self, key: &str) -> Option<Location> {
+        let _ = key;
+        None
+    }
+
+    /// Returns where the value of `key` comes from, as reported by `LoadedEnvar::origin`.
+    ///
+    /// The default implementation returns `Origin::Dotenv` if `location` knows the line,
+    /// and `Origin::Other` otherwise.
+    fn origin(&self, key: &str) -> Origin {
+        match self.location(key) {
+            Some(location) => Origin::Dotenv(location),
+            None => Origin::Other,
+        }
+    }
+}
+
+
+impl<S: Source + ?Sized> Source for &S {
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        (**self).var(key)
+    }
+
+    fn names(&self) -> Vec<String> {
+        (**self).names()
+    }
+
+    fn location(&self, key: &str) -> Option<Location> {
+        (**self).location(key)
+    }
+
+    fn origin(&self, key: &str) -> Origin {
+        (**self).origin(key)
+    }
+}
+
+
+/// A line in a `.env` file where a variable is defined.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{DotenvFile, Source};
+///
+/// let path = std::env::temp_dir().join("location_doc.env");
+/// std::fs::write(&path, "# Server\nLOCATION_DOC_HOST=localhost\nLOCATION_DOC_PORT=8080\n").unwrap();
+///
+/// let location = DotenvFile::from_path(&path).unwrap().location("LOCATION_DOC_PORT").unwrap();
+///
+/// assert_eq!(location.path(), path);
+/// assert_eq!(location.line(), 3);
+/// assert_eq!(location.to_string(), format!("{}:3", path.display()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    path: Arc<Path>,
+    line: usize,
+}
+
+
+impl Location {
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the line number, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)
+    }
+}
+
+
+impl<K, V> Source for HashMap<K, V>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+{
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        match self.get(key) {
+            Some(val) => Ok(val.as_ref().to_string()),
+            None => Err(EnvarError::Missing { key: key.to_string(), description: None, suggestion: None }),
+        }
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.keys().map(|key| key.borrow().to_string()).collect()
+    }
+}
+
+
+/// The environment of the current process.
+///
+/// This is the source used by `load`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessEnv;
+
+
+impl Source for ProcessEnv {
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        crate::var(key)
+    }
+
+    fn names(&self) -> Vec<String> {
+        std::env::vars_os()
+            .filter_map(|(key, _)| key.into_string().ok())
+            .collect()
+    }
+
+    /// Returns where `key` is defined in a `.env` file loaded by `init` or `init_from_path`,
+    /// if the file's value is still the one in the environment.
+    fn location(&self, key: &str) -> Option<Location> {
+        let val = std::env::var(key).ok()?;
+        let files = LOADED_DOTENV_FILES.lock().unwrap_or_else(|e| e.into_inner());
+
+        files.iter()
+            .find(|file| file.entries.get(key) == Some(&val))
+            .and_then(|file| file.location(key))
+    }
+
+    fn origin(&self, key: &str) -> Origin {
+        match self.location(key) {
+            Some(location) => Origin::Dotenv(location),
+            None => Origin::ProcessEnv,
+        }
+    }
+}
+
+
+/// Reports a syntax error in a `.env` file.
+pub(crate) fn syntax_error(e: SyntaxError, path: Option<&Path>) -> EnvarError {
+    EnvarError::Syntax { path: path.map(Path::to_path_buf), line: e.line, column: e.column, message: e.message }
+}
+
+
+/// Remembers a `.env` file that was already read, such as a decrypted one.
+pub(crate) fn remember(file: DotenvFile) {
+    LOADED_DOTENV_FILES.lock().unwrap_or_else(|e| e.into_inner()).push(file);
+}
+
+
+/// The entries of a `.env` file, read without modifying the process environment.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{DotenvFile, Source};
+///
+/// let path = std::env::temp_dir().join("dotenv_file_doc.env");
+/// std::fs::write(&path, "DOTENV_FILE_DOC_PORT=8080\n").unwrap();
+///
+/// let file = DotenvFile::from_path(&path).unwrap();
+///
+/// assert_eq!(file.var("DOTENV_FILE_DOC_PORT").unwrap(), "8080");
+/// assert!(std::env::var("DOTENV_FILE_DOC_PORT").is_err());
+/// ```
+///
+/// Values keep `=` and unicode as they are, and keep `#` and line breaks when quoted:
+///
+/// ```rust
+/// use easy_envar::{DotenvFile, Source};
+///
+/// let path = std::env::temp_dir().join("dotenv_file_values_doc.env");
+/// std::fs::write(&path, "URL=postgres://db/app?sslmode=require\nCOLOR=\"#ff0000\"\nGREETING=héllo→🌍\nMOTD=\"line one\nline two\"\n").unwrap();
+///
+/// let file = DotenvFile::from_path(&path).unwrap();
+///
+/// assert_eq!(file.var("URL").unwrap(), "postgres://db/app?sslmode=require");
+/// assert_eq!(file.var("COLOR").unwrap(), "#ff0000");
+/// assert_eq!(file.var("GREETING").unwrap(), "héllo→🌍");
+/// assert_eq!(file.var("MOTD").unwrap(), "line one\nline two");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DotenvFile {
+    path: Option<Arc<Path>>,
+    keys: Vec<String>,
+    entries: HashMap<String, String>,
+    lines: HashMap<String, usize>,
+    warnings: Vec<DotenvWarning>,
+}
+
+
+impl DotenvFile {
+    /// Reads the `.env` file, found the same way as `init`:
+    /// in the current directory, or the closest of its ancestors that has one.
+    pub fn find() -> Result<Self, EnvarError> {
+        let dir = std::env::current_dir().map_err(|source| EnvarError::DotenvError { path: PathBuf::from(".env"), source })?;
+
+        match dir.ancestors().map(|dir| dir.join(".env")).find(|path| path.is_file()) {
+            Some(path) => Self::from_path(path),
+            None => Err(EnvarError::DotenvError {
+                path: dir.join(".env"),
+                source: std::io::Error::new(std::io::ErrorKind::NotFound, "not found in this directory or its ancestors"),
+            }),
+        }
+    }
+
+    /// Reads the `.env` file at the given path.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(DotenvFile)`: the entries of the file.
+    /// - `Err(EnvarError::DotenvError)`: if the file could not be read.
+    /// - `Err(EnvarError::Syntax)`: if the file is not valid, with the line and column of the first error.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{DotenvFile, EnvarError};
+    ///
+    /// let path = std::env::temp_dir().join("dotenv_syntax_doc.env");
+    /// std::fs::write(&path, "HOST=localhost\nGREETING=hello world\n").unwrap();
+    ///
+    /// let error = DotenvFile::from_path(&path).unwrap_err();
+    ///
+    /// assert!(matches!(error, EnvarError::Syntax { line: 2, column: 16, .. }));
+    /// assert!(error.to_string().ends_with("at line 2, column 16: unexpected `w` after a space; quote values that contain spaces"));
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, EnvarError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| EnvarError::DotenvError { path: path.to_path_buf(), source })?;
+        Self::from_contents(&contents, path)
+    }
+
+    /// Parses `.env` contents that were read from `path`, possibly in some other form, such as encrypted.
+    pub(crate) fn from_contents(contents: &str, path: &Path) -> Result<Self, EnvarError> {
+        let parsed = crate::dotenv::parse(contents).map_err(|e| syntax_error(e, Some(path)))?;
+        let mut file = Self::from_parsed(parsed);
+        file.path = Some(Arc::from(path));
+        Ok(file)
+    }
+
+    /// Collects the entries of a parsed `.env` file, which keeps the first definition of each key, like `init`.
+    fn from_parsed(parsed: Parsed) -> Self {
+        let mut file = DotenvFile { warnings: parsed.warnings, ..DotenvFile::default() };
+
+        for entry in parsed.entries {
+            file.keys.push(entry.key.clone());
+            file.lines.insert(entry.key.clone(), entry.line);
+            file.entries.insert(entry.key, entry.val);
+        }
+
+        file
+    }
+
+    /// Returns the suspicious lines found while reading the file, such as keys defined twice.
+    pub fn warnings(&self) -> &[DotenvWarning] {
+        &self.warnings
+    }
+
+    /// Sets every entry that isn't already set in the process environment,
+    /// printing the file's warnings as `cargo:warning` directives in a build script, or to stderr otherwise.
+    pub(crate) fn apply(&self) {
+        for key in &self.keys {
+            if std::env::var_os(key).is_none() {
+                std::env::set_var(key, &self.entries[key]);
+            }
+        }
+
+        let diagnostics = Diagnostics::detect();
+        for warning in &self.warnings {
+            match &self.path {
+                Some(path) => diagnostics.warn(format_args!("{}, {}", path.display(), warning)),
+                None => diagnostics.warn(format_args!("{}", warning)),
+            }
+        }
+    }
+
+    /// Returns the keys defined in the file, in file order.
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Returns the path the file was read from.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+
+impl Source for DotenvFile {
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        self.entries.var(key)
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.keys.clone()
+    }
+
+    fn location(&self, key: &str) -> Option<Location> {
+        Some(Location {
+            path: self.path.clone()?,
+            line: *self.lines.get(key)?,
+        })
+    }
+}
+
+
+/// Sources layered in explicit precedence order.
+///
+/// Each variable is read from the first source that defines it,
+/// so sources pushed first take precedence over those pushed later.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{DotenvFile, Envar, LoadedEnvar, ProcessEnv, SourceStack};
+/// use std::collections::HashMap;
+///
+/// let path = std::env::temp_dir().join("source_stack_doc.env");
+/// std::fs::write(&path, "SOURCE_STACK_DOC_HOST=example.com\nSOURCE_STACK_DOC_PORT=80\n").unwrap();
+///
+/// std::env::set_var("SOURCE_STACK_DOC_PORT", "8080");
+///
+/// // The process environment always wins, then `.env`, then the defaults.
+/// let sources = SourceStack::new()
+///     .with(ProcessEnv)
+///     .with(DotenvFile::from_path(&path).unwrap())
+///     .with(HashMap::from([("SOURCE_STACK_DOC_SECURE", "false")]));
+///
+/// let port = Envar::u16("SOURCE_STACK_DOC_PORT").load_from(&sources).unwrap();
+/// let secure = Envar::bool("SOURCE_STACK_DOC_SECURE").load_from(&sources).unwrap();
+///
+/// assert!(matches!(port, LoadedEnvar::U16(_, 8080)));
+/// assert!(matches!(secure, LoadedEnvar::Bool(_, false)));
+/// ```
+#[derive(Default)]
+pub struct SourceStack {
+    sources: Vec<Box<dyn Source>>,
+}
+
+
+impl SourceStack {
+    /// Creates an empty stack, in which every variable is missing.
+    pub fn new() -> Self {
+        SourceStack::default()
+    }
+
+    /// Adds a source with lower precedence than every source already in the stack.
+    pub fn with(mut self, source: impl Source + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Returns the first source that defines `key`, which is the one its value is read from.
+    fn defining(&self, key: &str) -> Option<&dyn Source> {
+        self.sources.iter()
+            .map(|source| source.as_ref())
+            .find(|source| !matches!(source.var(key), Err(EnvarError::Missing { .. })))
+    }
+}
+
+
+impl std::fmt::Debug for SourceStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceStack").field("len", &self.sources.len()).finish()
+    }
+}
+
+
+impl Source for SourceStack {
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        for source in &self.sources {
+            match source.var(key) {
+                Err(EnvarError::Missing { .. }) => continue,
+                raw => return raw,
+            }
+        }
+
+        Err(EnvarError::Missing { key: key.to_string(), description: None, suggestion: None })
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.sources.iter().flat_map(|source| source.names()).collect()
+    }
+
+    fn location(&self, key: &str) -> Option<Location> {
+        self.defining(key)?.location(key)
+    }
+
+    fn origin(&self, key: &str) -> Origin {
+        match self.defining(key) {
+            Some(source) => source.origin(key),
+            None => Origin::Other,
+        }
+    }
+}
@@ -0,0 +1,1351 @@
+use std::borrow::Cow;
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+
+use crate::condition::{Condition, Profile};
+use crate::{Envar, EnvarError, LoadedEnvar, NumberFormat, Origin, ProcessEnv, Secret, Source};
+
+
+/// An `Envar` together with the options that control how it is loaded.
+///
+/// Created by calling an option method such as `or_default` on an `Envar`,
+/// or by converting an `Envar` with `EnvarSpec::from`.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{Envar, EnvarSpec};
+///
+/// let spec: EnvarSpec = Envar::u16("PORT").or_default(8080);
+/// let plain: EnvarSpec = Envar::string("HOST").into();
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvarSpec<'a> {
+    envar: Envar<'a>,
+    default: Option<String>,
+    profile_defaults: Vec<(Profile, String)>,
+    constraints: Vec<Constraint>,
+    transforms: Vec<Transform>,
+    /// The level set on this variable, if any, or else `LoadLevel::Required`.
+    level: Option<LoadLevel>,
+    conditions: Vec<Condition>,
+    secret: bool,
+    expand: bool,
+    description: Option<String>,
+    aliases: Vec<String>,
+    fallbacks: Vec<Cow<'a, str>>,
+    case_insensitive: bool,
+    trim: Option<bool>,
+    number_format: NumberFormat,
+    #[cfg(feature = "remote")]
+    remote: Option<crate::remote::Remote>,
+}
+
+
+/// Controls how a missing environment variable is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadLevel {
+    /// A missing variable without a default is an error.
+    #[default]
+    Required,
+
+    /// A missing variable prints a `cargo:warning` instead of failing the build.
+    ///
+    /// If a default was given, it is used. Otherwise, `EnvarSet` and `load_all`
+    /// skip the variable, while loading it on its own still returns `EnvarError::Missing`.
+    Warn,
+}
+
+
+/// Where warnings raised while loading are printed.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Diagnostics {
+    /// As `cargo:warning` directives, for build scripts.
+    Cargo,
+
+    /// To stderr, for programs loading their configuration at runtime.
+    Stderr,
+
+    /// Nowhere, for inspecting the environment without side effects.
+    Silent,
+}
+
+
+impl Diagnostics {
+    /// Returns `Cargo` when running in a build script, which Cargo runs with `OUT_DIR` and `TARGET` set,
+    /// or else `Stderr`.
+    pub(crate) fn detect() -> Self {
+        if std::env::var_os("OUT_DIR").is_some() && std::env::var_os("TARGET").is_some() {
+            Diagnostics::Cargo
+        } else {
+            Diagnostics::Stderr
+        }
+    }
+
+    /// Prints a warning to this target.
+    pub(crate) fn warn(self, message: std::fmt::Arguments) {
+        match self {
+            Diagnostics::Cargo => println!("{}warning={}", crate::directive::prefix(), message),
+            Diagnostics::Stderr => eprintln!("warning: {}", message),
+            Diagnostics::Silent => {},
+        }
+    }
+}
+
+
+/// A function checking a value, given as written and as loaded, returning why it is invalid if it is.
+type Check = dyn Fn(&str, &LoadedEnvar<'_>) -> Result<(), String> + Send + Sync;
+
+
+/// A check applied to a value after it has been parsed.
+#[derive(Clone)]
+pub struct Constraint {
+    description: String,
+    check: Arc<Check>,
+}
+
+
+impl Constraint {
+    /// Creates a constraint that reports its description when `check` returns `false`.
+    fn new(description: String, check: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        let reported = description.clone();
+
+        Constraint {
+            description,
+            check: Arc::new(move |raw, _| if check(raw) { Ok(()) } else { Err(reported.clone()) }),
+        }
+    }
+
+    /// Creates a constraint like `new`, but checking the loaded value rather than the raw one.
+    fn loaded(description: String, check: impl Fn(&LoadedEnvar) -> bool + Send + Sync + 'static) -> Self {
+        let reported = description.clone();
+
+        Constraint {
+            description,
+            check: Arc::new(move |_, loaded| if check(loaded) { Ok(()) } else { Err(reported.clone()) }),
+        }
+    }
+
+    /// Returns a human-readable description of the constraint, such as `must be in range 1..=10`.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+
+/// A type whose values `EnvarSpec::range` can bound: the primitive numbers, and `Duration` for `Envar::Duration`.
+///
+/// Numbers are compared with the loaded value, not the raw one, so a `ByteSize` is compared in bytes,
+/// a `Percent` as a fraction, and a number written as `1_000` with `number_format` as `1000`.
+pub trait RangeValue: PartialOrd + Send + Sync + Sized + 'static {
+    /// Returns the loaded value as `Self`, or `None` if it isn't one, such as a `Duration` bounded by integers.
+    fn from_loaded(loaded: &LoadedEnvar) -> Option<Self>;
+
+    /// Formats a bound in the description of the constraint, such as `1024` or `30s`.
+    fn describe(&self) -> String;
+}
+
+
+/// Implements `RangeValue` for number types, read from the value as it is exported.
+macro_rules! range_value {
+    ($($ty:ty),*) => {$(
+        impl RangeValue for $ty {
+            fn from_loaded(loaded: &LoadedEnvar) -> Option<Self> {
+                match loaded {
+                    LoadedEnvar::Duration(..) => None,
+                    _ => loaded.value_string().parse().ok(),
+                }
+            }
+
+            fn describe(&self) -> String {
+                self.to_string()
+            }
+        }
+    )*};
+}
+
+range_value!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+
+impl RangeValue for std::time::Duration {
+    fn from_loaded(loaded: &LoadedEnvar) -> Option<Self> {
+        match loaded {
+            LoadedEnvar::Duration(_, val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+
+impl std::fmt::Debug for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Constraint").field(&self.description).finish()
+    }
+}
+
+
+/// A function normalizing a value, added with `EnvarSpec::map`.
+#[derive(Clone)]
+struct Transform(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+
+impl std::fmt::Debug for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Transform")
+    }
+}
+
+
+impl<'a> EnvarSpec<'a> {
+    /// Makes this environment variable optional, falling back to `default` when it is not set.
+    ///
+    /// The default is parsed exactly like a value read from the environment,
+    /// so an invalid default is reported as `EnvarError::ParseError`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSpec, LoadedEnvar};
+    ///
+    /// let spec = EnvarSpec::from(Envar::string("SPEC_DOC_HOST")).or_default("localhost");
+    ///
+    /// assert!(matches!(spec.load().unwrap(), LoadedEnvar::String(_, host) if host == "localhost"));
+    /// ```
+    pub fn or_default(mut self, default: impl ToString) -> Self {
+        self.default = Some(default.to_string());
+        self
+    }
+
+    /// Falls back to `default` when the variable is not set and the crate is built with `profile`,
+    /// taking precedence over `or_default`. In other profiles, the variable is required unless it has another default.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar, Profile};
+    ///
+    /// // Defaults to `false` in debug builds, but must be set explicitly in release builds.
+    /// let spec = Envar::bool("PROFILE_DOC_SECURE").or_default_in(Profile::Debug, false);
+    ///
+    /// // Cargo sets `PROFILE` for build scripts.
+    /// std::env::set_var("PROFILE", "debug");
+    ///
+    /// assert!(matches!(spec.load().unwrap(), LoadedEnvar::Bool(_, false)));
+    ///
+    /// std::env::set_var("PROFILE", "release");
+    ///
+    /// assert!(spec.load().is_err());
+    /// ```
+    pub fn or_default_in(mut self, profile: Profile, default: impl ToString) -> Self {
+        self.profile_defaults.retain(|(set, _)| *set != profile);
+        self.profile_defaults.push((profile, default.to_string()));
+        self
+    }
+
+    /// Requires the parsed value to lie within `range`.
+    ///
+    /// The loaded value is compared as the range's element type, so use a float range (e.g. `0.0..=1.0`)
+    /// for float and `Percent` variables, a range of bytes for `ByteSize` variables,
+    /// and a `Duration` range for `Duration` variables. See `RangeValue`.
+    /// Violations are reported as `EnvarError::ConstraintViolation`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError};
+    ///
+    /// std::env::set_var("RANGE_DOC_PORT", "80");
+    ///
+    /// let result = Envar::u16("RANGE_DOC_PORT").range(1024..=65535).load();
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ConstraintViolation { .. })));
+    /// ```
+    ///
+    /// Units are parsed before the value is compared:
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easy_envar::Envar;
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let env = MockEnv::new().var("MAX_UPLOAD", "512KiB").var("TIMEOUT", "90s");
+    ///
+    /// assert!(Envar::byte_size("MAX_UPLOAD").range(1024u64..=1024 * 1024).load_from(&env).is_ok());
+    /// assert!(Envar::byte_size("MAX_UPLOAD").range(..=256 * 1024u64).load_from(&env).is_err());
+    ///
+    /// let timeout = Envar::duration("TIMEOUT").range(Duration::from_secs(1)..=Duration::from_secs(60));
+    ///
+    /// assert_eq!(
+    ///     timeout.load_from(&env).unwrap_err().to_string(),
+    ///     r#"environment variable `TIMEOUT` has value "90s", but must be in range 1s..=60s"#,
+    /// );
+    /// ```
+    pub fn range<T, R>(mut self, range: R) -> Self
+    where
+        T: RangeValue,
+        R: RangeBounds<T> + Send + Sync + 'static,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) | Bound::Excluded(start) => start.describe(),
+            Bound::Unbounded => String::new(),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => format!("={}", end.describe()),
+            Bound::Excluded(end) => end.describe(),
+            Bound::Unbounded => String::new(),
+        };
+
+        self.constraints.push(Constraint::loaded(
+            format!("must be in range {}..{}", start, end),
+            move |loaded| T::from_loaded(loaded).is_some_and(|val| range.contains(&val)),
+        ));
+        self
+    }
+
+    /// Requires `check` to accept the value, for formats easy-envar doesn't know about, such as cron expressions.
+    ///
+    /// `check` receives the raw string, trimmed as it is before the variable's own parsing, not the parsed value.
+    /// It may return the value it parsed, which is discarded; use `Envar::custom` to keep it. An error is reported as
+    /// `EnvarError::ConstraintViolation` with the error's message, so phrase it to follow "has value ..., but",
+    /// such as `must be a cron expression`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// fn check_cron(raw: &str) -> Result<(), String> {
+    ///     match raw.split_whitespace().count() {
+    ///         5 => Ok(()),
+    ///         n => Err(format!("must have 5 fields, not {}", n)),
+    ///     }
+    /// }
+    ///
+    /// let spec = Envar::string("CRON").validate(check_cron);
+    ///
+    /// assert!(spec.load_from(&MockEnv::new().var("CRON", "0 3 * * *")).is_ok());
+    ///
+    /// let error = spec.load_from(&MockEnv::new().var("CRON", "0 3 *")).unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), r#"environment variable `CRON` has value "0 3 *", but must have 5 fields, not 3"#);
+    /// ```
+    pub fn validate<T, E>(mut self, check: impl Fn(&str) -> Result<T, E> + Send + Sync + 'static) -> Self
+    where
+        E: std::fmt::Display,
+    {
+        self.constraints.push(Constraint {
+            description: "must pass a custom check".to_string(),
+            check: Arc::new(move |raw, _| check(raw).map(|_| ()).map_err(|e| e.to_string())),
+        });
+        self
+    }
+
+    /// Normalizes the value with `transform`, such as lowercasing a host name or stripping a URL's trailing slash,
+    /// so the loaded and exported value is already canonical.
+    ///
+    /// `transform` is called with the value once it has been parsed as the variable's type, and its result
+    /// is parsed again, so a transform that makes the value invalid is reported as `EnvarError::ParseError`.
+    /// Transforms run in the order they were added, and constraints check the transformed value.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let spec = Envar::string("API_URL")
+    ///     .map(|url| url.trim_end_matches('/').to_string())
+    ///     .map(|url| url.to_lowercase());
+    ///
+    /// let loaded = spec.load_from(&MockEnv::new().var("API_URL", "https://API.example.com/")).unwrap();
+    ///
+    /// assert_eq!(loaded.value_string(), "https://api.example.com");
+    /// ```
+    pub fn map(mut self, transform: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        self.transforms.push(Transform(Arc::new(transform)));
+        self
+    }
+
+    /// Requires the value to contain at least one non-whitespace character.
+    ///
+    /// Violations are reported as `EnvarError::ConstraintViolation`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError};
+    ///
+    /// std::env::set_var("NON_EMPTY_DOC_HOST", "  ");
+    ///
+    /// let result = Envar::string("NON_EMPTY_DOC_HOST").non_empty().load();
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ConstraintViolation { .. })));
+    /// ```
+    pub fn non_empty(mut self) -> Self {
+        self.constraints.push(Constraint::new(
+            "must not be empty".to_string(),
+            |raw| !raw.trim().is_empty(),
+        ));
+        self
+    }
+
+    /// Requires a boolean to be spelled exactly `true` or `false`,
+    /// rejecting the lenient spellings such as `yes` and `1` that are accepted by default.
+    ///
+    /// Violations are reported as `EnvarError::ConstraintViolation`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let env = MockEnv::new().var("SECURE", "Yes");
+    ///
+    /// assert!(matches!(Envar::bool("SECURE").load_from(&env).unwrap(), LoadedEnvar::Bool(_, true)));
+    ///
+    /// let result = Envar::bool("SECURE").strict_bool().load_from(&env);
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ConstraintViolation { .. })));
+    /// ```
+    pub fn strict_bool(mut self) -> Self {
+        self.constraints.push(Constraint::new(
+            "must be `true` or `false`".to_string(),
+            |raw| raw == "true" || raw == "false",
+        ));
+        self
+    }
+
+    /// Requires the value to be a path that exists.
+    ///
+    /// Relative paths are resolved against the current directory,
+    /// which is the package root when running a build script.
+    /// Violations are reported as `EnvarError::ConstraintViolation`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError};
+    ///
+    /// std::env::set_var("MUST_EXIST_DOC_CERT_FILE", "does/not/exist.pem");
+    ///
+    /// let result = Envar::path("MUST_EXIST_DOC_CERT_FILE").must_exist().load();
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ConstraintViolation { .. })));
+    /// ```
+    pub fn must_exist(mut self) -> Self {
+        self.constraints.push(Constraint::new(
+            "must be an existing path".to_string(),
+            |raw| std::path::Path::new(raw).exists(),
+        ));
+        self
+    }
+
+    /// Requires the value to be a path to an existing file.
+    ///
+    /// Violations are reported as `EnvarError::ConstraintViolation`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError};
+    ///
+    /// std::env::set_var("MUST_BE_FILE_DOC_CERT_FILE", "src");
+    ///
+    /// let result = Envar::path("MUST_BE_FILE_DOC_CERT_FILE").must_be_file().load();
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ConstraintViolation { .. })));
+    /// ```
+    pub fn must_be_file(mut self) -> Self {
+        self.constraints.push(Constraint::new(
+            "must be an existing file".to_string(),
+            |raw| std::path::Path::new(raw).is_file(),
+        ));
+        self
+    }
+
+    /// Requires the value to be a path to an existing directory.
+    ///
+    /// Violations are reported as `EnvarError::ConstraintViolation`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    ///
+    /// std::env::set_var("MUST_BE_DIR_DOC_CERT_DIR", "src");
+    ///
+    /// let loaded = Envar::path("MUST_BE_DIR_DOC_CERT_DIR").must_be_dir().load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::Path(_, path) if path.ends_with("src")));
+    /// ```
+    pub fn must_be_dir(mut self) -> Self {
+        self.constraints.push(Constraint::new(
+            "must be an existing directory".to_string(),
+            |raw| std::path::Path::new(raw).is_dir(),
+        ));
+        self
+    }
+
+    /// Requires the value to be a URL whose scheme is one of `schemes`.
+    ///
+    /// Schemes are compared without the trailing `://`, e.g. `postgres`.
+    /// Violations are reported as `EnvarError::ConstraintViolation`.
+    ///
+    /// Requires the `url` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError};
+    ///
+    /// std::env::set_var("SCHEMES_DOC_DATABASE_URL", "mysql://localhost/app");
+    ///
+    /// let result = Envar::url("SCHEMES_DOC_DATABASE_URL")
+    ///     .schemes(["postgres", "postgresql"])
+    ///     .load();
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ConstraintViolation { .. })));
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn schemes<I, S>(mut self, schemes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let schemes: Vec<String> = schemes.into_iter().map(Into::into).collect();
+
+        self.constraints.push(Constraint::new(
+            format!("must use one of the schemes: {}", schemes.join(", ")),
+            move |raw| match raw.parse::<url::Url>() {
+                Ok(url) => schemes.iter().any(|scheme| scheme == url.scheme()),
+                Err(_) => false,
+            },
+        ));
+        self
+    }
+
+    /// Sets how a missing value is handled.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet, LoadLevel};
+    ///
+    /// // Prints `cargo:warning=LEVEL_DOC_TELEMETRY_KEY not set, skipping`.
+    /// let loaded = EnvarSet::new()
+    ///     .var(Envar::string("LEVEL_DOC_TELEMETRY_KEY").level(LoadLevel::Warn))
+    ///     .load()
+    ///     .unwrap();
+    ///
+    /// assert!(loaded.is_empty());
+    /// ```
+    pub fn level(mut self, level: LoadLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Shorthand for `level(LoadLevel::Warn)`.
+    pub fn warn_if_missing(self) -> Self {
+        self.level(LoadLevel::Warn)
+    }
+
+    /// Requires this variable only when `feature` is enabled in the crate whose build script loads it,
+    /// as reported by the `CARGO_FEATURE_<NAME>` variable Cargo sets.
+    ///
+    /// When the feature is disabled, `EnvarSet` and `load_all` silently skip the variable if it is missing,
+    /// while loading it on its own still returns `EnvarError::Missing`. A value that is set is always validated.
+    /// Calling this several times requires the variable only when every feature is enabled.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// // Cargo sets `CARGO_FEATURE_TELEMETRY` for build scripts when the `telemetry` feature is enabled.
+    /// let set = EnvarSet::new().var(Envar::string("FEATURE_DOC_SENTRY_DSN").required_if_feature("telemetry"));
+    ///
+    /// assert!(set.load().unwrap().is_empty());
+    ///
+    /// std::env::set_var("CARGO_FEATURE_TELEMETRY", "1");
+    ///
+    /// assert!(set.load().is_err());
+    /// ```
+    pub fn required_if_feature(self, feature: impl Into<String>) -> Self {
+        self.required_if(Condition::Feature(feature.into()))
+    }
+
+    /// Requires this variable only when building for a target whose `cfg` option `name` is `value`,
+    /// such as `required_on("target_os", "linux")` for `cfg(target_os = "linux")`,
+    /// as reported by the `CARGO_CFG_<NAME>` variables Cargo sets for build scripts.
+    ///
+    /// These describe the target being compiled for, not the host running the build script,
+    /// so cross-compiling doesn't demand configuration only the host platform needs.
+    /// Otherwise behaves like `required_if_feature`, and combines with it: the variable is required
+    /// only when every condition holds.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// let set = EnvarSet::new().var(Envar::path("TARGET_DOC_SYSTEMD_DIR").required_on("target_os", "linux"));
+    ///
+    /// // Cargo sets `CARGO_CFG_TARGET_OS` for build scripts.
+    /// std::env::set_var("CARGO_CFG_TARGET_OS", "windows");
+    ///
+    /// assert!(set.load().unwrap().is_empty());
+    ///
+    /// std::env::set_var("CARGO_CFG_TARGET_OS", "linux");
+    ///
+    /// assert!(set.load().is_err());
+    /// ```
+    pub fn required_on(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.required_if(Condition::Cfg(name.into(), value.into()))
+    }
+
+    /// Requires this variable only when the crate is built with `profile`,
+    /// such as a signing key only release builds need. Otherwise behaves like `required_if_feature`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarSet, Profile};
+    ///
+    /// let set = EnvarSet::new().var(Envar::string("PROFILE_DOC_SIGNING_KEY").required_in(Profile::Release));
+    ///
+    /// std::env::set_var("PROFILE", "debug");
+    ///
+    /// assert!(set.load().unwrap().is_empty());
+    /// ```
+    pub fn required_in(self, profile: Profile) -> Self {
+        self.required_if(Condition::Profile(profile))
+    }
+
+    /// Attaches a human-readable description, used in errors, audit reports,
+    /// generated templates, and schemas.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::Envar;
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let spec = Envar::u16("PORT").describe("The port the server listens on");
+    ///
+    /// assert_eq!(spec.description(), Some("The port the server listens on"));
+    ///
+    /// let error = spec.load_from(&MockEnv::new()).unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "environment variable `PORT` (The port the server listens on) is not set");
+    /// ```
+    pub fn describe(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Marks this environment variable as secret.
+    ///
+    /// The value is still validated as the declared type and exported as-is. A `String` loads as `LoadedEnvar::Secret`,
+    /// and a value of any other type keeps its type and is marked secret (see `LoadedEnvar::is_secret`),
+    /// so either way it is masked in `Debug` and `Display` output. Values reported in errors are masked as `***` too.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError, LoadedEnvar};
+    ///
+    /// std::env::set_var("SECRET_SPEC_DOC_PORT", "8080");
+    ///
+    /// let loaded = Envar::u16("SECRET_SPEC_DOC_PORT").secret().load().unwrap();
+    ///
+    /// assert!(matches!(loaded, LoadedEnvar::U16(_, 8080)));
+    /// assert!(loaded.is_secret());
+    ///
+    /// std::env::set_var("SECRET_SPEC_DOC_PORT", "hunter2");
+    ///
+    /// let result = Envar::u16("SECRET_SPEC_DOC_PORT").secret().load();
+    ///
+    /// assert!(matches!(result, Err(EnvarError::ParseError { raw, .. }) if raw == "***"));
+    /// ```
+    pub fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+
+    /// Expands `${NAME}` references in the value with the values of other variables,
+    /// such as `DATABASE_URL=postgres://${DB_USER}:${DB_PASS}@${DB_HOST}/app`.
+    ///
+    /// References are read from the same source as the value, by their exact names, and are expanded in turn.
+    /// Defaults are expanded too. `$${` stands for a literal `${`, and a `$` without braces is kept as it is,
+    /// so values such as passwords containing `$` load unchanged.
+    ///
+    /// A reference to a variable that isn't set is reported as `EnvarError::UndefinedReference`,
+    /// and a variable that refers back to itself, directly or not, as `EnvarError::ReferenceCycle`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, EnvarError};
+    ///
+    /// std::env::set_var("EXPAND_DOC_DATABASE_URL", "postgres://${EXPAND_DOC_DB_USER}@${EXPAND_DOC_DB_HOST}/app");
+    /// std::env::set_var("EXPAND_DOC_DB_USER", "app");
+    /// std::env::set_var("EXPAND_DOC_DB_HOST", "${EXPAND_DOC_DB_REGION}.db.internal");
+    /// std::env::set_var("EXPAND_DOC_DB_REGION", "eu");
+    ///
+    /// let loaded = Envar::string("EXPAND_DOC_DATABASE_URL").expand().load().unwrap();
+    ///
+    /// assert_eq!(loaded.value_string(), "postgres://app@eu.db.internal/app");
+    ///
+    /// std::env::set_var("EXPAND_DOC_A", "${EXPAND_DOC_B}");
+    /// std::env::set_var("EXPAND_DOC_B", "x${EXPAND_DOC_A}");
+    ///
+    /// let error = Envar::string("EXPAND_DOC_A").expand().load().unwrap_err();
+    ///
+    /// assert!(matches!(&error, EnvarError::ReferenceCycle { cycle, .. } if cycle == &["EXPAND_DOC_A", "EXPAND_DOC_B", "EXPAND_DOC_A"]));
+    /// assert_eq!(error.to_string(), "environment variable `EXPAND_DOC_A` refers back to itself: EXPAND_DOC_A -> EXPAND_DOC_B -> EXPAND_DOC_A");
+    ///
+    /// std::env::set_var("EXPAND_DOC_C", "${EXPAND_DOC_MISSING}");
+    ///
+    /// let error = Envar::string("EXPAND_DOC_C").expand().load().unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "environment variable `EXPAND_DOC_C` refers to `${EXPAND_DOC_MISSING}`, which is not set");
+    /// ```
+    pub fn expand(mut self) -> Self {
+        self.expand = true;
+        self
+    }
+
+    /// Also loads the value from `alias`, a deprecated former name of this variable.
+    ///
+    /// The current name takes precedence. When only the alias is set, its value is used,
+    /// and a `cargo:warning` asks for it to be renamed. Aliases are tried in the order they were added,
+    /// and are looked up under the same prefix as the variable itself.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let env = MockEnv::new().var("SVC_PORT", "8080");
+    ///
+    /// // Prints `cargo:warning=SVC_PORT is deprecated, rename it to PORT`.
+    /// let loaded = Envar::u16("PORT").deprecated_alias("SVC_PORT").load_from(&env).unwrap();
+    ///
+    /// assert!(matches!(&loaded, LoadedEnvar::U16(key, 8080) if key == "PORT"));
+    /// ```
+    pub fn deprecated_alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Falls back to `key` when the variable is not set, such as a name a hosting platform provides.
+    ///
+    /// Fallback keys are tried in the order they were added, after the variable's own name and its
+    /// deprecated aliases. Unlike those, they are looked up as-is, without the `EnvarSet` prefix.
+    /// Errors report the key that was actually read, and `matched_key` tells which one that is.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let env = MockEnv::new().var("DB_URL", "postgres://localhost/app");
+    ///
+    /// let spec = Envar::string("DATABASE_URL").or_env("POSTGRES_URL").or_env("DB_URL");
+    /// let loaded = spec.load_from(&env).unwrap();
+    ///
+    /// assert_eq!(loaded.key(), "DATABASE_URL");
+    /// assert_eq!(loaded.as_str(), Some("postgres://localhost/app"));
+    /// assert_eq!(spec.matched_key(&env).as_deref(), Some("DB_URL"));
+    /// ```
+    pub fn or_env(mut self, key: impl Into<Cow<'a, str>>) -> Self {
+        self.fallbacks.push(key.into());
+        self
+    }
+
+    /// Accepts integers spelled in `format`, such as `1_000_000` or `0x1F`, instead of only plain decimals.
+    ///
+    /// The value is rewritten as a plain decimal before it is parsed and checked against constraints,
+    /// so `range` applies to the number it denotes. Has no effect on non-integer variables.
+    ///
+    /// See `NumberFormat` for an example.
+    pub fn number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = format;
+        self
+    }
+
+    /// Controls whether the value is normalized before it is parsed and checked against constraints.
+    ///
+    /// Normalizing trims surrounding whitespace, then strips one pair of matching single
+    /// or double quotes, so values copied from shell exports such as `" 8080 "` or `'true'` still parse.
+    /// It is on by default for booleans and numbers, and off for every other type.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let env = MockEnv::new().var("PORT", " '8080' ").var("HOST", " \"localhost\" ");
+    ///
+    /// assert!(matches!(Envar::u16("PORT").load_from(&env).unwrap(), LoadedEnvar::U16(_, 8080)));
+    /// assert!(Envar::u16("PORT").trim(false).load_from(&env).is_err());
+    ///
+    /// let host = Envar::string("HOST").trim(true).load_from(&env).unwrap();
+    ///
+    /// assert_eq!(host.as_str(), Some("localhost"));
+    /// ```
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = Some(trim);
+        self
+    }
+
+    /// Also matches names that differ only in ASCII case, as on Windows.
+    ///
+    /// An exact match always takes precedence. When only a differently-cased name is set,
+    /// its value is used, and a `cargo:warning` points out the mismatch, since the same
+    /// name would not match on a case-sensitive platform such as Linux.
+    ///
+    /// Differently-cased names are found through `Source::names`.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easy_envar::{Envar, LoadedEnvar};
+    /// use easy_envar::testing::MockEnv;
+    ///
+    /// let env = MockEnv::new().var("Port", "8080");
+    ///
+    /// // Prints `cargo:warning=PORT not set, using Port instead`.
+    /// let loaded = Envar::u16("PORT").case_insensitive().load_from(&env).unwrap();
+    ///
+    /// assert!(matches!(&loaded, LoadedEnvar::U16(key, 8080) if key == "PORT"));
+    /// assert!(Envar::u16("PORT").load_from(&env).is_err());
+    /// ```
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Fetches the value from AWS Secrets Manager when the variable is not set.
+    ///
+    /// `secret_id` is the secret's name or ARN, and its string value is used as the raw value.
+    /// A value set in the environment or a `.env` file takes precedence, and isn't fetched at all.
+    /// When a set is loaded, every such secret is fetched up front, twenty per request,
+    /// and fetched values are cached for the rest of the process.
+    ///
+    /// Secrets are fetched with the `aws` command (`aws secretsmanager batch-get-secret-value`),
+    /// so it must be installed, and it finds credentials and the region as usual.
+    /// Without it, loading fails with `RemoteErrorKind::Unavailable` and a hint to install the AWS CLI.
+    /// A secret that can't be fetched fails to load with `EnvarError::Remote`, even if a default is declared.
+    /// Requires the `aws` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// EnvarSet::new()
+    ///     .var(Envar::secret_string("DB_PASSWORD").from_aws("prod/db/password"))
+    ///     .var(Envar::secret_string("STRIPE_KEY").from_aws("prod/stripe/key"))
+    ///     .load()
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "aws")]
+    pub fn from_aws(mut self, secret_id: impl Into<String>) -> Self {
+        self.remote = Some(crate::remote::Remote::new(crate::remote::Backend::Aws, secret_id));
+        self
+    }
+
+    /// Fetches the value from a field of a HashiCorp Vault KV v2 secret when the variable is not set.
+    ///
+    /// `path` includes the mount, as in `vault kv get secret/app/db`. A value set in the environment
+    /// or a `.env` file takes precedence, and isn't fetched at all. When a set is loaded, each secret path
+    /// is read once for all of its fields, and fetched values are cached for the rest of the process.
+    ///
+    /// Secrets are read with the `vault` command from the server in `VAULT_ADDR`, so it must be installed,
+    /// or loading fails with `RemoteErrorKind::Unavailable`.
+    /// It authenticates with `VAULT_TOKEN`, or else logs in with AppRole using `VAULT_ROLE_ID` and `VAULT_SECRET_ID`.
+    /// A secret that can't be fetched fails to load with `EnvarError::Remote`, whose `RemoteErrorKind`
+    /// tells an authentication failure (`Unauthorized`) from a missing path or field (`NotFound`).
+    /// Requires the `vault` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use easy_envar::{Envar, EnvarError, EnvarSet, RemoteErrorKind};
+    ///
+    /// let result = EnvarSet::new()
+    ///     .var(Envar::string("DB_USER").from_vault("secret/app/db", "username"))
+    ///     .var(Envar::secret_string("DB_PASSWORD").from_vault("secret/app/db", "password"))
+    ///     .load();
+    ///
+    /// if let Err(errors) = result {
+    ///     for error in errors {
+    ///         if let EnvarError::Remote { kind: RemoteErrorKind::Unauthorized, .. } = error {
+    ///             eprintln!("log in to Vault first: {}", error);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "vault")]
+    pub fn from_vault(mut self, path: impl std::fmt::Display, field: impl std::fmt::Display) -> Self {
+        let id = format!("{}#{}", path, field);
+        self.remote = Some(crate::remote::Remote::new(crate::remote::Backend::Vault, id));
+        self
+    }
+
+    /// Fetches the value from Google Cloud Secret Manager when the variable is not set.
+    ///
+    /// `secret` is a secret name in the project configured for `gcloud`, or a full resource name
+    /// such as `projects/my-project/secrets/db-password/versions/3`. The latest version is read unless one is given.
+    /// Secrets are read with the `gcloud` command, one call per secret, and otherwise behave like `from_aws`.
+    /// Requires the `gcp` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use easy_envar::Envar;
+    ///
+    /// let password = Envar::secret_string("DB_PASSWORD").from_gcp("projects/my-project/secrets/db-password").load().unwrap();
+    /// ```
+    #[cfg(feature = "gcp")]
+    pub fn from_gcp(mut self, secret: impl Into<String>) -> Self {
+        self.remote = Some(crate::remote::Remote::new(crate::remote::Backend::Gcp, secret));
+        self
+    }
+
+    /// Fetches the value from Azure Key Vault when the variable is not set.
+    ///
+    /// `secret` is `vault/name`, or a secret name in the key vault named by `EASY_ENVAR_AZURE_VAULT`.
+    /// Secrets are read with the `az` command, one call per secret, and otherwise behave like `from_aws`.
+    /// Requires the `azure` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use easy_envar::Envar;
+    ///
+    /// let password = Envar::secret_string("DB_PASSWORD").from_azure("my-vault/db-password").load().unwrap();
+    /// ```
+    #[cfg(feature = "azure")]
+    pub fn from_azure(mut self, secret: impl Into<String>) -> Self {
+        self.remote = Some(crate::remote::Remote::new(crate::remote::Backend::Azure, secret));
+        self
+    }
+
+    /// Reads the value from the OS keyring when the variable is not set,
+    /// so developers don't have to keep plaintext API keys in `.env` files on their laptops.
+    ///
+    /// The password is stored under `service` (usually the application's name), with the variable's
+    /// declared name, without any prefix, as the user. The keyring is the macOS Keychain,
+    /// the Windows Credential Manager, or the Secret Service (GNOME Keyring, KWallet) on Linux.
+    /// A value set in the environment or a `.env` file takes precedence. Without a keyring entry,
+    /// the variable is missing as usual, so a default or `LoadLevel::Warn` still applies.
+    /// Requires the `keyring` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // Stored once with `security add-generic-password -s my-app -a OPENAI_API_KEY -w`
+    /// // or `secret-tool store --label=OPENAI_API_KEY service my-app username OPENAI_API_KEY`.
+    /// use easy_envar::Envar;
+    ///
+    /// let api_key = Envar::secret_string("OPENAI_API_KEY").from_keyring("my-app").load().unwrap();
+    /// ```
+    #[cfg(feature = "keyring")]
+    pub fn from_keyring(mut self, service: impl std::fmt::Display) -> Self {
+        let id = format!("{}#{}", service, self.key());
+        self.remote = Some(crate::remote::Remote::new(crate::remote::Backend::Keyring, id));
+        self
+    }
+
+    /// Fetches the value from the secret store chosen per deployment when the variable is not set.
+    ///
+    /// The store is read from `EASY_ENVAR_SECRET_STORE` at load time: `aws`, `gcp`, or `azure`,
+    /// among the enabled features. The same declarations can then run on any cloud,
+    /// with `name` interpreted as by `from_aws`, `from_gcp`, or `from_azure`.
+    /// If `EASY_ENVAR_SECRET_STORE` names no enabled store, the variable fails to load with `EnvarError::Remote`.
+    /// Requires the `aws`, `gcp`, or `azure` feature.
+    ///
+    /// ***
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// // EASY_ENVAR_SECRET_STORE=gcp on Google Cloud, EASY_ENVAR_SECRET_STORE=aws on AWS
+    /// use easy_envar::{Envar, EnvarSet};
+    ///
+    /// EnvarSet::new()
+    ///     .var(Envar::secret_string("DB_PASSWORD").from_secret_store("db-password"))
+    ///     .load()
+    ///     .unwrap();
+    /// ```
+    #[cfg(any(feature = "aws", feature = "azure", feature = "gcp"))]
+    pub fn from_secret_store(mut self, name: impl Into<String>) -> Self {
+        self.remote = Some(crate::remote::Remote::selected(name));
+        self
+    }
+
+    /// Returns where the value is fetched from when it isn't set, if anywhere.
+    #[cfg(feature = "remote")]
+    pub(crate) fn remote(&self) -> Option<&crate::remote::Remote> {
+        self.remote.as_ref()
+    }
+
+    /// Returns the underlying `Envar`.
+    pub fn envar(&self) -> &Envar<'a> {
+        &self.envar
+    }
+
+    /// Returns the environment variable name.
+    pub fn key(&self) -> &str {
+        self.envar.key()
+    }
+
+    /// Returns the raw default value for the current profile, if any: the one given to `or_default_in`
+    /// for `Profile::current`, or else the one given to `or_default`.
+    pub fn default(&self) -> Option<&str> {
+        let profile = Profile::current();
+
+        self.profile_defaults.iter()
+            .find(|(set, _)| *set == profile)
+            .map(|(_, default)| default.as_str())
+            .or(self.default.as_deref())
+    }
+
+    /// Returns the description, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns how a missing value is handled.
+    pub fn load_level(&self) -> LoadLevel {
+        self.level.unwrap_or_default()
+    }
+
+    /// Returns whether a missing value fails the current build:
+    /// when the level is `LoadLevel::Required` and every condition, such as `required_if_feature`, `required_on`, or `required_in`, holds.
+    ///
+    /// A variable with a default never fails when missing, whatever this returns.
+    pub fn is_required(&self) -> bool {
+        self.load_level() == LoadLevel::Required && self.conditions.iter().all(Condition::holds)
+    }
+
+    /// Adds a condition for the variable to be required.
+    pub(crate) fn required_if(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Applies the settings of a group: prefixes the name and aliases, prefixes the description with the group's,
+    /// and sets the level, unless one was set on the variable, and secrecy.
+    pub(crate) fn grouped(mut self, prefix: &str, description: Option<&str>, level: Option<LoadLevel>, secret: bool) -> Self {
+        if !prefix.is_empty() {
+            self.envar.set_key(format!("{}{}", prefix, self.key()));
+            self.aliases = self.aliases.iter().map(|alias| format!("{}{}", prefix, alias)).collect();
+        }
+
+        if let Some(group) = description {
+            self.description = Some(match self.description.take() {
+                Some(description) => format!("{}: {}", group, description),
+                None => group.to_string(),
+            });
+        }
+
+        self.level = self.level.or(level);
+        self.secret |= secret;
+        self
+    }
+
+    /// Returns the conditions under which the variable is required.
+    pub(crate) fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+
+    /// Returns whether the value is masked, either through `secret` or because it is an `Envar::Secret`.
+    pub fn is_secret(&self) -> bool {
+        self.secret || matches!(self.envar, Envar::Secret(_))
+    }
+
+    /// Returns the deprecated former names this variable is also loaded from.
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// Returns whether the value is trimmed before parsing, either through `trim` or by default.
+    pub fn trims(&self) -> bool {
+        self.trim.unwrap_or_else(|| self.envar.trims_by_default())
+    }
+
+    /// Returns the keys tried, in order, when the variable is not set.
+    pub fn fallbacks(&self) -> &[Cow<'a, str>] {
+        &self.fallbacks
+    }
+
+    /// Returns the key the value is read from in `source`: the variable's own name,
+    /// a deprecated alias, or a fallback key. Returns `None` if none of them is set.
+    pub fn matched_key(&self, source: &dyn Source) -> Option<String> {
+        self.raw(source, "", Diagnostics::Silent).ok().map(|(key, _)| key)
+    }
+
+    /// Returns the constraints applied to the parsed value.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Loads the environment variable's value from the system environment,
+    /// then attempts to parse it into the corresponding data type.
+    ///
+    /// If the variable is not set and a default was given, the default is used instead.
+    /// Warnings, such as for a deprecated name, are printed as `cargo:warning` directives in a build script,
+    /// and to stderr otherwise, so they don't end up in a program's output.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(LoadedEnvar)`: if the value (or default) is successfully retrieved and parsed.
+    /// - `Err(EnvarError::Missing)`: if the environment variable is not set and has no default.
+    /// - `Err(EnvarError::NotUnicode)`: if the value is not valid unicode.
+    /// - `Err(EnvarError::ParseError)`: if the value (or default) is invalid for the expected type.
+    /// - `Err(EnvarError::ConstraintViolation)`: if the parsed value violates a constraint.
+    pub fn load(&self) -> Result<LoadedEnvar<'a>, EnvarError> {
+        self.load_from(&ProcessEnv)
+    }
+
+    /// Loads like `load`, but reads the value from `source` instead of the process environment.
+    pub fn load_from(&self, source: &dyn Source) -> Result<LoadedEnvar<'a>, EnvarError> {
+        #[cfg(feature = "remote")]
+        let source = &crate::remote::Layered::resolve(std::slice::from_ref(self), "", source, crate::remote::FetchOptions::default());
+
+        self.load_prefixed(source, "", Diagnostics::detect())
+    }
+
+    /// Loads like `load`, but looks the value up under `prefix` followed by the variable name.
+    ///
+    /// The loaded variable keeps its declared (unprefixed) name.
+    pub(crate) fn load_prefixed(&self, source: &dyn Source, prefix: &str, diagnostics: Diagnostics) -> Result<LoadedEnvar<'a>, EnvarError> {
+        #[cfg(feature = "tracing")]
+        let _span = crate::trace::var_span(self.key(), &self.envar.type_name()).entered();
+
+        let (lookup, raw, defaulted) = match (self.raw(source, prefix, diagnostics), self.default()) {
+            (Err(EnvarError::Missing { key, .. }), Some(default)) => {
+                if self.load_level() == LoadLevel::Warn {
+                    diagnostics.warn(format_args!("{} not set, using default", key));
+                }
+                (key, default.to_string(), true)
+            },
+            (raw, _) => {
+                let (key, raw) = raw.map_err(|e| self.annotate(e))?;
+                (key, raw, false)
+            },
+        };
+
+        // Bad values are reported with the `.env` line that defines them, unless they are defaults.
+        let location = |key: &str| match defaulted {
+            true => None,
+            false => source.location(key),
+        };
+
+        let raw = match self.expand {
+            true => crate::expand::expand(source, &lookup, &raw).map_err(|e| self.annotate(e))?,
+            false => raw,
+        };
+
+        let raw = match self.trims() {
+            true => crate::trim_value(&raw).to_string(),
+            false => raw,
+        };
+
+        let raw = match self.envar.is_integer() {
+            true => self.number_format.normalize(&raw).unwrap_or(raw),
+            false => raw,
+        };
+
+        let mut loaded = self.envar.parse_raw(&lookup, raw.clone())
+            .map_err(|e| self.annotate(e).with_location(location(&lookup)))?;
+
+        // Transforms see a value of the right type, and must leave one.
+        let raw = match self.transforms.is_empty() {
+            true => raw,
+            false => {
+                let mapped = self.transforms.iter().fold(raw, |raw, transform| (transform.0)(&raw));
+                loaded = self.envar.parse_raw(&lookup, mapped.clone()).map_err(|e| self.annotate(e))?;
+                mapped
+            },
+        };
+
+        for constraint in &self.constraints {
+            if let Err(violation) = (constraint.check)(&raw, &loaded) {
+                return Err(self.annotate(EnvarError::ConstraintViolation {
+                    location: location(&lookup),
+                    key: lookup,
+                    raw,
+                    constraint: violation,
+                    description: None,
+                }));
+            }
+        }
+
+        // Strings become `Secret`s, while values of other types keep their type and are only marked secret.
+        let mut loaded = match loaded {
+            LoadedEnvar::String(key, val) if self.secret => LoadedEnvar::Secret(key, Secret::new(val)),
+            mut loaded => {
+                if self.secret {
+                    loaded.set_secret();
+                }
+                loaded
+            },
+        };
+
+        loaded.set_origin(match defaulted {
+            true => Origin::Default,
+            false => source.origin(&lookup),
+        });
+
+        #[cfg(feature = "tracing")]
+        crate::trace::loaded(&loaded);
+
+        Ok(loaded)
+    }
+
+    /// Reads the raw value under the prefixed name, falling back to the deprecated aliases,
+    /// then to the `or_env` keys, then to differently-cased names if `case_insensitive` is set.
+    /// Returns the key that matched together with its value.
+    pub(crate) fn raw(&self, source: &dyn Source, prefix: &str, diagnostics: Diagnostics) -> Result<(String, String), EnvarError> {
+        let lookup = format!("{}{}", prefix, self.key());
+
+        match source.var(&lookup) {
+            Err(EnvarError::Missing { .. }) => {},
+            raw => return raw.map(|raw| (lookup, raw)),
+        }
+
+        for alias in &self.aliases {
+            let alias = format!("{}{}", prefix, alias);
+
+            match source.var(&alias) {
+                Err(EnvarError::Missing { .. }) => {},
+                raw => {
+                    diagnostics.warn(format_args!("{} is deprecated, rename it to {}", alias, lookup));
+                    return raw.map(|raw| (alias, raw));
+                },
+            }
+        }
+
+        for fallback in &self.fallbacks {
+            match source.var(fallback) {
+                Err(EnvarError::Missing { .. }) => {},
+                raw => return raw.map(|raw| (fallback.to_string(), raw)),
+            }
+        }
+
+        if self.case_insensitive {
+            let names = source.names();
+            let candidates = std::iter::once(lookup.clone())
+                .chain(self.aliases.iter().map(|alias| format!("{}{}", prefix, alias)))
+                .chain(self.fallbacks.iter().map(|fallback| fallback.to_string()));
+
+            for candidate in candidates {
+                if let Some(name) = names.iter().find(|name| name.eq_ignore_ascii_case(&candidate)) {
+                    diagnostics.warn(format_args!("{} not set, using {} instead", candidate, name));
+                    return source.var(name).map(|raw| (name.clone(), raw));
+                }
+            }
+        }
+
+        Err(EnvarError::Missing { key: lookup, description: None, suggestion: None }.with_suggestion(source))
+    }
+
+    /// Attaches this variable's description to `error`, and masks the reported value if it is secret.
+    fn annotate(&self, error: EnvarError) -> EnvarError {
+        let error = error.with_description(self.description());
+
+        if !self.is_secret() {
+            return error;
+        }
+
+        match error {
+            EnvarError::ParseError { key, expected_type, description, location, .. } => {
+                EnvarError::ParseError { key, raw: "***".to_string(), expected_type, description, location }
+            },
+            EnvarError::ConstraintViolation { key, constraint, description, location, .. } => {
+                EnvarError::ConstraintViolation { key, raw: "***".to_string(), constraint, description, location }
+            },
+            e => e,
+        }
+    }
+
+    /// Loads like `load_prefixed`, but skips a missing `LoadLevel::Warn` variable with a warning,
+    /// and a missing variable whose conditions don't hold silently.
+    pub(crate) fn load_lenient(&self, source: &dyn Source, prefix: &str, diagnostics: Diagnostics) -> Result<Option<LoadedEnvar<'a>>, EnvarError> {
+        match self.load_prefixed(source, prefix, diagnostics) {
+            Ok(loaded) => Ok(Some(loaded)),
+            Err(EnvarError::Missing { key, .. }) if self.load_level() == LoadLevel::Warn => {
+                diagnostics.warn(format_args!("{} not set, skipping", key));
+                Ok(None)
+            },
+            Err(EnvarError::Missing { .. }) if !self.is_required() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+
+impl<'a> From<Envar<'a>> for EnvarSpec<'a> {
+    fn from(envar: Envar<'a>) -> Self {
+        EnvarSpec {
+            envar,
+            default: None,
+            profile_defaults: Vec::new(),
+            constraints: Vec::new(),
+            transforms: Vec::new(),
+            level: None,
+            conditions: Vec::new(),
+            secret: false,
+            expand: false,
+            description: None,
+            aliases: Vec::new(),
+            fallbacks: Vec::new(),
+            case_insensitive: false,
+            trim: None,
+            number_format: NumberFormat::new(),
+            #[cfg(feature = "remote")]
+            remote: None,
+        }
+    }
+}
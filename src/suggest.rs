@@ -0,0 +1,48 @@
+/// Finds the name in `names` most likely to be a misspelling of `key`, for did-you-mean hints.
+///
+/// Names are compared ignoring ASCII case, and a name matches if it is within two edits of `key`,
+/// counting a swap of adjacent characters as one edit. Keys of four characters or fewer
+/// only match within one edit, so short names don't suggest unrelated variables.
+/// Ties are broken alphabetically, so the suggestion doesn't depend on the source's order.
+pub(crate) fn closest(key: &str, names: &[String]) -> Option<String> {
+    let max_distance = if key.chars().count() <= 4 { 1 } else { 2 };
+    let upper = key.to_ascii_uppercase();
+
+    names.iter()
+        .filter(|name| name.as_str() != key)
+        .map(|name| (distance(&upper, &name.to_ascii_uppercase()), name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, name)| name.clone())
+}
+
+
+/// Counts the insertions, deletions, substitutions, and adjacent swaps needed to turn `a` into `b`.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // rows[i][j] is the distance between the first i characters of `a` and the first j of `b`.
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    rows[0] = (0..=b.len()).collect();
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            rows[i][j] = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
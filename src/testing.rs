@@ -0,0 +1,173 @@
+//! Helpers for testing code that loads environment variables.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{EnvarError, Source};
+
+
+/// Serializes access to the process environment across `EnvGuard`s.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+
+/// Sets process environment variables until dropped, then restores their previous state.
+///
+/// Created by `set_vars`. The previous state is restored even if a test panics.
+/// Holding a guard also blocks every other `set_vars` and `with_vars` call,
+/// so tests using them don't race each other. Don't create a second guard on the same thread
+/// while one is alive, as that deadlocks.
+#[must_use = "the variables are restored as soon as the guard is dropped"]
+pub struct EnvGuard {
+    saved: Vec<(String, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+
+impl std::fmt::Debug for EnvGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnvGuard").field("keys", &self.saved.iter().map(|(key, _)| key).collect::<Vec<_>>()).finish()
+    }
+}
+
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (key, val) in self.saved.drain(..).rev() {
+            match val {
+                Some(val) => std::env::set_var(&key, val),
+                None => std::env::remove_var(&key),
+            }
+        }
+    }
+}
+
+
+/// Sets the given process environment variables, returning a guard that restores them when dropped.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::testing;
+///
+/// {
+///     let _guard = testing::set_vars([("SET_VARS_DOC_PORT", "8080")]);
+///     assert_eq!(std::env::var("SET_VARS_DOC_PORT").unwrap(), "8080");
+/// }
+///
+/// assert!(std::env::var("SET_VARS_DOC_PORT").is_err());
+/// ```
+pub fn set_vars<I, K, V>(vars: I) -> EnvGuard
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<OsString>,
+{
+    // A panicking test poisons the lock, but the guard has already restored the environment.
+    let lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut saved = Vec::new();
+
+    for (key, val) in vars {
+        let key = key.into();
+        saved.push((key.clone(), std::env::var_os(&key)));
+        std::env::set_var(&key, val.into());
+    }
+
+    EnvGuard { saved, _lock: lock }
+}
+
+
+/// Runs `f` with the given process environment variables set, then restores their previous state,
+/// even if `f` panics.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{testing, Envar, LoadedEnvar};
+///
+/// let loaded = testing::with_vars([("WITH_VARS_DOC_PORT", "8080")], || {
+///     Envar::u16("WITH_VARS_DOC_PORT").load().unwrap()
+/// });
+///
+/// assert!(matches!(loaded, LoadedEnvar::U16(_, 8080)));
+/// assert!(std::env::var("WITH_VARS_DOC_PORT").is_err());
+/// ```
+pub fn with_vars<I, K, V, F, R>(vars: I, f: F) -> R
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<OsString>,
+    F: FnOnce() -> R,
+{
+    let _guard = set_vars(vars);
+    f()
+}
+
+
+/// An in-memory source for tests, which never touches the process environment.
+///
+/// The process environment is shared by every test thread,
+/// so setting variables in parallel tests is racy. A `MockEnv` is not.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::testing::MockEnv;
+/// use easy_envar::{Envar, EnvarError, LoadedEnvar};
+///
+/// let env = MockEnv::new().var("PORT", "8080");
+///
+/// assert!(matches!(Envar::u16("PORT").load_from(&env).unwrap(), LoadedEnvar::U16(_, 8080)));
+/// assert!(matches!(Envar::u16("HOST").load_from(&env), Err(EnvarError::Missing { .. })));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockEnv {
+    vars: HashMap<String, String>,
+}
+
+
+impl MockEnv {
+    /// Creates an empty environment.
+    pub fn new() -> Self {
+        MockEnv::default()
+    }
+
+    /// Sets a variable.
+    pub fn var(mut self, key: impl Into<String>, val: impl Into<String>) -> Self {
+        self.vars.insert(key.into(), val.into());
+        self
+    }
+
+    /// Sets a variable in place.
+    pub fn set(&mut self, key: impl Into<String>, val: impl Into<String>) {
+        self.vars.insert(key.into(), val.into());
+    }
+
+    /// Removes a variable in place.
+    pub fn remove(&mut self, key: &str) {
+        self.vars.remove(key);
+    }
+}
+
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for MockEnv {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        MockEnv {
+            vars: iter.into_iter().map(|(key, val)| (key.into(), val.into())).collect(),
+        }
+    }
+}
+
+
+impl Source for MockEnv {
+    fn var(&self, key: &str) -> Result<String, EnvarError> {
+        self.vars.var(key)
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.vars.names()
+    }
+}
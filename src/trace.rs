@@ -0,0 +1,36 @@
+//! Structured `tracing` spans and events for the load process.
+
+use tracing::Span;
+
+use crate::{EnvarError, LoadedEnvar};
+
+
+/// Creates the span covering the load of a whole set.
+pub(crate) fn set_span(prefix: &str, len: usize) -> Span {
+    tracing::info_span!("load_envar_set", prefix, vars = len)
+}
+
+
+/// Creates the span covering the load of a single variable.
+pub(crate) fn var_span(key: &str, type_name: &str) -> Span {
+    tracing::debug_span!("load_envar", key, r#type = type_name)
+}
+
+
+/// Emits an event for a loaded variable, with its value masked as `***` if it is secret.
+pub(crate) fn loaded(env_var: &LoadedEnvar) {
+    let value = match env_var.is_secret() {
+        true => "***".to_string(),
+        false => env_var.value_string(),
+    };
+
+    let origin = env_var.origin().map(|origin| origin.to_string());
+
+    tracing::info!(key = env_var.key(), origin = origin.as_deref(), value = %value, "loaded environment variable");
+}
+
+
+/// Emits an event for a variable that failed to load.
+pub(crate) fn failed(error: &EnvarError) {
+    tracing::warn!(error = %error, "failed to load environment variable");
+}
@@ -0,0 +1,129 @@
+use crate::{LoadedEnvar, Secret};
+
+
+/// The typed value of a loaded environment variable, without its name.
+///
+/// Created by `EnvarSet::load_map`, or from a `LoadedEnvar` with `LoadedValue::from`.
+/// Integers, floats, addresses, durations, bytes, and lists keep their types, grouped into a few variants,
+/// and every other variable, such as a URL or a version, becomes its exported string (see `LoadedEnvar::value_string`).
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use easy_envar::{LoadedEnvar, LoadedValue};
+///
+/// let port = LoadedValue::from(LoadedEnvar::U16("PORT".into(), 8080));
+/// assert!(matches!(port, LoadedValue::UInt(8080)));
+///
+/// let hosts = LoadedValue::from(LoadedEnvar::List("HOSTS".into(), vec!["a".to_string(), "b".to_string()]));
+/// assert_eq!(hosts.to_string(), "a,b");
+/// ```
+#[derive(Debug, Clone)]
+pub enum LoadedValue {
+    /// A `bool` value.
+    Bool(bool),
+
+    /// An unsigned integer, from any unsigned or non-zero integer variable, or an `Envar::ByteSize` in bytes.
+    UInt(u128),
+
+    /// A signed integer, from any signed integer variable.
+    Int(i128),
+
+    /// A float, from an `f32` or `f64` variable, or an `Envar::Percent` as a fraction.
+    ///
+    /// An `f32` keeps its shortest decimal form, so `0.1` stays `0.1` rather than `0.10000000149011612`.
+    Float(f64),
+
+    /// A `char` value.
+    Char(char),
+
+    /// An IP address.
+    IpAddr(std::net::IpAddr),
+
+    /// A socket address.
+    SocketAddr(std::net::SocketAddr),
+
+    /// A duration.
+    Duration(std::time::Duration),
+
+    /// A path.
+    Path(std::path::PathBuf),
+
+    /// Decoded bytes, from an `Envar::Hex` or `Envar::Base64` variable.
+    Bytes(Vec<u8>),
+
+    /// The trimmed items of an `Envar::List` or `Envar::ListOf` variable.
+    List(Vec<String>),
+
+    /// A secret value, masked in `Debug` and `Display` output.
+    Secret(Secret),
+
+    /// Any other value, as exported.
+    String(String),
+}
+
+
+impl From<LoadedEnvar<'_>> for LoadedValue {
+    fn from(env_var: LoadedEnvar<'_>) -> Self {
+        match env_var {
+            env_var if env_var.is_secret() && !matches!(env_var, LoadedEnvar::Secret(..)) => {
+                LoadedValue::Secret(Secret::new(env_var.value_string()))
+            },
+            LoadedEnvar::Bool(_, val)       => LoadedValue::Bool(val),
+            LoadedEnvar::U8(_, val)         => LoadedValue::UInt(val.into()),
+            LoadedEnvar::U16(_, val)        => LoadedValue::UInt(val.into()),
+            LoadedEnvar::U32(_, val)        => LoadedValue::UInt(val.into()),
+            LoadedEnvar::U64(_, val)        => LoadedValue::UInt(val.into()),
+            LoadedEnvar::U128(_, val)       => LoadedValue::UInt(val),
+            LoadedEnvar::Usize(_, val)      => LoadedValue::UInt(val as u128),
+            LoadedEnvar::I8(_, val)         => LoadedValue::Int(val.into()),
+            LoadedEnvar::I16(_, val)        => LoadedValue::Int(val.into()),
+            LoadedEnvar::I32(_, val)        => LoadedValue::Int(val.into()),
+            LoadedEnvar::I64(_, val)        => LoadedValue::Int(val.into()),
+            LoadedEnvar::I128(_, val)       => LoadedValue::Int(val),
+            LoadedEnvar::Isize(_, val)      => LoadedValue::Int(val as i128),
+            LoadedEnvar::F32(_, val)        => LoadedValue::Float(val.to_string().parse().unwrap_or(val.into())),
+            LoadedEnvar::F64(_, val)        => LoadedValue::Float(val),
+            LoadedEnvar::Char(_, val)       => LoadedValue::Char(val),
+            LoadedEnvar::NonZeroU16(_, val) => LoadedValue::UInt(val.get().into()),
+            LoadedEnvar::NonZeroU32(_, val) => LoadedValue::UInt(val.get().into()),
+            LoadedEnvar::NonZeroU64(_, val) => LoadedValue::UInt(val.get().into()),
+            LoadedEnvar::IpAddr(_, val)     => LoadedValue::IpAddr(val),
+            LoadedEnvar::SocketAddr(_, val) => LoadedValue::SocketAddr(val),
+            LoadedEnvar::Duration(_, val)   => LoadedValue::Duration(val),
+            LoadedEnvar::ByteSize(_, val)   => LoadedValue::UInt(val.into()),
+            LoadedEnvar::Percent(_, val)    => LoadedValue::Float(val),
+            LoadedEnvar::Hex(_, val)        => LoadedValue::Bytes(val),
+            #[cfg(feature = "base64")]
+            LoadedEnvar::Base64(_, val)     => LoadedValue::Bytes(val),
+            LoadedEnvar::Path(_, val)       => LoadedValue::Path(val),
+            LoadedEnvar::Secret(_, val)     => LoadedValue::Secret(val),
+            LoadedEnvar::List(_, val)       => LoadedValue::List(val),
+            env_var => LoadedValue::String(env_var.value_string()),
+        }
+    }
+}
+
+
+/// Formats the value like `LoadedEnvar::value_string`, except that bytes are always formatted as lowercase hex,
+/// and `Secret` values are masked as `***`.
+impl std::fmt::Display for LoadedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadedValue::Bool(val)       => write!(f, "{}", val),
+            LoadedValue::UInt(val)       => write!(f, "{}", val),
+            LoadedValue::Int(val)        => write!(f, "{}", val),
+            LoadedValue::Float(val)      => write!(f, "{}", val),
+            LoadedValue::Char(val)       => write!(f, "{}", val),
+            LoadedValue::IpAddr(val)     => write!(f, "{}", val),
+            LoadedValue::SocketAddr(val) => write!(f, "{}", val),
+            LoadedValue::Duration(val)   => write!(f, "{}", val.as_millis()),
+            LoadedValue::Path(val)       => write!(f, "{}", val.display()),
+            LoadedValue::Bytes(val)      => val.iter().try_for_each(|byte| write!(f, "{:02x}", byte)),
+            LoadedValue::List(val)       => write!(f, "{}", val.join(",")),
+            LoadedValue::Secret(val)     => write!(f, "{}", val),
+            LoadedValue::String(val)     => write!(f, "{}", val),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::remote::{env_context, not_installed, parallel, Failure, RemoteErrorKind};
+
+
+/// Fetches fields of KV v2 secrets from Vault with the `vault` command, reading each secret once
+/// and up to `concurrency` secrets at once.
+///
+/// Each id is a secret path and a field, joined by `#`, such as `secret/app/db#password`.
+/// The server is taken from `VAULT_ADDR` as usual. The command authenticates with `VAULT_TOKEN` if it is set,
+/// or else logs in with AppRole using `VAULT_ROLE_ID` and `VAULT_SECRET_ID`.
+pub(crate) fn fetch(ids: &[&str], concurrency: usize) -> HashMap<String, Result<String, Failure>> {
+    let token = match token() {
+        Ok(token) => token,
+        Err(failure) => return ids.iter().map(|id| (id.to_string(), Err(failure.clone()))).collect(),
+    };
+
+    let mut fields: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for id in ids {
+        let (path, field) = id.rsplit_once('#').unwrap_or((id, ""));
+        fields.entry(path).or_default().push((id, field));
+    }
+
+    let fields: Vec<(&str, Vec<(&str, &str)>)> = fields.into_iter().collect();
+    let responses = parallel(&fields, concurrency, |(path, _)| get(path, token.as_deref()));
+    let mut values = HashMap::new();
+
+    for ((path, fields), response) in fields.into_iter().zip(responses) {
+        match response {
+            Ok(data) => values.extend(fields.into_iter().map(|(id, field)| {
+                let value = match &data[field] {
+                    serde_json::Value::String(val) => Ok(val.clone()),
+                    serde_json::Value::Null => Err(Failure {
+                        kind: RemoteErrorKind::NotFound,
+                        message: format!("secret `{}` has no field `{}`", path, field),
+                    }),
+                    val => Ok(val.to_string()),
+                };
+                (id.to_string(), value)
+            })),
+            Err(failure) => values.extend(fields.into_iter().map(|(id, _)| (id.to_string(), Err(failure.clone())))),
+        }
+    }
+
+    values
+}
+
+
+/// Returns the server and namespace the `vault` command reads from.
+pub(crate) fn context() -> String {
+    env_context(&["VAULT_ADDR", "VAULT_NAMESPACE"])
+}
+
+
+/// Returns the token to authenticate with: `None` to let the command use `VAULT_TOKEN`,
+/// or a token from an AppRole login with `VAULT_ROLE_ID` and `VAULT_SECRET_ID`.
+fn token() -> Result<Option<String>, Failure> {
+    if std::env::var_os("VAULT_TOKEN").is_some() {
+        return Ok(None);
+    }
+
+    let (Ok(role_id), Ok(secret_id)) = (std::env::var("VAULT_ROLE_ID"), std::env::var("VAULT_SECRET_ID")) else {
+        return Err(Failure {
+            kind: RemoteErrorKind::Unauthorized,
+            message: "neither `VAULT_TOKEN` nor `VAULT_ROLE_ID` and `VAULT_SECRET_ID` are set".to_string(),
+        });
+    };
+
+    // The secret id is passed on stdin, so it never appears in the process list.
+    let mut child = Command::new("vault")
+        .args(["write", "-format=json", "auth/approle/login"])
+        .arg(format!("role_id={}", role_id))
+        .arg("secret_id=-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| not_installed("vault", "Vault CLI", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(secret_id.as_bytes()).map_err(unavailable)?;
+    }
+
+    let response = json(child.wait_with_output().map_err(unavailable)?)?;
+
+    match response["auth"]["client_token"].as_str() {
+        Some(token) => Ok(Some(token.to_string())),
+        None => Err(Failure { kind: RemoteErrorKind::Unauthorized, message: "the AppRole login returned no token".to_string() }),
+    }
+}
+
+
+/// Reads the latest version of the secret at `path`, returning its fields.
+fn get(path: &str, token: Option<&str>) -> Result<serde_json::Value, Failure> {
+    let mut command = Command::new("vault");
+    command.args(["kv", "get", "-format=json", path]);
+
+    if let Some(token) = token {
+        command.env("VAULT_TOKEN", token);
+    }
+
+    let mut response = json(command.output().map_err(|e| not_installed("vault", "Vault CLI", e))?)?;
+    Ok(response["data"]["data"].take())
+}
+
+
+/// Parses the JSON printed by a successful command, or classifies its failure.
+fn json(output: std::process::Output) -> Result<serde_json::Value, Failure> {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Failure { kind: classify(&stderr), message: stderr.trim().to_string() });
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|e| Failure {
+        kind: RemoteErrorKind::Unavailable,
+        message: format!("unexpected response from `vault`: {}", e),
+    })
+}
+
+
+/// Tells authentication failures and missing paths apart from the command's error output.
+fn classify(stderr: &str) -> RemoteErrorKind {
+    if stderr.contains("permission denied") || stderr.contains("Code: 403") || stderr.contains("missing client token") {
+        RemoteErrorKind::Unauthorized
+    } else if stderr.contains("No value found at") || stderr.contains("Code: 404") {
+        RemoteErrorKind::NotFound
+    } else {
+        RemoteErrorKind::Unavailable
+    }
+}
+
+
+/// Reports an I/O failure while talking to the command.
+fn unavailable(e: std::io::Error) -> Failure {
+    Failure { kind: RemoteErrorKind::Unavailable, message: e.to_string() }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use notify::Watcher;
+
+use crate::spec::Diagnostics;
+use crate::{DotenvFile, EnvarConfig, EnvarError, EnvarSet, Source, SourceDiff, SourceStack};
+
+
+/// How long to wait for more events after a change, so saving a file reloads it once.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+
+/// A function called with the changed variables after a reload.
+type Callback = Box<dyn Fn(&SourceDiff) + Send>;
+
+
+/// Options for watching a `.env` file, created by `EnvarSet::watch`.
+///
+/// Requires the `watch` feature.
+pub struct Watch {
+    set: EnvarSet<'static>,
+    path: PathBuf,
+    callbacks: Vec<Callback>,
+}
+
+
+/// A runtime configuration that is reloaded whenever its `.env` file changes, or the process receives `SIGHUP`.
+///
+/// Created by `Watch::start`. Watching stops when the handle is dropped.
+/// Requires the `watch` feature.
+///
+/// ***
+/// # Examples
+///
+/// ```rust
+/// use std::sync::mpsc;
+/// use std::time::Duration;
+/// use easy_envar::{Envar, EnvarSet};
+///
+/// let path = std::env::temp_dir().join("watched_config_doc.env");
+/// std::fs::write(&path, "WATCH_DOC_WORKERS=4\n").unwrap();
+///
+/// let (tx, rx) = mpsc::channel();
+/// let config = EnvarSet::new()
+///     .var(Envar::u8("WATCH_DOC_WORKERS"))
+///     .watch(&path)
+///     .on_change(move |diff| tx.send(diff.to_string()).unwrap())
+///     .start()
+///     .unwrap();
+///
+/// assert_eq!(config.current().get_as::<u8>("WATCH_DOC_WORKERS"), Some(4));
+///
+/// std::fs::write(&path, "WATCH_DOC_WORKERS=8\n").unwrap();
+///
+/// // Reload right away, rather than waiting for the file event.
+/// config.reload().unwrap();
+///
+/// assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), "~ WATCH_DOC_WORKERS: 4 -> 8\n");
+/// assert_eq!(config.current().get_as::<u8>("WATCH_DOC_WORKERS"), Some(8));
+/// ```
+pub struct WatchedConfig {
+    shared: Arc<Shared>,
+    _watcher: notify::RecommendedWatcher,
+    #[cfg(unix)]
+    signals: signal_hook::iterator::Handle,
+}
+
+
+/// The state shared by the handle and the thread reloading it.
+struct Shared {
+    set: EnvarSet<'static>,
+    path: PathBuf,
+    /// The process environment when watching started, without the values it got from the `.env` file.
+    env: HashMap<String, String>,
+    config: RwLock<Arc<EnvarConfig<'static>>>,
+    /// Held while reloading, so reloads run one at a time.
+    reloading: Mutex<Reloading>,
+}
+
+
+/// What a reload compares against and reports to.
+struct Reloading {
+    /// The raw value of each declared variable, as of the last reload.
+    inputs: HashMap<String, String>,
+    callbacks: Vec<Callback>,
+}
+
+
+impl EnvarSet<'static> {
+    /// Loads the set from the `.env` file at `path` and the process environment, and keeps it up to date as the file changes.
+    ///
+    /// As with `init`, a variable set in the environment takes precedence over the file.
+    /// Variables the environment only got from the file, such as by calling `init`, are read from the file,
+    /// so editing them takes effect. Call `Watch::start` to load and start watching.
+    /// Requires the `watch` feature.
+    pub fn watch(self, path: impl AsRef<Path>) -> Watch {
+        Watch { set: self, path: path.as_ref().to_path_buf(), callbacks: Vec::new() }
+    }
+}
+
+
+impl Watch {
+    /// Calls `callback` with the changed variables whenever a reload changes any, with secrets masked.
+    ///
+    /// The callback runs on the watching thread, and should return quickly.
+    pub fn on_change(mut self, callback: impl Fn(&SourceDiff) + Send + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Loads the set, then watches the `.env` file for changes and, on Unix, listens for `SIGHUP`.
+    ///
+    /// A reload that fails, such as one that finds an invalid value, keeps the previous configuration
+    /// and prints the errors to stderr.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(WatchedConfig)`: the loaded configuration, kept up to date until it is dropped.
+    /// - `Err(Vec<EnvarError>)`: every error encountered while loading,
+    ///   or a single `EnvarError::Watch` if the file can't be watched.
+    pub fn start(self) -> Result<WatchedConfig, Vec<EnvarError>> {
+        let env = environment(&self.path);
+        let shared = Arc::new(Shared {
+            set: self.set,
+            path: self.path,
+            env,
+            config: RwLock::new(Arc::new(EnvarConfig::default())),
+            reloading: Mutex::new(Reloading { inputs: HashMap::new(), callbacks: Vec::new() }),
+        });
+
+        shared.reload()?;
+        shared.lock().callbacks = self.callbacks;
+
+        let error = |message: String| vec![EnvarError::Watch { path: shared.path.clone(), message }];
+        let (tx, rx) = mpsc::channel();
+
+        // Editors often save by replacing the file, so its directory is watched rather than the file itself.
+        let file_name = shared.path.file_name().map(ToOwned::to_owned);
+        let dir = match shared.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let events = tx.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let changed = event.is_ok_and(|event| {
+                !event.kind.is_access() && event.paths.iter().any(|path| path.file_name() == file_name.as_deref())
+            });
+            if changed {
+                let _ = events.send(());
+            }
+        }).map_err(|e| error(e.to_string()))?;
+
+        watcher.watch(&dir, notify::RecursiveMode::NonRecursive).map_err(|e| error(e.to_string()))?;
+
+        #[cfg(unix)]
+        let signals = {
+            let mut signals = signal_hook::iterator::Signals::new([signal_hook::consts::SIGHUP])
+                .map_err(|e| error(e.to_string()))?;
+            let handle = signals.handle();
+
+            std::thread::spawn(move || {
+                for _ in signals.forever() {
+                    let _ = tx.send(());
+                }
+            });
+
+            handle
+        };
+
+        let reloader = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            // Ends once the watcher and the signal thread are gone, when the handle is dropped.
+            while rx.recv().is_ok() {
+                while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+                if let Err(errors) = reloader.reload() {
+                    for e in errors {
+                        eprintln!("error: failed to reload `{}`: {}", reloader.path.display(), e);
+                    }
+                }
+            }
+        });
+
+        Ok(WatchedConfig {
+            shared,
+            _watcher: watcher,
+            #[cfg(unix)]
+            signals,
+        })
+    }
+}
+
+
+impl WatchedConfig {
+    /// Returns the configuration as of the last successful reload.
+    ///
+    /// The returned configuration doesn't change, so call this again to see later reloads.
+    pub fn current(&self) -> Arc<EnvarConfig<'static>> {
+        Arc::clone(&self.shared.config.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Reloads the configuration now, without waiting for the file to change.
+    ///
+    /// ***
+    /// # Returns
+    ///
+    /// - `Ok(SourceDiff)`: the changed variables, which are also passed to the `on_change` callbacks if there are any.
+    /// - `Err(Vec<EnvarError>)`: every error encountered, in which case the previous configuration is kept.
+    pub fn reload(&self) -> Result<SourceDiff, Vec<EnvarError>> {
+        self.shared.reload()
+    }
+
+    /// Returns the path of the watched `.env` file.
+    pub fn path(&self) -> &Path {
+        &self.shared.path
+    }
+}
+
+
+impl Drop for WatchedConfig {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        self.signals.close();
+    }
+}
+
+
+impl std::fmt::Debug for WatchedConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatchedConfig").field("path", &self.shared.path).field("config", &self.current()).finish()
+    }
+}
+
+
+impl Shared {
+    /// Loads the set again, replacing the configuration and calling the callbacks if anything changed.
+    fn reload(&self) -> Result<SourceDiff, Vec<EnvarError>> {
+        let mut reloading = self.lock();
+
+        let file = DotenvFile::from_path(&self.path).map_err(|e| vec![e])?;
+        let source = SourceStack::new().with(self.env.clone()).with(file);
+        let config = self.set.load_with(&source, Diagnostics::Stderr)?.into_iter().collect();
+
+        let inputs = inputs(&self.set, &source);
+        let diff = self.set.diff(&reloading.inputs, &inputs);
+
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(config);
+        reloading.inputs = inputs;
+
+        if !diff.is_empty() {
+            for callback in &reloading.callbacks {
+                callback(&diff);
+            }
+        }
+
+        Ok(diff)
+    }
+
+    /// Waits for any other reload to finish.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Reloading> {
+        self.reloading.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+
+/// Returns the process environment, without the variables whose value came from the `.env` file at `path`.
+fn environment(path: &Path) -> HashMap<String, String> {
+    let file = DotenvFile::from_path(path).ok();
+
+    std::env::vars()
+        .filter(|(key, val)| file.as_ref().and_then(|file| file.var(key).ok()).as_ref() != Some(val))
+        .collect()
+}
+
+
+/// Returns the raw value of every declared variable that `source` sets.
+fn inputs(set: &EnvarSet, source: &dyn Source) -> HashMap<String, String> {
+    set.env_vars().iter()
+        .map(|env_var| format!("{}{}", set.prefix(), env_var.key()))
+        .filter_map(|key| Some((key.clone(), source.var(&key).ok()?)))
+        .collect()
+}